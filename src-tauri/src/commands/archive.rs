@@ -0,0 +1,422 @@
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
+use std::fs;
+use std::path::Path;
+use std::sync::Mutex;
+
+use super::boards::{self, BoardConfig};
+use super::dependencies;
+use super::history::{self, DAY_MS};
+use super::merge::{self, task_key};
+use super::tasks::{self, TasksMdFilter, TasksMdTask, TasksMdWriteResult};
+use super::templates::{format_date, local_date};
+
+/// Serializes archive updates from commands and the scheduler thread
+static ARCHIVE_LOCK: Mutex<()> = Mutex::new(());
+
+pub(crate) const ARCHIVE_FILE: &str = "TASKS.archive.md";
+
+/// Which Done tasks move out of a board file. A task is archived when either limit applies.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ArchivePolicy {
+    /// Archive tasks completed more than this many days ago
+    #[serde(rename = "olderThanDays", default)]
+    pub older_than_days: Option<u32>,
+    /// Keep only this many of the most recently completed tasks in Done
+    #[serde(rename = "keepRecent", default)]
+    pub keep_recent: Option<usize>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArchivedTask {
+    pub task: TasksMdTask,
+    /// YYYY-MM heading the task is filed under: the local month it was completed, or archived
+    /// when that is unknown
+    pub month: String,
+    /// Local YYYY-MM-DD; `None` for tasks completed before history was recorded
+    #[serde(rename = "completedOn")]
+    pub completed_on: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArchiveResult {
+    pub archived: Vec<ArchivedTask>,
+    /// `None` when nothing was due for archiving and the board was left alone
+    pub write: Option<TasksMdWriteResult>,
+}
+
+/// Indexes of the Done tasks the policy archives. `completed_at` holds each task's completion
+/// time if history knows it; tasks completed before history was recorded count as the oldest
+/// and are only archived by `keep_recent`.
+fn select_for_archive(
+    tasks: &[TasksMdTask],
+    completed_at: &[Option<u64>],
+    policy: &ArchivePolicy,
+    now: u64,
+) -> Vec<usize> {
+    let mut done: Vec<usize> = (0..tasks.len()).filter(|&i| tasks[i].column == "done").collect();
+    // Most recent first; among equals, later in the file counts as more recent
+    done.sort_by(|&a, &b| completed_at[b].cmp(&completed_at[a]).then(b.cmp(&a)));
+
+    let cutoff = policy
+        .older_than_days
+        .map(|days| now.saturating_sub(u64::from(days) * DAY_MS));
+
+    let mut selected: Vec<usize> = done
+        .iter()
+        .enumerate()
+        .filter(|&(rank, &i)| {
+            let beyond_recent = policy.keep_recent.map(|keep| rank >= keep).unwrap_or(false);
+            let too_old = match (cutoff, completed_at[i]) {
+                (Some(cutoff), Some(at)) => at < cutoff,
+                _ => false,
+            };
+            beyond_recent || too_old
+        })
+        .map(|(_, &i)| i)
+        .collect();
+
+    selected.sort();
+    selected
+}
+
+/// TASKS.archive.md for TASKS.md, `<name>.archive.md` next to any other board file
+fn archive_path(project_path: &str, board: &BoardConfig) -> std::path::PathBuf {
+    if board.is_default() {
+        return Path::new(project_path).join(ARCHIVE_FILE);
+    }
+
+    let path = board.path(project_path);
+    let stem = path
+        .file_stem()
+        .map(|s| s.to_string_lossy().to_string())
+        .unwrap_or_default();
+    path.with_file_name(format!("{}.archive.md", stem))
+}
+
+/// Parse TASKS.archive.md: `## YYYY-MM` headings with `- [x] YYYY-MM-DD subject` entries, or
+/// `- [x] subject` when the completion date is unknown
+pub fn parse_archive(content: &str) -> Result<Vec<ArchivedTask>, String> {
+    let month_re = Regex::new(r"^\d{4}-\d{2}$").unwrap();
+    let dated_re = Regex::new(r"^(\d{4}-\d{2}-\d{2})\s+(.+)$").unwrap();
+
+    let tasks = tasks::parse_checklist(content, None, |level, heading| {
+        (level == 2 && month_re.is_match(heading)).then(|| heading.to_string())
+    })?;
+
+    Ok(tasks
+        .into_iter()
+        .map(|mut task| {
+            let month = std::mem::replace(&mut task.column, "done".to_string());
+            let dated = dated_re
+                .captures(&task.subject)
+                .map(|caps| (caps[1].to_string(), caps[2].to_string()));
+            let completed_on = dated.map(|(date, subject)| {
+                task.subject = subject;
+                date
+            });
+            ArchivedTask {
+                task,
+                month,
+                completed_on,
+            }
+        })
+        .collect())
+}
+
+/// Render the archive with the newest month first, newest task first within a month
+pub fn render_archive(entries: &[ArchivedTask]) -> String {
+    let mut months: BTreeMap<&str, Vec<&ArchivedTask>> = BTreeMap::new();
+    for entry in entries {
+        months.entry(entry.month.as_str()).or_default().push(entry);
+    }
+
+    let mut content = String::from("# TASKS Archive\n\n");
+
+    for (month, mut entries) in months.into_iter().rev() {
+        entries.sort_by(|a, b| b.completed_on.cmp(&a.completed_on));
+
+        content.push_str(&format!("## {}\n", month));
+        for entry in entries {
+            let mut task = entry.task.clone();
+            task.completed = true;
+            let rendered = tasks::render_task(&task);
+            match &entry.completed_on {
+                Some(date) => content.push_str(&rendered.replacen("- [x] ", &format!("- [x] {} ", date), 1)),
+                None => content.push_str(&rendered),
+            }
+        }
+        content.push('\n');
+    }
+
+    content
+}
+
+fn load_archive(project_path: &str, board: &BoardConfig) -> Result<Vec<ArchivedTask>, String> {
+    let path = archive_path(project_path, board);
+
+    if !path.exists() {
+        return Ok(vec![]);
+    }
+
+    let content = fs::read_to_string(&path)
+        .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+
+    parse_archive(&content)
+}
+
+fn save_archive(project_path: &str, board: &BoardConfig, entries: &[ArchivedTask]) -> Result<(), String> {
+    let path = archive_path(project_path, board);
+    fs::write(&path, render_archive(entries))
+        .map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+}
+
+/// Move the Done tasks selected by the policy from a board file into its archive. The archive
+/// is written first and put back if the board can't be, so a failure never loses tasks.
+pub fn archive_done_tasks(
+    project_path: &str,
+    board: &BoardConfig,
+    policy: &ArchivePolicy,
+    now: u64,
+    versions: &merge::TasksMdVersions,
+) -> Result<ArchiveResult, String> {
+    let _guard = ARCHIVE_LOCK.lock().map_err(|e| e.to_string())?;
+
+    let completion_times = history::completion_times(&board.history_key(project_path))?;
+    let completed_at = |tasks: &[TasksMdTask]| -> Vec<Option<u64>> {
+        tasks
+            .iter()
+            .map(|t| completion_times.get(task_key(t)).copied())
+            .collect()
+    };
+
+    // Leave the board untouched when nothing is due
+    let current = tasks::load_board(project_path, board)?;
+    if select_for_archive(&current, &completed_at(&current), policy, now).is_empty() {
+        return Ok(ArchiveResult {
+            archived: vec![],
+            write: None,
+        });
+    }
+
+    let previous = load_archive(project_path, board)?;
+    let mut archived = Vec::new();
+
+    let result = tasks::mutate_board(project_path, board, None, versions, true, |tasks| {
+        let completed_at = completed_at(tasks);
+        let selected = select_for_archive(tasks, &completed_at, policy, now);
+
+        for &i in &selected {
+            let completed_on = completed_at[i].map(format_date);
+            let mut task = tasks[i].clone();
+            task.completed = true;
+            archived.push(ArchivedTask {
+                month: local_date(completed_at[i].unwrap_or(now)).format("%Y-%m").to_string(),
+                completed_on,
+                task,
+            });
+        }
+
+        let mut entries = previous.clone();
+        entries.extend(archived.iter().cloned());
+        save_archive(project_path, board, &entries)?;
+
+        let selected: HashSet<usize> = selected.into_iter().collect();
+        let mut index = 0;
+        tasks.retain(|_| {
+            let keep = !selected.contains(&index);
+            index += 1;
+            keep
+        });
+        Ok(())
+    });
+
+    match result {
+        Ok(write) => Ok(ArchiveResult {
+            archived,
+            write: Some(write),
+        }),
+        Err(e) => {
+            if !archived.is_empty() {
+                if previous.is_empty() {
+                    let _ = fs::remove_file(archive_path(project_path, board));
+                } else {
+                    let _ = save_archive(project_path, board, &previous);
+                }
+            }
+            Err(e)
+        }
+    }
+}
+
+pub fn archive_policy(state: &crate::state::AppState, project_path: &str) -> Option<ArchivePolicy> {
+    state.projects.lock().ok().and_then(|projects| {
+        projects
+            .iter()
+            .find(|p| p.path == project_path)
+            .and_then(|p| p.archive_policy.clone())
+    })
+}
+
+/// Set a project's archive policy; `None` turns automatic archiving off
+#[tauri::command]
+pub fn set_archive_policy(
+    project_id: String,
+    policy: Option<ArchivePolicy>,
+    state: tauri::State<'_, crate::state::AppState>,
+) -> Result<(), String> {
+    let mut projects = state.projects.lock().map_err(|e| e.to_string())?;
+    let project = projects
+        .iter_mut()
+        .find(|p| p.id == project_id)
+        .ok_or("Project not found")?;
+
+    project.archive_policy = policy.filter(|p| p.older_than_days.is_some() || p.keep_recent.is_some());
+
+    crate::state::save_projects(&projects)
+}
+
+/// Archive a board's Done tasks now (TASKS.md unless `board` is given), using `policy` or else
+/// the project's own
+#[tauri::command]
+pub fn archive_tasks(
+    project_path: String,
+    board: Option<String>,
+    policy: Option<ArchivePolicy>,
+    state: tauri::State<'_, crate::state::AppState>,
+    versions: tauri::State<'_, merge::TasksMdVersions>,
+) -> Result<ArchiveResult, String> {
+    let board = boards::board_config(&state, &project_path, board.as_deref())?;
+    let policy = policy
+        .or_else(|| archive_policy(&state, &project_path))
+        .ok_or("No archive policy set for this project")?;
+
+    archive_done_tasks(&project_path, &board, &policy, history::now_ms(), &versions)
+}
+
+/// A board's archived tasks, newest first, optionally filtered by metadata
+#[tauri::command]
+pub fn read_task_archive(
+    project_path: String,
+    board: Option<String>,
+    filter: Option<TasksMdFilter>,
+    state: tauri::State<'_, crate::state::AppState>,
+) -> Result<Vec<ArchivedTask>, String> {
+    let board = boards::board_config(&state, &project_path, board.as_deref())?;
+    let mut entries = load_archive(&project_path, &board)?;
+    entries.sort_by(|a, b| b.completed_on.cmp(&a.completed_on));
+
+    Ok(match filter {
+        Some(filter) => entries.into_iter().filter(|e| filter.matches(&e.task)).collect(),
+        None => entries,
+    })
+}
+
+/// Move an archived task back onto its board, into Done unless `column` is given. `task_id` is
+/// the task's stable id or its subject.
+#[tauri::command]
+pub fn restore_archived_task(
+    project_path: String,
+    board: Option<String>,
+    task_id: String,
+    column: Option<String>,
+    base_version: Option<String>,
+    state: tauri::State<'_, crate::state::AppState>,
+    versions: tauri::State<'_, merge::TasksMdVersions>,
+) -> Result<TasksMdWriteResult, String> {
+    let board = boards::board_config(&state, &project_path, board.as_deref())?;
+    let _guard = ARCHIVE_LOCK.lock().map_err(|e| e.to_string())?;
+
+    let mut entries = load_archive(&project_path, &board)?;
+    let archived_tasks: Vec<TasksMdTask> = entries.iter().map(|e| e.task.clone()).collect();
+    let index = dependencies::resolve_task_ref(&archived_tasks, &task_id)
+        .map_err(|e| format!("Archive of {}: {}", board.file, e))?;
+
+    let column = column.unwrap_or_else(|| "done".to_string());
+    board.check_column(&column)?;
+
+    let mut task = entries[index].task.clone();
+    task.completed = column == "done";
+    task.column = column;
+
+    let write = tasks::frontend_write(|| {
+        tasks::mutate_board(&project_path, &board, base_version, &versions, false, |tasks| {
+            if tasks.iter().any(|t| task_key(t) == task_key(&task)) {
+                return Err(format!("'{}' is already in {}", task.subject, board.file));
+            }
+            tasks.push(task.clone());
+            Ok(())
+        })
+    })?;
+
+    if write.conflicts.is_empty() {
+        entries.remove(index);
+        save_archive(&project_path, &board, &entries)?;
+    }
+
+    Ok(write)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_select_for_archive() {
+        let content = "# TASKS\n\n## Backlog\n- [ ] Open\n\n## Done\n- [x] A\n- [x] B\n- [x] C\n- [x] D\n";
+        let tasks = tasks::parse_tasks_md(content).unwrap();
+        let now = 100 * DAY_MS;
+        // Open, A (unknown), B (90 days ago), C (1 day ago), D (2 days ago)
+        let completed_at = vec![None, None, Some(10 * DAY_MS), Some(99 * DAY_MS), Some(98 * DAY_MS)];
+
+        let by_count = ArchivePolicy {
+            older_than_days: None,
+            keep_recent: Some(2),
+        };
+        assert_eq!(select_for_archive(&tasks, &completed_at, &by_count, now), vec![1, 2]);
+
+        let by_age = ArchivePolicy {
+            older_than_days: Some(30),
+            keep_recent: None,
+        };
+        assert_eq!(select_for_archive(&tasks, &completed_at, &by_age, now), vec![2]);
+    }
+
+    #[test]
+    fn test_archive_round_trip() {
+        let entries = vec![
+            ArchivedTask {
+                task: TasksMdTask::new("Ship it #release id:ship", Some("Notes".to_string()), "done"),
+                month: "2026-09".to_string(),
+                completed_on: Some("2026-09-30".to_string()),
+            },
+            ArchivedTask {
+                task: TasksMdTask::new("Later", None, "done"),
+                month: "2026-10".to_string(),
+                completed_on: Some("2026-10-02".to_string()),
+            },
+            ArchivedTask {
+                task: TasksMdTask::new("Ancient", None, "done"),
+                month: "2026-10".to_string(),
+                completed_on: None,
+            },
+        ];
+
+        let content = render_archive(&entries);
+        assert_eq!(
+            content,
+            "# TASKS Archive\n\n## 2026-10\n- [x] 2026-10-02 Later\n- [x] Ancient\n\n## 2026-09\n- [x] 2026-09-30 Ship it #release id:ship\n  Notes\n\n"
+        );
+
+        let parsed = parse_archive(&content).unwrap();
+        assert_eq!(parsed.len(), 3);
+        assert_eq!(parsed[1].task.subject, "Ancient");
+        assert_eq!(parsed[1].completed_on, None);
+        assert_eq!(parsed[2].task.subject, "Ship it");
+        assert_eq!(parsed[2].task.stable_id.as_deref(), Some("ship"));
+        assert_eq!(parsed[2].completed_on.as_deref(), Some("2026-09-30"));
+        assert_eq!(parsed[2].month, "2026-09");
+        assert_eq!(render_archive(&parsed), content);
+    }
+}
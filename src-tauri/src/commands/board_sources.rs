@@ -0,0 +1,611 @@
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Component, Path};
+use regex::Regex;
+
+use super::boards::{self, BoardConfig};
+use super::dependencies::slugify;
+use super::journal;
+use super::merge::{self, fingerprint, task_key};
+use super::tasks::{self, TasksMdTask, TasksMdWriteResult};
+use crate::commands::projects::Project;
+
+/// Layouts a board can be registered with; "auto" picks one from the file's content
+pub(crate) const LAYOUTS: &[&str] = &["auto", "tasks_md", "headings", "checklist"];
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BoardColumn {
+    pub id: String,
+    pub title: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Board {
+    pub file: String,
+    /// The layout actually used, with "auto" resolved
+    pub layout: String,
+    pub columns: Vec<BoardColumn>,
+    pub tasks: Vec<TasksMdTask>,
+    /// Content version, passed back as `base_version` when moving tasks
+    pub version: String,
+}
+
+/// A way of turning a markdown file into board columns and tasks, and of moving a task
+/// between those columns in the file
+pub trait BoardSource {
+    fn layout(&self) -> &'static str;
+
+    fn parse(&self, content: &str) -> Result<(Vec<BoardColumn>, Vec<TasksMdTask>), String>;
+
+    /// Return the new file content with the task on `line_number` moved into `column`. Layouts
+    /// without their own move are rewritten as a whole through `tasks::mutate_board` instead.
+    fn move_task(&self, _content: &str, _line_number: usize, _column: &str) -> Result<String, String> {
+        Err(format!("{} boards don't move tasks in place", self.layout()))
+    }
+}
+
+/// A TASKS.md-style file with the `##` column headings of its board config
+pub struct TasksMdSource(pub BoardConfig);
+
+/// Every heading that holds checkboxes becomes a column
+pub struct HeadingsSource;
+
+/// A flat checklist split into todo and done
+pub struct ChecklistSource;
+
+impl BoardSource for TasksMdSource {
+    fn layout(&self) -> &'static str {
+        "tasks_md"
+    }
+
+    fn parse(&self, content: &str) -> Result<(Vec<BoardColumn>, Vec<TasksMdTask>), String> {
+        let columns = self
+            .0
+            .columns()
+            .into_iter()
+            .map(|c| BoardColumn {
+                id: c.id,
+                title: c.heading,
+            })
+            .collect();
+
+        Ok((columns, tasks::parse_board(content, &self.0)?))
+    }
+}
+
+struct Heading {
+    /// 0-indexed line
+    line: usize,
+    level: usize,
+    id: String,
+    title: String,
+}
+
+/// Headings outside code blocks, with ids slugified from their text and made unique
+fn headings(content: &str) -> Vec<Heading> {
+    let heading_re = Regex::new(tasks::HEADING_PATTERN).unwrap();
+    let mut headings: Vec<Heading> = Vec::new();
+    let mut in_code_block = false;
+
+    for (i, line) in content.lines().enumerate() {
+        if line.trim().starts_with("```") {
+            in_code_block = !in_code_block;
+            continue;
+        }
+        if in_code_block {
+            continue;
+        }
+
+        if let Some(caps) = heading_re.captures(line) {
+            let title = caps.get(2).map(|m| m.as_str().trim()).unwrap_or("").to_string();
+            let base = match slugify(&title) {
+                slug if slug.is_empty() => "section".to_string(),
+                slug => slug,
+            };
+
+            let mut id = base.clone();
+            let mut n = 2;
+            while headings.iter().any(|h| h.id == id) {
+                id = format!("{}-{}", base, n);
+                n += 1;
+            }
+
+            headings.push(Heading {
+                line: i,
+                level: caps.get(1).map(|m| m.as_str().len()).unwrap_or(0),
+                id,
+                title,
+            });
+        }
+    }
+
+    headings
+}
+
+/// Column for checkboxes that come before the first heading
+const UNSORTED: &str = "unsorted";
+
+fn is_done_column(column: &str) -> bool {
+    matches!(column, "done" | "completed" | "complete" | "finished")
+}
+
+/// Flip the checkbox on a task line
+fn set_checkbox(line: &str, completed: bool) -> String {
+    let checkbox_re = Regex::new(r"^(\s*-\s*\[)[ xX](\])").unwrap();
+    let mark = if completed { "x" } else { " " };
+    checkbox_re
+        .replace(line, |caps: &regex::Captures| format!("{}{}{}", &caps[1], mark, &caps[2]))
+        .into_owned()
+}
+
+fn task_on_line(tasks: &[TasksMdTask], line_number: usize) -> Result<&TasksMdTask, String> {
+    tasks
+        .iter()
+        .find(|t| t.line_number == line_number)
+        .ok_or_else(|| format!("No task on line {}", line_number))
+}
+
+fn join_lines(lines: &[String], trailing_newline: bool) -> String {
+    let mut content = lines.join("\n");
+    if trailing_newline {
+        content.push('\n');
+    }
+    content
+}
+
+impl BoardSource for HeadingsSource {
+    fn layout(&self) -> &'static str {
+        "headings"
+    }
+
+    fn parse(&self, content: &str) -> Result<(Vec<BoardColumn>, Vec<TasksMdTask>), String> {
+        let headings = headings(content);
+        let mut ids = headings.iter().map(|h| h.id.clone());
+        let tasks = tasks::parse_checklist(content, Some(UNSORTED.to_string()), |_, _| ids.next())?;
+
+        // Offer every heading on a level that holds tasks, so tasks can move into empty sections
+        let levels: Vec<usize> = headings
+            .iter()
+            .filter(|h| tasks.iter().any(|t| t.column == h.id))
+            .map(|h| h.level)
+            .collect();
+
+        let mut columns = Vec::new();
+        if tasks.iter().any(|t| t.column == UNSORTED) {
+            columns.push(BoardColumn {
+                id: UNSORTED.to_string(),
+                title: "Unsorted".to_string(),
+            });
+        }
+        columns.extend(headings.iter().filter(|h| levels.contains(&h.level)).map(|h| BoardColumn {
+            id: h.id.clone(),
+            title: h.title.clone(),
+        }));
+
+        Ok((columns, tasks))
+    }
+
+    fn move_task(&self, content: &str, line_number: usize, column: &str) -> Result<String, String> {
+        let (_, tasks) = self.parse(content)?;
+        let task = task_on_line(&tasks, line_number)?;
+
+        // The task block as parsed: its line, body and subtasks, blank lines within included
+        let mut lines: Vec<String> = content.lines().map(String::from).collect();
+        let mut block: Vec<String> = lines.drain(task.line_number - 1..task.end_line_number).collect();
+        block[0] = set_checkbox(&block[0], is_done_column(column));
+
+        // Append to the end of the target section, before any trailing blank lines
+        let headings = headings(&join_lines(&lines, false));
+        let target = headings
+            .iter()
+            .position(|h| h.id == column)
+            .ok_or_else(|| format!("Unknown column '{}'", column))?;
+        let mut insert_at = headings
+            .get(target + 1)
+            .map(|h| h.line)
+            .unwrap_or(lines.len());
+        while insert_at > headings[target].line + 1 && lines[insert_at - 1].trim().is_empty() {
+            insert_at -= 1;
+        }
+
+        lines.splice(insert_at..insert_at, block);
+
+        Ok(join_lines(&lines, content.ends_with('\n')))
+    }
+}
+
+impl BoardSource for ChecklistSource {
+    fn layout(&self) -> &'static str {
+        "checklist"
+    }
+
+    fn parse(&self, content: &str) -> Result<(Vec<BoardColumn>, Vec<TasksMdTask>), String> {
+        let mut tasks = tasks::parse_checklist(content, Some("todo".to_string()), |_, _| None)?;
+        for task in &mut tasks {
+            if task.completed {
+                task.column = "done".to_string();
+            }
+        }
+
+        let columns = vec![
+            BoardColumn {
+                id: "todo".to_string(),
+                title: "Todo".to_string(),
+            },
+            BoardColumn {
+                id: "done".to_string(),
+                title: "Done".to_string(),
+            },
+        ];
+
+        Ok((columns, tasks))
+    }
+
+    fn move_task(&self, content: &str, line_number: usize, column: &str) -> Result<String, String> {
+        let completed = match column {
+            "todo" => false,
+            "done" => true,
+            _ => return Err(format!("Unknown column '{}'", column)),
+        };
+
+        let (_, tasks) = self.parse(content)?;
+        let index = task_on_line(&tasks, line_number)?.line_number - 1;
+
+        let mut lines: Vec<String> = content.lines().map(String::from).collect();
+        lines[index] = set_checkbox(&lines[index], completed);
+
+        Ok(join_lines(&lines, content.ends_with('\n')))
+    }
+}
+
+/// Pick a layout for a file: TASKS.md keeps its fixed columns, files with checkboxes under
+/// more than one heading use their headings, anything else is a flat checklist
+fn detect_layout(file: &str, content: &str) -> &'static str {
+    if Path::new(file).file_name().and_then(|n| n.to_str()) == Some("TASKS.md") {
+        return "tasks_md";
+    }
+
+    let sections = HeadingsSource
+        .parse(content)
+        .map(|(_, tasks)| {
+            let mut columns: Vec<String> = tasks.into_iter().map(|t| t.column).collect();
+            columns.sort();
+            columns.dedup();
+            columns.len()
+        })
+        .unwrap_or(0);
+
+    if sections > 1 {
+        "headings"
+    } else {
+        "checklist"
+    }
+}
+
+/// The source for a board's layout
+pub fn source_for(board: &BoardConfig) -> Result<Box<dyn BoardSource>, String> {
+    match board.layout.as_str() {
+        "tasks_md" => Ok(Box::new(TasksMdSource(board.clone()))),
+        "headings" => Ok(Box::new(HeadingsSource)),
+        "checklist" => Ok(Box::new(ChecklistSource)),
+        layout => Err(format!("Unknown board layout '{}'", layout)),
+    }
+}
+
+/// Board source files must stay inside the project
+pub(crate) fn validate_source_file(file: &str) -> Result<(), String> {
+    let path = Path::new(file);
+    if file.is_empty() || !path.components().all(|c| matches!(c, Component::Normal(_))) {
+        return Err(format!("Invalid board source file '{}'", file));
+    }
+    Ok(())
+}
+
+fn find_project(projects: &[Project], project_id: &str) -> Result<Project, String> {
+    projects
+        .iter()
+        .find(|p| p.id == project_id)
+        .cloned()
+        .ok_or_else(|| "Project not found".to_string())
+}
+
+fn read_source(project_path: &str, board: &BoardConfig) -> Result<(String, Box<dyn BoardSource>), String> {
+    let path = board.path(project_path);
+
+    let content = if path.exists() {
+        fs::read_to_string(&path).map_err(|e| format!("Failed to read {}: {}", board.file, e))?
+    } else {
+        String::new()
+    };
+
+    Ok((content, source_for(board)?))
+}
+
+/// Move the task on `line_number` of `content` in place and write the file, returning the
+/// result and the move for the journal
+fn write_move(
+    project_path: &str,
+    board: &BoardConfig,
+    source: &dyn BoardSource,
+    content: &str,
+    line_number: usize,
+    column: &str,
+    versions: &merge::TasksMdVersions,
+) -> Result<(TasksMdWriteResult, journal::TaskOperation), String> {
+    let (_, tasks) = source.parse(content)?;
+    let index = tasks
+        .iter()
+        .position(|t| t.line_number == line_number)
+        .ok_or_else(|| format!("No task on line {}", line_number))?;
+
+    let updated = source.move_task(content, line_number, column)?;
+    let (_, written) = source.parse(&updated)?;
+    tasks::write_board_content(project_path, board, &updated, &written)?;
+
+    let before = tasks[index].clone();
+    let mut after = before.clone();
+    after.column = column.to_string();
+    let operation = journal::TaskOperation::new("move", index, Some(before), Some(after)).on_board(board);
+
+    Ok((
+        TasksMdWriteResult {
+            version: versions.remember(&updated),
+            merged: false,
+            conflicts: vec![],
+            tasks: written,
+        },
+        operation,
+    ))
+}
+
+/// Undo or redo a journaled move on a board that isn't TASKS.md-style: the task has to still
+/// look like `from`, and goes back into the column of `to`. `diverged` is set when the
+/// operation can never apply again, as opposed to failing to read or write the file.
+pub(crate) fn replay_move(
+    project_path: &str,
+    board: &BoardConfig,
+    from: Option<&TasksMdTask>,
+    to: Option<&TasksMdTask>,
+    versions: &merge::TasksMdVersions,
+    diverged: &mut bool,
+) -> Result<TasksMdWriteResult, String> {
+    let (Some(from), Some(to)) = (from, to) else {
+        *diverged = true;
+        return Err(format!("Only moves can be replayed on {} boards", board.layout));
+    };
+
+    let (content, source) = read_source(project_path, board)?;
+    let (_, tasks) = source.parse(&content)?;
+    let Some(task) = tasks.iter().find(|t| task_key(t) == task_key(from)) else {
+        *diverged = true;
+        return Err(format!("'{}' is no longer in {}", from.subject, board.file));
+    };
+    if fingerprint(task) != fingerprint(from) {
+        *diverged = true;
+        return Err(format!("'{}' has changed since", from.subject));
+    }
+
+    write_move(project_path, board, source.as_ref(), &content, task.line_number, &to.column, versions)
+        .map(|(result, _)| result)
+}
+
+/// Checklist files in the project root a board can be built from
+#[tauri::command]
+pub fn list_board_sources(project_path: String) -> Result<Vec<String>, String> {
+    let project_dir = Path::new(&project_path);
+
+    if !project_dir.is_dir() {
+        return Err("Invalid project path".to_string());
+    }
+
+    Ok(tasks::TASK_FILES
+        .iter()
+        .filter(|f| project_dir.join(f).is_file())
+        .map(|f| f.to_string())
+        .collect())
+}
+
+/// Register a file as one of a project's boards, or change the layout of one that is; "auto"
+/// picks the layout from the file's content
+#[tauri::command]
+pub fn set_board_source(
+    project_id: String,
+    file: String,
+    layout: Option<String>,
+    state: tauri::State<'_, crate::state::AppState>,
+) -> Result<(), String> {
+    validate_source_file(&file)?;
+
+    let layout = layout.unwrap_or_else(|| "auto".to_string());
+    if !LAYOUTS.contains(&layout.as_str()) {
+        return Err(format!("Unknown board layout '{}'", layout));
+    }
+
+    let mut projects = state.projects.lock().map_err(|e| e.to_string())?;
+    let project = projects
+        .iter_mut()
+        .find(|p| p.id == project_id)
+        .ok_or("Project not found")?;
+
+    let layout = match layout.as_str() {
+        "auto" => {
+            let content = fs::read_to_string(Path::new(&project.path).join(&file)).unwrap_or_default();
+            detect_layout(&file, &content).to_string()
+        }
+        _ => layout,
+    };
+
+    let mut board = project
+        .boards
+        .iter()
+        .find(|b| b.file == file)
+        .cloned()
+        .unwrap_or_else(|| BoardConfig {
+            file: file.clone(),
+            ..Default::default()
+        });
+    board.layout = layout;
+    boards::validate_board(&board)?;
+
+    match project.boards.iter_mut().find(|b| b.file == file) {
+        Some(existing) => *existing = board,
+        None => project.boards.push(board),
+    }
+
+    crate::state::save_projects(&projects)
+}
+
+/// Build one of a project's boards (TASKS.md unless `board` is given) with its layout
+#[tauri::command]
+pub fn read_board(
+    project_id: String,
+    board: Option<String>,
+    state: tauri::State<'_, crate::state::AppState>,
+    versions: tauri::State<'_, merge::TasksMdVersions>,
+) -> Result<Board, String> {
+    let project = {
+        let projects = state.projects.lock().map_err(|e| e.to_string())?;
+        find_project(&projects, &project_id)?
+    };
+
+    let board = boards::find_board(&state, &project.path, board.as_deref())?;
+    let (content, source) = read_source(&project.path, &board)?;
+    let (columns, tasks) = source.parse(&content)?;
+
+    Ok(Board {
+        file: board.file,
+        layout: source.layout().to_string(),
+        columns,
+        tasks,
+        version: versions.remember(&content),
+    })
+}
+
+/// Move the task on `line_number` of a project's board (TASKS.md unless `board` is given) into
+/// another column. Line numbers refer to the content at `base_version` when it is given.
+/// TASKS.md-style files are written like any board mutation, merging concurrent edits; other
+/// layouts move the task in place and refuse the move when the file changed since `base_version`.
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+pub fn move_board_task(
+    project_id: String,
+    board: Option<String>,
+    line_number: usize,
+    column: String,
+    base_version: Option<String>,
+    state: tauri::State<'_, crate::state::AppState>,
+    versions: tauri::State<'_, merge::TasksMdVersions>,
+    journal: tauri::State<'_, journal::TaskJournal>,
+) -> Result<TasksMdWriteResult, String> {
+    let project = {
+        let projects = state.projects.lock().map_err(|e| e.to_string())?;
+        find_project(&projects, &project_id)?
+    };
+
+    let board = boards::find_board(&state, &project.path, board.as_deref())?;
+
+    if board.is_tasks_md() {
+        board.check_column(&column)?;
+
+        let mut operation = None;
+        let result = tasks::frontend_write(|| {
+            tasks::mutate_board(&project.path, &board, base_version, &versions, true, |tasks| {
+                let index = tasks
+                    .iter()
+                    .position(|t| t.line_number == line_number)
+                    .ok_or_else(|| format!("No task on line {}", line_number))?;
+                let before = tasks[index].clone();
+                tasks[index].column = column.clone();
+                tasks[index].completed = column == "done";
+                operation = Some(journal::TaskOperation::new("move", index, Some(before), Some(tasks[index].clone())));
+                Ok(())
+            })
+        })?;
+
+        journal.record(&project.path, &result, operation.map(|o| o.on_board(&board)));
+        return Ok(result);
+    }
+
+    let (content, source) = read_source(&project.path, &board)?;
+    if base_version.is_some_and(|version| version != merge::content_hash(&content)) {
+        return Err(format!("{} changed since it was read; reload the board", board.file));
+    }
+
+    let (result, operation) = tasks::frontend_write(|| {
+        write_move(&project.path, &board, source.as_ref(), &content, line_number, &column, &versions)
+    })?;
+    journal.record(&project.path, &result, Some(operation));
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PLAN_MD: &str = r#"# Plan
+
+## Phase 1
+- [x] Scaffold crate
+- [ ] Parser
+  Handles nested lists
+
+## Phase 2
+- [ ] Renderer
+
+## Done
+"#;
+
+    #[test]
+    fn test_detect_layout() {
+        assert_eq!(detect_layout("TASKS.md", ""), "tasks_md");
+        assert_eq!(detect_layout("plan.md", PLAN_MD), "headings");
+        assert_eq!(detect_layout("todo.md", "# Todo\n- [ ] One\n- [x] Two\n"), "checklist");
+    }
+
+    #[test]
+    fn test_headings_source() {
+        let (columns, tasks) = HeadingsSource.parse(PLAN_MD).unwrap();
+
+        let ids: Vec<&str> = columns.iter().map(|c| c.id.as_str()).collect();
+        assert_eq!(ids, vec!["phase-1", "phase-2", "done"]);
+        assert_eq!(tasks[1].column, "phase-1");
+        assert_eq!(tasks[1].description.as_deref(), Some("Handles nested lists"));
+        assert_eq!(tasks[2].column, "phase-2");
+
+        let moved = HeadingsSource.move_task(PLAN_MD, tasks[1].line_number, "done").unwrap();
+        assert!(moved.ends_with("## Done\n- [x] Parser\n  Handles nested lists\n"));
+        assert!(moved.contains("- [x] Scaffold crate\n\n## Phase 2"));
+    }
+
+    #[test]
+    fn test_headings_move_keeps_the_whole_body() {
+        let content = "## Todo\n- [ ] Parser\n  First paragraph\n\n  Second paragraph\n\n- [ ] Renderer\n\n## Done\n";
+        let (_, tasks) = HeadingsSource.parse(content).unwrap();
+        assert_eq!(tasks[0].description.as_deref(), Some("First paragraph\n\nSecond paragraph"));
+
+        let moved = HeadingsSource.move_task(content, tasks[0].line_number, "done").unwrap();
+        assert_eq!(
+            moved,
+            "## Todo\n\n- [ ] Renderer\n\n## Done\n- [x] Parser\n  First paragraph\n\n  Second paragraph\n"
+        );
+    }
+
+    #[test]
+    fn test_checklist_source() {
+        let content = "# Todo\n- [ ] One\n- [x] Two\n";
+        let (_, tasks) = ChecklistSource.parse(content).unwrap();
+        assert_eq!(tasks[0].column, "todo");
+        assert_eq!(tasks[1].column, "done");
+
+        let moved = ChecklistSource.move_task(content, tasks[0].line_number, "done").unwrap();
+        assert_eq!(moved, "# Todo\n- [x] One\n- [x] Two\n");
+        assert!(ChecklistSource.move_task(content, 1, "done").is_err());
+    }
+
+    #[test]
+    fn test_validate_source_file() {
+        assert!(validate_source_file("docs/plan.md").is_ok());
+        assert!(validate_source_file("../plan.md").is_err());
+        assert!(validate_source_file("/etc/passwd").is_err());
+    }
+}
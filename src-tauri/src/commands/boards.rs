@@ -0,0 +1,291 @@
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+use super::board_sources::{validate_source_file, LAYOUTS};
+use super::tasks;
+use crate::commands::projects::Project;
+use crate::state::AppState;
+
+/// The board every project has, used when a command gets no `board`
+pub const DEFAULT_BOARD: &str = "TASKS.md";
+
+/// A column of a board file: tasks under `## {heading}` belong to column `id`. Tasks moved
+/// into the column with id `done` are checked off.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ColumnConfig {
+    pub id: String,
+    pub heading: String,
+}
+
+/// A file registered as one of a project's boards
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BoardConfig {
+    /// Path relative to the project root; also identifies the board
+    pub file: String,
+    /// How the file is turned into a board: "tasks_md", "headings" or "checklist". Only
+    /// "tasks_md" boards take the task commands; the others are read with `read_board`.
+    #[serde(default = "default_layout")]
+    pub layout: String,
+    /// Title written as the file's `#` heading; the file name without extension when unset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// Columns in board order; the standard Backlog to Done columns when empty
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub columns: Vec<ColumnConfig>,
+}
+
+fn default_layout() -> String {
+    "tasks_md".to_string()
+}
+
+impl Default for BoardConfig {
+    fn default() -> Self {
+        Self {
+            file: DEFAULT_BOARD.to_string(),
+            layout: default_layout(),
+            name: None,
+            columns: vec![],
+        }
+    }
+}
+
+impl BoardConfig {
+    pub fn is_default(&self) -> bool {
+        self.file == DEFAULT_BOARD
+    }
+
+    pub fn is_tasks_md(&self) -> bool {
+        self.layout == "tasks_md"
+    }
+
+    pub fn columns(&self) -> Vec<ColumnConfig> {
+        if !self.columns.is_empty() {
+            return self.columns.clone();
+        }
+
+        tasks::COLUMN_HEADINGS
+            .iter()
+            .map(|(id, heading)| ColumnConfig {
+                id: id.to_string(),
+                heading: heading.trim_start_matches("## ").to_string(),
+            })
+            .collect()
+    }
+
+    pub fn title(&self) -> String {
+        if let Some(name) = &self.name {
+            return name.clone();
+        }
+
+        let stem = Path::new(&self.file)
+            .file_stem()
+            .map(|s| s.to_string_lossy().to_string())
+            .unwrap_or_default();
+        if self.is_default() {
+            stem
+        } else {
+            stem.to_uppercase()
+        }
+    }
+
+    /// Column of a `##` heading's text, matched case-insensitively
+    pub fn heading_to_column(&self, heading: &str) -> Option<String> {
+        self.columns()
+            .into_iter()
+            .find(|c| c.heading.eq_ignore_ascii_case(heading.trim()))
+            .map(|c| c.id)
+    }
+
+    /// Tasks in a column the board doesn't have would be dropped when the file is written
+    pub fn check_column(&self, column: &str) -> Result<(), String> {
+        if self.columns().iter().any(|c| c.id == column) {
+            Ok(())
+        } else {
+            Err(format!("'{}' has no column '{}'", self.file, column))
+        }
+    }
+
+    /// Position of a column on this board, for comparing columns; unknown columns sort last
+    pub fn column_rank(&self, column: &str) -> usize {
+        let columns = self.columns();
+        columns.iter().position(|c| c.id == column).unwrap_or(columns.len())
+    }
+
+    pub fn path(&self, project_path: &str) -> PathBuf {
+        Path::new(project_path).join(&self.file)
+    }
+
+    /// Key task history is recorded under: the project for TASKS.md, the file for other boards
+    pub fn history_key(&self, project_path: &str) -> String {
+        if self.is_default() {
+            project_path.to_string()
+        } else {
+            self.path(project_path).to_string_lossy().to_string()
+        }
+    }
+}
+
+pub(crate) fn validate_board(board: &BoardConfig) -> Result<(), String> {
+    validate_source_file(&board.file)?;
+
+    if board.layout == "auto" || !LAYOUTS.contains(&board.layout.as_str()) {
+        return Err(format!("Unknown board layout '{}'", board.layout));
+    }
+    if board.is_default() && !board.is_tasks_md() {
+        return Err(format!("{} always uses the tasks_md layout", DEFAULT_BOARD));
+    }
+
+    let columns = board.columns();
+    for (i, column) in columns.iter().enumerate() {
+        if column.id.trim().is_empty() || column.heading.trim().is_empty() {
+            return Err(format!("Columns of '{}' need an id and a heading", board.file));
+        }
+        if columns[..i]
+            .iter()
+            .any(|c| c.id == column.id || c.heading.eq_ignore_ascii_case(&column.heading))
+        {
+            return Err(format!("Column '{}' appears twice in '{}'", column.id, board.file));
+        }
+    }
+
+    Ok(())
+}
+
+/// A project's boards: TASKS.md first, unless it was registered with its own columns
+pub fn project_boards(project: Option<&Project>) -> Vec<BoardConfig> {
+    let registered = project.map(|p| p.boards.clone()).unwrap_or_default();
+
+    let mut boards = Vec::new();
+    if !registered.iter().any(|b| b.is_default()) {
+        boards.push(BoardConfig::default());
+    }
+    boards.extend(registered);
+    boards
+}
+
+/// Every board of the project at `project_path`, whatever its layout
+pub fn all_boards_for_path(state: &AppState, project_path: &str) -> Vec<BoardConfig> {
+    state
+        .projects
+        .lock()
+        .map(|projects| project_boards(projects.iter().find(|p| p.path == project_path)))
+        .unwrap_or_else(|_| project_boards(None))
+}
+
+/// The TASKS.md-style boards of the project at `project_path`
+pub fn boards_for_path(state: &AppState, project_path: &str) -> Vec<BoardConfig> {
+    all_boards_for_path(state, project_path)
+        .into_iter()
+        .filter(BoardConfig::is_tasks_md)
+        .collect()
+}
+
+/// Resolve a `board` argument to a board of any layout; `None` is TASKS.md
+pub fn find_board(state: &AppState, project_path: &str, board: Option<&str>) -> Result<BoardConfig, String> {
+    let file = board.unwrap_or(DEFAULT_BOARD);
+
+    all_boards_for_path(state, project_path)
+        .into_iter()
+        .find(|b| b.file == file)
+        .ok_or_else(|| format!("'{}' is not a board of this project", file))
+}
+
+/// Resolve a `board` argument of a task command; `None` is TASKS.md
+pub fn board_config(state: &AppState, project_path: &str, board: Option<&str>) -> Result<BoardConfig, String> {
+    let board = find_board(state, project_path, board)?;
+    if !board.is_tasks_md() {
+        return Err(format!("'{}' is a {} board and can only be read with its layout", board.file, board.layout));
+    }
+    Ok(board)
+}
+
+/// The project's boards with their columns resolved
+#[tauri::command]
+pub fn list_boards(
+    project_path: String,
+    state: tauri::State<'_, crate::state::AppState>,
+) -> Result<Vec<BoardConfig>, String> {
+    let projects = state.projects.lock().map_err(|e| e.to_string())?;
+    let project = projects.iter().find(|p| p.path == project_path);
+
+    Ok(project_boards(project)
+        .into_iter()
+        .map(|mut b| {
+            b.columns = b.columns();
+            b
+        })
+        .collect())
+}
+
+/// Register the board files of a project; TASKS.md is always a board, listing it here only
+/// changes its columns
+#[tauri::command]
+pub fn set_project_boards(
+    project_id: String,
+    boards: Vec<BoardConfig>,
+    state: tauri::State<'_, crate::state::AppState>,
+) -> Result<(), String> {
+    for (i, board) in boards.iter().enumerate() {
+        validate_board(board)?;
+        if boards[..i].iter().any(|b| b.file == board.file) {
+            return Err(format!("'{}' is registered twice", board.file));
+        }
+    }
+
+    let mut projects = state.projects.lock().map_err(|e| e.to_string())?;
+    let project = projects
+        .iter_mut()
+        .find(|p| p.id == project_id)
+        .ok_or("Project not found")?;
+
+    project.boards = boards;
+
+    crate::state::save_projects(&projects)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_board_config() {
+        let default = BoardConfig::default();
+        assert_eq!(default.title(), "TASKS");
+        assert_eq!(default.columns().len(), 5);
+        assert_eq!(default.heading_to_column("in progress").as_deref(), Some("in_progress"));
+
+        let bugs = BoardConfig {
+            file: "docs/bugs.md".to_string(),
+            layout: "tasks_md".to_string(),
+            name: None,
+            columns: vec![
+                ColumnConfig {
+                    id: "triage".to_string(),
+                    heading: "Triage".to_string(),
+                },
+                ColumnConfig {
+                    id: "done".to_string(),
+                    heading: "Fixed".to_string(),
+                },
+            ],
+        };
+        assert_eq!(bugs.title(), "BUGS");
+        assert_eq!(bugs.heading_to_column("Fixed").as_deref(), Some("done"));
+        assert_eq!(bugs.heading_to_column("Backlog"), None);
+        assert!(validate_board(&bugs).is_ok());
+
+        let mut duplicate = bugs.clone();
+        duplicate.columns[1].heading = "triage".to_string();
+        assert!(validate_board(&duplicate).is_err());
+
+        let mut escaping = bugs.clone();
+        escaping.file = "../bugs.md".to_string();
+        assert!(validate_board(&escaping).is_err());
+
+        let mut plan = bugs;
+        plan.layout = "headings".to_string();
+        assert!(validate_board(&plan).is_ok());
+        plan.file = DEFAULT_BOARD.to_string();
+        assert!(validate_board(&plan).is_err());
+    }
+}
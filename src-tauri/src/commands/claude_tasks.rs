@@ -0,0 +1,559 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use notify::{Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use tauri::{AppHandle, Emitter};
+
+use super::sync;
+use super::tasks::{self, ClaudeTask};
+
+/// Watchers on Claude Code task list directories, keyed by task list id
+pub struct ClaudeTasksWatcherState {
+    watchers: Mutex<HashMap<String, RecommendedWatcher>>,
+}
+
+impl ClaudeTasksWatcherState {
+    pub fn new() -> Self {
+        Self {
+            watchers: Mutex::new(HashMap::new()),
+        }
+    }
+}
+
+impl Default for ClaudeTasksWatcherState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A change between two reads of a task list, emitted to the frontend under `event`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ClaudeTaskEvent {
+    /// "claude-task-added", "claude-task-started", "claude-task-completed",
+    /// "claude-task-updated" or "claude-task-removed"
+    pub event: String,
+    #[serde(rename = "taskListId")]
+    pub task_list_id: String,
+    pub task: ClaudeTask,
+    #[serde(rename = "previousStatus")]
+    pub previous_status: Option<String>,
+}
+
+/// What the watcher last saw in a task list directory
+#[derive(Debug, Clone, Default)]
+struct TaskListSnapshot {
+    tasks: Vec<ClaudeTask>,
+    locked: bool,
+}
+
+impl TaskListSnapshot {
+    fn read(tasks_dir: &Path) -> Self {
+        Self {
+            tasks: tasks::read_claude_tasks(tasks_dir),
+            locked: tasks_dir.join(".lock").exists(),
+        }
+    }
+}
+
+fn same_task(a: &ClaudeTask, b: &ClaudeTask) -> bool {
+    a.subject == b.subject
+        && a.status == b.status
+        && a.description == b.description
+        && a.blocks == b.blocks
+        && a.blocked_by == b.blocked_by
+        && a.owner == b.owner
+}
+
+/// Compare two reads of a task list. Status changes into `in_progress` and `completed` get
+/// their own events; any other edit is an update.
+pub fn diff_task_lists(task_list_id: &str, old: &[ClaudeTask], new: &[ClaudeTask]) -> Vec<ClaudeTaskEvent> {
+    let event = |name: &str, task: &ClaudeTask, previous_status: Option<String>| ClaudeTaskEvent {
+        event: name.to_string(),
+        task_list_id: task_list_id.to_string(),
+        task: task.clone(),
+        previous_status,
+    };
+
+    let mut events = Vec::new();
+
+    for task in new {
+        match old.iter().find(|o| o.id == task.id) {
+            None => events.push(event("claude-task-added", task, None)),
+            Some(previous) if previous.status != task.status => {
+                let name = match task.status.as_str() {
+                    "in_progress" => "claude-task-started",
+                    "completed" => "claude-task-completed",
+                    _ => "claude-task-updated",
+                };
+                events.push(event(name, task, Some(previous.status.clone())));
+            }
+            Some(previous) if !same_task(previous, task) => {
+                events.push(event("claude-task-updated", task, Some(previous.status.clone())));
+            }
+            Some(_) => {}
+        }
+    }
+
+    for task in old {
+        if !new.iter().any(|n| n.id == task.id) {
+            events.push(event("claude-task-removed", task, Some(task.status.clone())));
+        }
+    }
+
+    events
+}
+
+/// Watch ~/.claude/tasks/<task_list_id>/ and emit an event per task that was added, started,
+/// completed, edited or removed, plus `claude-tasks-lock-changed` when an agent takes or
+/// releases the `.lock` file
+#[tauri::command]
+pub fn watch_claude_tasks(
+    task_list_id: String,
+    app: AppHandle,
+    state: tauri::State<'_, ClaudeTasksWatcherState>,
+) -> Result<(), String> {
+    let tasks_dir = tasks::claude_tasks_dir(&task_list_id)?;
+
+    // The directory has to exist to be watched; agents create their files inside it later
+    fs::create_dir_all(&tasks_dir)
+        .map_err(|e| format!("Failed to create tasks directory: {}", e))?;
+
+    let snapshot = Mutex::new(TaskListSnapshot::read(&tasks_dir));
+    let tasks_dir_clone = tasks_dir.clone();
+    let task_list_id_clone = task_list_id.clone();
+
+    let mut watcher = notify::recommended_watcher(move |res: Result<Event, notify::Error>| {
+        match res {
+            Ok(event) => {
+                if !matches!(event.kind, EventKind::Create(_) | EventKind::Modify(_) | EventKind::Remove(_)) {
+                    return;
+                }
+
+                // Ignore our own temporary files from atomic writes
+                let relevant = event.paths.iter().any(|p| {
+                    let name = p.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+                    name == ".lock" || (name.ends_with(".json") && !name.starts_with('.'))
+                });
+                if !relevant {
+                    return;
+                }
+
+                let Ok(mut previous) = snapshot.lock() else {
+                    return;
+                };
+                let current = TaskListSnapshot::read(&tasks_dir_clone);
+
+                for change in diff_task_lists(&task_list_id_clone, &previous.tasks, &current.tasks) {
+                    let _ = app.emit(&change.event, &change);
+                }
+
+                if current.locked != previous.locked {
+                    let _ = app.emit("claude-tasks-lock-changed", serde_json::json!({
+                        "taskListId": task_list_id_clone,
+                        "isActive": current.locked,
+                    }));
+                }
+
+                *previous = current;
+            }
+            Err(e) => {
+                eprintln!("Watch error: {:?}", e);
+            }
+        }
+    }).map_err(|e| format!("Failed to create watcher: {}", e))?;
+
+    watcher.watch(&tasks_dir, RecursiveMode::NonRecursive)
+        .map_err(|e| format!("Failed to watch directory: {}", e))?;
+
+    let mut watchers = state.watchers.lock().map_err(|e| e.to_string())?;
+    watchers.insert(task_list_id, watcher);
+
+    Ok(())
+}
+
+/// Stop watching a Claude Code task list
+#[tauri::command]
+pub fn unwatch_claude_tasks(
+    task_list_id: String,
+    state: tauri::State<'_, ClaudeTasksWatcherState>,
+) -> Result<(), String> {
+    let mut watchers = state.watchers.lock().map_err(|e| e.to_string())?;
+    watchers.remove(&task_list_id);
+    Ok(())
+}
+
+const STATUSES: &[&str] = &["pending", "in_progress", "completed"];
+
+/// Write a Claude task file via a temporary file and rename, so readers never see half a file
+pub(crate) fn write_claude_task(tasks_dir: &Path, task: &ClaudeTask) -> Result<(), String> {
+    let tmp_path = stage_claude_task(tasks_dir, task)?;
+    commit_claude_task(tasks_dir, &tmp_path, task)
+}
+
+/// Write a task to a temp file that task list readers skip; `commit_claude_task` moves it
+/// into place
+fn stage_claude_task(tasks_dir: &Path, task: &ClaudeTask) -> Result<PathBuf, String> {
+    fs::create_dir_all(tasks_dir)
+        .map_err(|e| format!("Failed to create tasks directory: {}", e))?;
+
+    let content = serde_json::to_string_pretty(task)
+        .map_err(|e| format!("Failed to serialize task: {}", e))?;
+
+    let tmp_path = tasks_dir.join(format!(".{}.json.tmp", task.id));
+    fs::write(&tmp_path, content).map_err(|e| format!("Failed to write task file: {}", e))?;
+    Ok(tmp_path)
+}
+
+fn commit_claude_task(tasks_dir: &Path, tmp_path: &Path, task: &ClaudeTask) -> Result<(), String> {
+    fs::rename(tmp_path, tasks_dir.join(format!("{}.json", task.id)))
+        .map_err(|e| format!("Failed to write task file: {}", e))
+}
+
+/// Task list directory for an edit; refused while an agent holds the `.lock` file
+pub(crate) fn unlocked_tasks_dir(task_list_id: &str) -> Result<PathBuf, String> {
+    let tasks_dir = tasks::claude_tasks_dir(task_list_id)?;
+
+    if tasks_dir.join(".lock").exists() {
+        return Err("Task list is locked by a running agent; try again when it finishes".to_string());
+    }
+
+    Ok(tasks_dir)
+}
+
+fn validate_status(status: &str) -> Result<(), String> {
+    if !STATUSES.contains(&status) {
+        return Err(format!("Unknown task status '{}'", status));
+    }
+    Ok(())
+}
+
+fn next_task_id(tasks: &[ClaudeTask]) -> String {
+    let max = tasks.iter().filter_map(|t| t.id.parse::<u64>().ok()).max().unwrap_or(0);
+    (max + 1).to_string()
+}
+
+/// Make `task`'s `blocks` / `blockedBy` authoritative: drop stale references to it from the
+/// other tasks and mirror its own references onto them. Returns the indices that changed.
+fn link_dependencies(tasks: &mut [ClaudeTask], task: &ClaudeTask) -> Result<Vec<usize>, String> {
+    for reference in task.blocks.iter().chain(&task.blocked_by) {
+        if *reference == task.id {
+            return Err("A task cannot block itself".to_string());
+        }
+        if !tasks.iter().any(|t| t.id == *reference) {
+            return Err(format!("Task {} not found", reference));
+        }
+    }
+
+    let mut changed = Vec::new();
+
+    for (i, other) in tasks.iter_mut().enumerate() {
+        if other.id == task.id {
+            continue;
+        }
+
+        let before = (other.blocks.clone(), other.blocked_by.clone());
+
+        other.blocks.retain(|id| *id != task.id);
+        other.blocked_by.retain(|id| *id != task.id);
+        if task.blocked_by.contains(&other.id) {
+            other.blocks.push(task.id.clone());
+        }
+        if task.blocks.contains(&other.id) {
+            other.blocked_by.push(task.id.clone());
+        }
+
+        if before != (other.blocks.clone(), other.blocked_by.clone()) {
+            changed.push(i);
+        }
+    }
+
+    Ok(changed)
+}
+
+/// Write `task` and the other tasks whose dependency lists had to follow it
+fn save_with_dependencies(tasks_dir: &Path, mut others: Vec<ClaudeTask>, task: &ClaudeTask) -> Result<(), String> {
+    for i in link_dependencies(&mut others, task)? {
+        write_claude_task(tasks_dir, &others[i])?;
+    }
+    write_claude_task(tasks_dir, task)
+}
+
+/// Add a task to the end of a Claude Code task list
+#[tauri::command]
+pub fn create_claude_task(
+    task_list_id: String,
+    subject: String,
+    description: Option<String>,
+    status: Option<String>,
+    blocks: Option<Vec<String>>,
+    blocked_by: Option<Vec<String>>,
+    owner: Option<String>,
+) -> Result<ClaudeTask, String> {
+    let tasks_dir = unlocked_tasks_dir(&task_list_id)?;
+    let existing = tasks::read_claude_tasks(&tasks_dir);
+
+    let status = status.unwrap_or_else(|| "pending".to_string());
+    validate_status(&status)?;
+    if subject.trim().is_empty() {
+        return Err("Task subject cannot be empty".to_string());
+    }
+
+    let task = ClaudeTask {
+        id: next_task_id(&existing),
+        subject: subject.trim().to_string(),
+        status,
+        description: description.unwrap_or_default(),
+        blocks: blocks.unwrap_or_default(),
+        blocked_by: blocked_by.unwrap_or_default(),
+        owner,
+    };
+
+    save_with_dependencies(&tasks_dir, existing, &task)?;
+    Ok(task)
+}
+
+/// Replace a task's subject, description, status, owner and dependencies
+#[tauri::command]
+pub fn update_claude_task(task_list_id: String, task: ClaudeTask) -> Result<ClaudeTask, String> {
+    let tasks_dir = unlocked_tasks_dir(&task_list_id)?;
+    let existing = tasks::read_claude_tasks(&tasks_dir);
+
+    validate_status(&task.status)?;
+    if !existing.iter().any(|t| t.id == task.id) {
+        return Err(format!("Task {} not found", task.id));
+    }
+    if task.subject.trim().is_empty() {
+        return Err("Task subject cannot be empty".to_string());
+    }
+
+    save_with_dependencies(&tasks_dir, existing, &task)?;
+    Ok(task)
+}
+
+/// Change only a task's status
+#[tauri::command]
+pub fn set_claude_task_status(task_list_id: String, task_id: String, status: String) -> Result<ClaudeTask, String> {
+    validate_status(&status)?;
+
+    let tasks_dir = unlocked_tasks_dir(&task_list_id)?;
+    let mut task = tasks::read_claude_tasks(&tasks_dir)
+        .into_iter()
+        .find(|t| t.id == task_id)
+        .ok_or_else(|| format!("Task {} not found", task_id))?;
+
+    task.status = status;
+    write_claude_task(&tasks_dir, &task)?;
+    Ok(task)
+}
+
+/// Delete a task and drop it from every other task's `blocks` / `blockedBy`
+#[tauri::command]
+pub fn delete_claude_task(task_list_id: String, task_id: String) -> Result<(), String> {
+    let tasks_dir = unlocked_tasks_dir(&task_list_id)?;
+    let mut existing = tasks::read_claude_tasks(&tasks_dir);
+
+    if !existing.iter().any(|t| t.id == task_id) {
+        return Err(format!("Task {} not found", task_id));
+    }
+
+    let removed = ClaudeTask {
+        id: task_id.clone(),
+        subject: String::new(),
+        status: "pending".to_string(),
+        description: String::new(),
+        blocks: vec![],
+        blocked_by: vec![],
+        owner: None,
+    };
+    for i in link_dependencies(&mut existing, &removed)? {
+        write_claude_task(&tasks_dir, &existing[i])?;
+    }
+
+    fs::remove_file(tasks_dir.join(format!("{}.json", task_id)))
+        .map_err(|e| format!("Failed to delete task file: {}", e))
+}
+
+/// Renumber tasks 1..n in the given order, since the list order is the numeric id order.
+/// References in `blocks` / `blockedBy` follow the new ids; tasks missing from `order` keep
+/// their relative order after the listed ones. Also returns the new id of every old id.
+fn renumber(tasks: &[ClaudeTask], order: &[String]) -> (Vec<ClaudeTask>, HashMap<String, String>) {
+    let mut ordered: Vec<&ClaudeTask> = order
+        .iter()
+        .filter_map(|id| tasks.iter().find(|t| t.id == *id))
+        .collect();
+    ordered.dedup_by(|a, b| a.id == b.id);
+    for task in tasks {
+        if !ordered.iter().any(|t| t.id == task.id) {
+            ordered.push(task);
+        }
+    }
+
+    let new_ids: HashMap<&str, String> = ordered
+        .iter()
+        .enumerate()
+        .map(|(i, t)| (t.id.as_str(), (i + 1).to_string()))
+        .collect();
+    let remap = |ids: &[String]| -> Vec<String> {
+        ids.iter().filter_map(|id| new_ids.get(id.as_str()).cloned()).collect()
+    };
+
+    let reordered = ordered
+        .iter()
+        .map(|t| ClaudeTask {
+            id: new_ids[t.id.as_str()].clone(),
+            blocks: remap(&t.blocks),
+            blocked_by: remap(&t.blocked_by),
+            ..(*t).clone()
+        })
+        .collect();
+    let new_ids = new_ids.into_iter().map(|(old, new)| (old.to_string(), new)).collect();
+
+    (reordered, new_ids)
+}
+
+/// Reorder a task list by renumbering its task files. Every file is written to a temp file
+/// before any is renamed into place, renames already made are undone if a later one fails, and
+/// board sync links follow the new ids.
+#[tauri::command]
+pub fn reorder_claude_tasks(task_list_id: String, order: Vec<String>) -> Result<Vec<ClaudeTask>, String> {
+    let tasks_dir = unlocked_tasks_dir(&task_list_id)?;
+    let existing = tasks::read_claude_tasks(&tasks_dir);
+    let (reordered, new_ids) = renumber(&existing, &order);
+
+    let mut staged = Vec::new();
+    for task in &reordered {
+        match stage_claude_task(&tasks_dir, task) {
+            Ok(tmp_path) => staged.push(tmp_path),
+            Err(e) => {
+                for tmp_path in &staged {
+                    let _ = fs::remove_file(tmp_path);
+                }
+                return Err(e);
+            }
+        }
+    }
+
+    // What each rename replaces, so a failure part-way leaves the list as it was
+    let originals: Vec<Option<Vec<u8>>> = reordered
+        .iter()
+        .map(|task| fs::read(tasks_dir.join(format!("{}.json", task.id))).ok())
+        .collect();
+    for (committed, (task, tmp_path)) in reordered.iter().zip(&staged).enumerate() {
+        if let Err(e) = commit_claude_task(&tasks_dir, tmp_path, task) {
+            for (task, original) in reordered.iter().zip(&originals).take(committed) {
+                let path = tasks_dir.join(format!("{}.json", task.id));
+                let _ = match original {
+                    Some(content) => fs::write(&path, content),
+                    None => fs::remove_file(&path),
+                };
+            }
+            for tmp_path in &staged[committed..] {
+                let _ = fs::remove_file(tmp_path);
+            }
+            return Err(e);
+        }
+    }
+
+    // Files for ids past the end belong to no task any more
+    for task in &existing {
+        if !reordered.iter().any(|t| t.id == task.id) {
+            fs::remove_file(tasks_dir.join(format!("{}.json", task.id)))
+                .map_err(|e| format!("Failed to delete task file: {}", e))?;
+        }
+    }
+
+    sync::remap_claude_task_ids(&task_list_id, &new_ids)?;
+
+    Ok(reordered)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn task(id: &str, status: &str) -> ClaudeTask {
+        ClaudeTask {
+            id: id.to_string(),
+            subject: format!("Task {}", id),
+            status: status.to_string(),
+            description: String::new(),
+            blocks: vec![],
+            blocked_by: vec![],
+            owner: None,
+        }
+    }
+
+    #[test]
+    fn test_diff_task_lists() {
+        let old = vec![task("1", "pending"), task("2", "in_progress"), task("3", "pending"), task("4", "pending")];
+        let mut edited = task("4", "pending");
+        edited.subject = "Renamed".to_string();
+        let new = vec![task("1", "in_progress"), task("2", "completed"), edited, task("5", "pending")];
+
+        let events = diff_task_lists("list", &old, &new);
+        let names: Vec<(&str, &str)> = events.iter().map(|e| (e.event.as_str(), e.task.id.as_str())).collect();
+
+        assert_eq!(
+            names,
+            vec![
+                ("claude-task-started", "1"),
+                ("claude-task-completed", "2"),
+                ("claude-task-updated", "4"),
+                ("claude-task-added", "5"),
+                ("claude-task-removed", "3"),
+            ]
+        );
+        assert_eq!(events[1].previous_status.as_deref(), Some("in_progress"));
+        assert_eq!(events[0].task_list_id, "list");
+    }
+
+    #[test]
+    fn test_diff_task_lists_unchanged() {
+        let tasks = vec![task("1", "pending")];
+        assert!(diff_task_lists("list", &tasks, &tasks).is_empty());
+    }
+
+    #[test]
+    fn test_link_dependencies() {
+        let mut others = vec![task("1", "pending"), task("2", "pending")];
+        others[0].blocks = vec!["3".to_string()];
+
+        let mut edited = task("3", "pending");
+        edited.blocked_by = vec!["2".to_string()];
+
+        let changed = link_dependencies(&mut others, &edited).unwrap();
+        assert_eq!(changed, vec![0, 1]);
+        assert!(others[0].blocks.is_empty());
+        assert_eq!(others[1].blocks, vec!["3"]);
+
+        edited.blocked_by = vec!["3".to_string()];
+        assert!(link_dependencies(&mut others, &edited).is_err());
+        edited.blocked_by = vec!["9".to_string()];
+        assert!(link_dependencies(&mut others, &edited).is_err());
+    }
+
+    #[test]
+    fn test_renumber() {
+        let mut tasks = vec![task("1", "pending"), task("2", "pending"), task("3", "pending")];
+        tasks[2].blocked_by = vec!["1".to_string()];
+        tasks[0].blocks = vec!["3".to_string()];
+
+        let (reordered, new_ids) = renumber(&tasks, &["3".to_string(), "1".to_string()]);
+        let subjects: Vec<&str> = reordered.iter().map(|t| t.subject.as_str()).collect();
+        assert_eq!(subjects, vec!["Task 3", "Task 1", "Task 2"]);
+        assert_eq!(reordered[0].id, "1");
+        assert_eq!(reordered[0].blocked_by, vec!["2"]);
+        assert_eq!(reordered[1].blocks, vec!["1"]);
+        assert_eq!(new_ids["3"], "1");
+        assert_eq!(new_ids["1"], "2");
+        assert_eq!(new_ids["2"], "3");
+    }
+
+    #[test]
+    fn test_next_task_id() {
+        assert_eq!(next_task_id(&[]), "1");
+        assert_eq!(next_task_id(&[task("2", "pending"), task("10", "pending")]), "11");
+    }
+}
@@ -0,0 +1,239 @@
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::sync::LazyLock;
+
+use super::boards;
+use super::history;
+use super::journal;
+use super::merge;
+use super::tasks::{self, TasksMdWriteResult};
+use super::templates::{format_timestamp, parse_timestamp};
+
+/// First line of a comment in a task body: `> [!comment] @alice (human) 2026-10-18T14:05:00Z`.
+/// The comment's body follows on `> ` lines.
+static COMMENT_HEADER_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^> \[!comment\] @(\S+) \((human|agent)\) (\S+)$").unwrap());
+
+/// A note left on a task by a person or an agent
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TaskComment {
+    pub author: String,
+    /// "human" or "agent"
+    #[serde(rename = "authorKind")]
+    pub author_kind: String,
+    /// Milliseconds since the Unix epoch
+    pub timestamp: u64,
+    pub body: String,
+    /// Index among the task's description lines and comments where the comment was read, so
+    /// it is written back in place; new comments go after the description
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub position: Option<usize>,
+}
+
+/// Split a task body into its description and its comments. `None` lines are the task's
+/// subtasks, whose positions are returned alongside. Comment blocks can sit anywhere in the body
+/// outside code fences and remember where they were. A comment runs until the first line that
+/// doesn't start with `>`, so a quote directly below it is part of the comment.
+pub(crate) fn split_comments(lines: Vec<Option<String>>) -> (Vec<String>, Vec<TaskComment>, Vec<usize>) {
+    let mut description: Vec<String> = Vec::new();
+    let mut comments: Vec<TaskComment> = Vec::new();
+    let mut subtasks: Vec<usize> = Vec::new();
+    let mut body: Option<Vec<String>> = None;
+    let mut in_fence = false;
+
+    fn finish(body: &mut Option<Vec<String>>, comments: &mut [TaskComment]) {
+        if let (Some(lines), Some(comment)) = (body.take(), comments.last_mut()) {
+            comment.body = lines.join("\n");
+        }
+    }
+
+    for line in lines {
+        let Some(line) = line else {
+            finish(&mut body, &mut comments);
+            subtasks.push(description.len() + comments.len() + subtasks.len());
+            continue;
+        };
+
+        if !in_fence {
+            if let Some(caps) = COMMENT_HEADER_RE.captures(&line) {
+                if let Some(timestamp) = parse_timestamp(&caps[3]) {
+                    finish(&mut body, &mut comments);
+                    comments.push(TaskComment {
+                        author: caps[1].to_string(),
+                        author_kind: caps[2].to_string(),
+                        timestamp,
+                        body: String::new(),
+                        position: Some(description.len() + comments.len() + subtasks.len()),
+                    });
+                    body = Some(Vec::new());
+                    continue;
+                }
+            }
+
+            if let Some(lines) = body.as_mut() {
+                if let Some(rest) = line.strip_prefix('>') {
+                    lines.push(rest.strip_prefix(' ').unwrap_or(rest).to_string());
+                    continue;
+                }
+                finish(&mut body, &mut comments);
+            }
+        }
+
+        if line.trim().starts_with("```") {
+            in_fence = !in_fence;
+        }
+        description.push(line);
+    }
+    finish(&mut body, &mut comments);
+
+    (description, comments, subtasks)
+}
+
+/// The lines of a comment block, without the task's indentation
+pub(crate) fn render_comment(comment: &TaskComment) -> Vec<String> {
+    let mut lines = vec![format!(
+        "> [!comment] @{} ({}) {}",
+        comment.author,
+        comment.author_kind,
+        format_timestamp(comment.timestamp)
+    )];
+
+    for line in comment.body.lines() {
+        if line.is_empty() {
+            lines.push(">".to_string());
+        } else {
+            lines.push(format!("> {}", line));
+        }
+    }
+
+    lines
+}
+
+fn new_comment(author: &str, author_kind: Option<String>, body: &str, now: u64) -> Result<TaskComment, String> {
+    let author = author.trim().trim_start_matches('@');
+    if author.is_empty() || author.contains(char::is_whitespace) {
+        return Err("Comment authors are a single word, like a username or agent name".to_string());
+    }
+
+    let author_kind = author_kind.unwrap_or_else(|| "human".to_string());
+    if author_kind != "human" && author_kind != "agent" {
+        return Err(format!("Unknown author kind '{}'", author_kind));
+    }
+
+    let body = body.trim_end();
+    if body.trim().is_empty() {
+        return Err("Comment is empty".to_string());
+    }
+
+    Ok(TaskComment {
+        author: author.to_string(),
+        author_kind,
+        timestamp: now,
+        body: body.lines().map(str::trim_end).collect::<Vec<_>>().join("\n"),
+        position: None,
+    })
+}
+
+/// Append a comment to a task's thread
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+pub fn add_task_comment(
+    project_path: String,
+    board: Option<String>,
+    task_subject: String,
+    author: String,
+    author_kind: Option<String>,
+    body: String,
+    base_version: Option<String>,
+    state: tauri::State<'_, crate::state::AppState>,
+    versions: tauri::State<'_, merge::TasksMdVersions>,
+    journal: tauri::State<'_, journal::TaskJournal>,
+) -> Result<TasksMdWriteResult, String> {
+    let comment = new_comment(&author, author_kind, &body, history::now_ms())?;
+    let mut operation = None;
+    let board = boards::board_config(&state, &project_path, board.as_deref())?;
+    let result = tasks::frontend_write(|| {
+        tasks::mutate_board(&project_path, &board, base_version, &versions, true, |tasks| {
+            let index = tasks::find_task_index(tasks, &task_subject)?;
+            let before = tasks[index].clone();
+            let t = &mut tasks[index];
+            t.comments.push(comment);
+            operation = Some(journal::TaskOperation::new("update", index, Some(before), Some(t.clone())));
+            Ok(())
+        })
+    })?;
+
+    journal.record(&project_path, &result, operation.map(|o| o.on_board(&board)));
+    Ok(result)
+}
+
+/// A task's comments, oldest first
+#[tauri::command]
+pub fn list_task_comments(
+    project_path: String,
+    board: Option<String>,
+    task_subject: String,
+    state: tauri::State<'_, crate::state::AppState>,
+) -> Result<Vec<TaskComment>, String> {
+    let tasks = tasks::read_tasks_md(project_path, board, None, state)?;
+    let index = tasks::find_task_index(&tasks, &task_subject)?;
+
+    let mut comments = tasks[index].comments.clone();
+    comments.sort_by_key(|c| c.timestamp);
+    Ok(comments)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_comments_round_trip() {
+        let content = "# TASKS\n\n## Backlog\n- [ ] Add caching\n  Cache the parsed config.\n  > [!comment] @alice (human) 2026-10-18T14:05:00Z\n  > Use the existing LRU.\n  >\n  > Not a new crate.\n  > [!comment] @claude (agent) 2026-10-18T14:20:00Z\n  > Done, see `cache.rs`.\n  - [ ] Benchmark\n\n## Blocked\n\n## In Progress\n\n## Review\n\n## Done\n\n";
+        let tasks = tasks::parse_tasks_md(content).unwrap();
+        let task = &tasks[0];
+
+        assert_eq!(task.description.as_deref(), Some("Cache the parsed config."));
+        assert_eq!(task.comments.len(), 2);
+        assert_eq!(task.comments[0].author, "alice");
+        assert_eq!(task.comments[0].body, "Use the existing LRU.\n\nNot a new crate.");
+        assert_eq!(task.comments[1].author_kind, "agent");
+        assert_eq!(task.subtasks.len(), 1);
+
+        assert_eq!(tasks::render_tasks_md(&tasks), content);
+    }
+
+    #[test]
+    fn test_comments_stay_in_place() {
+        let content = "# TASKS\n\n## Backlog\n- [ ] Add caching\n  > [!comment] @alice (human) 2026-10-18T14:05:00Z\n  > First.\n\n  Cache the parsed config.\n\n  > [!comment] @bob (human) 2026-10-18T14:20:00Z\n  > Second.\n\n  > A quote, not a comment\n\n## Blocked\n\n## In Progress\n\n## Review\n\n## Done\n\n";
+        let tasks = tasks::parse_tasks_md(content).unwrap();
+        let task = &tasks[0];
+
+        assert_eq!(
+            task.description.as_deref(),
+            Some("\nCache the parsed config.\n\n\n> A quote, not a comment")
+        );
+        assert_eq!(task.comments[1].body, "Second.");
+        assert_eq!(tasks::render_tasks_md(&tasks), content);
+
+        // A quote written directly below a comment is kept apart from it
+        let mut task = task.clone();
+        task.description = Some("> Quoted".to_string());
+        task.comments.truncate(1);
+        assert_eq!(
+            tasks::render_task(&task),
+            "- [ ] Add caching\n  > [!comment] @alice (human) 2026-10-18T14:05:00Z\n  > First.\n\n  > Quoted\n"
+        );
+    }
+
+    #[test]
+    fn test_new_comment() {
+        let comment = new_comment("@alice", None, "Looks good  \n", 0).unwrap();
+        assert_eq!((comment.author.as_str(), comment.author_kind.as_str()), ("alice", "human"));
+        assert_eq!(comment.body, "Looks good");
+
+        assert!(new_comment("two words", None, "Hi", 0).is_err());
+        assert!(new_comment("alice", Some("robot".to_string()), "Hi", 0).is_err());
+        assert!(new_comment("alice", None, "  \n", 0).is_err());
+    }
+}
@@ -0,0 +1,421 @@
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::{LazyLock, Mutex};
+
+use super::boards::{self, BoardConfig};
+use super::dependencies;
+use super::merge::{self, task_key};
+use super::settings;
+use super::tasks::{self, TasksMdTask, TasksMdWriteResult};
+
+/// Serializes updates of the advanced-commit store
+static COMMITS_LOCK: Mutex<()> = Mutex::new(());
+
+/// How far back the log is scanned
+const LOG_LIMIT: usize = 1000;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TaskCommit {
+    pub hash: String,
+    #[serde(rename = "shortHash")]
+    pub short_hash: String,
+    /// First line of the commit message
+    pub subject: String,
+    pub author: String,
+    /// Milliseconds since the Unix epoch
+    pub timestamp: u64,
+    /// Whether the commit is reachable from the default branch
+    #[serde(rename = "onDefaultBranch")]
+    pub on_default_branch: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TaskCommits {
+    /// Stable id of the task if it has one, otherwise its subject
+    pub task: String,
+    pub subject: String,
+    /// Newest first
+    pub commits: Vec<TaskCommit>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CommitAdvanceResult {
+    /// Subjects of the tasks that were moved
+    pub advanced: Vec<String>,
+    /// `None` when no task had to move
+    pub write: Option<TasksMdWriteResult>,
+}
+
+/// A commit with its full message, before it is linked to tasks
+struct LoggedCommit {
+    commit: TaskCommit,
+    message: String,
+}
+
+/// Commits already used to advance a task, so a card moved back by hand stays put
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct AdvancedCommits {
+    hashes: HashSet<String>,
+}
+
+fn git(project_path: &str, args: &[&str]) -> Result<String, String> {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(project_path)
+        .args(args)
+        .output()
+        .map_err(|e| format!("Failed to run git: {}", e))?;
+
+    if !output.status.success() {
+        return Err(format!(
+            "git {} failed: {}",
+            args.first().unwrap_or(&""),
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// The branch origin's HEAD points to, else `main` or `master`, else the checked out branch
+fn default_branch(project_path: &str) -> Result<String, String> {
+    if let Ok(head) = git(project_path, &["symbolic-ref", "--short", "refs/remotes/origin/HEAD"]) {
+        let head = head.trim();
+        if !head.is_empty() {
+            return Ok(head.to_string());
+        }
+    }
+
+    for branch in ["main", "master"] {
+        if git(project_path, &["rev-parse", "--verify", "--quiet", branch]).is_ok() {
+            return Ok(branch.to_string());
+        }
+    }
+
+    Ok(git(project_path, &["rev-parse", "--abbrev-ref", "HEAD"])?.trim().to_string())
+}
+
+/// Parse `git log` output written with `--format=%H%x1f%h%x1f%an%x1f%at%x1f%B%x1e`
+fn parse_log(output: &str, default_hashes: &HashSet<String>) -> Vec<LoggedCommit> {
+    output
+        .split('\x1e')
+        .filter_map(|record| {
+            let mut fields = record.trim_start_matches('\n').splitn(5, '\x1f');
+            let hash = fields.next()?.to_string();
+            let short_hash = fields.next()?.to_string();
+            let author = fields.next()?.to_string();
+            let timestamp = fields.next()?.parse::<u64>().ok()? * 1000;
+            let message = fields.next()?.trim().to_string();
+
+            Some(LoggedCommit {
+                commit: TaskCommit {
+                    on_default_branch: default_hashes.contains(&hash),
+                    subject: message.lines().next().unwrap_or_default().to_string(),
+                    hash,
+                    short_hash,
+                    author,
+                    timestamp,
+                },
+                message,
+            })
+        })
+        .collect()
+}
+
+/// Recent commits on all local branches, newest first
+fn read_commits(project_path: &str) -> Result<Vec<LoggedCommit>, String> {
+    let limit = format!("-n{}", LOG_LIMIT);
+    let branch = default_branch(project_path)?;
+
+    let default_hashes: HashSet<String> = git(project_path, &["rev-list", &limit, &branch])?
+        .lines()
+        .map(|l| l.trim().to_string())
+        .collect();
+
+    let log = git(
+        project_path,
+        &["log", "--branches", &limit, "--format=%H%x1f%h%x1f%an%x1f%at%x1f%B%x1e"],
+    )?;
+
+    Ok(parse_log(&log, &default_hashes))
+}
+
+static TRAILER_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"(?im)^\s*task:\s*(.+?)\s*$").unwrap());
+static ID_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"(?:^|\s)id:([\w.-]+)").unwrap());
+
+/// Task references in a commit message: `Task: <id or subject>` trailer lines and `id:<id>`
+/// tokens, the same syntax TASKS.md uses for stable ids
+pub fn task_references(message: &str) -> Vec<String> {
+    let mut references: Vec<String> = Vec::new();
+    let found = TRAILER_RE
+        .captures_iter(message)
+        .map(|c| c[1].to_string())
+        .chain(ID_RE.captures_iter(message).map(|c| c[1].to_string()));
+
+    for reference in found {
+        if !references.contains(&reference) {
+            references.push(reference);
+        }
+    }
+
+    references
+}
+
+/// Commits per task index, keeping the log's newest-first order
+fn link_commits(tasks: &[TasksMdTask], commits: &[LoggedCommit]) -> Vec<Vec<TaskCommit>> {
+    let mut linked = vec![Vec::new(); tasks.len()];
+
+    for logged in commits {
+        let mut indexes: Vec<usize> = task_references(&logged.message)
+            .iter()
+            .filter_map(|r| dependencies::resolve_task_ref(tasks, r).ok())
+            .collect();
+        indexes.sort();
+        indexes.dedup();
+
+        for index in indexes {
+            if !linked[index].contains(&logged.commit) {
+                linked[index].push(logged.commit.clone());
+            }
+        }
+    }
+
+    linked
+}
+
+/// Move tasks with a not yet used commit on the default branch forward to `target`. Tasks are
+/// never moved backwards. Returns the subjects moved and the commits used.
+fn advance_tasks(
+    tasks: &mut [TasksMdTask],
+    board: &BoardConfig,
+    linked: &[Vec<TaskCommit>],
+    target: &str,
+    already_used: &HashSet<String>,
+) -> (Vec<String>, Vec<String>) {
+    let mut advanced = Vec::new();
+    let mut used = Vec::new();
+
+    for (task, commits) in tasks.iter_mut().zip(linked) {
+        let fresh: Vec<&TaskCommit> = commits
+            .iter()
+            .filter(|c| c.on_default_branch && !already_used.contains(&c.hash))
+            .collect();
+        if fresh.is_empty() {
+            continue;
+        }
+
+        used.extend(fresh.iter().map(|c| c.hash.clone()));
+        if board.column_rank(&task.column) < board.column_rank(target) {
+            task.column = target.to_string();
+            task.completed = target == "done";
+            advanced.push(task.subject.clone());
+        }
+    }
+
+    (advanced, used)
+}
+
+/// Keyed by the board's history key, so each board of a project uses commits separately
+fn store_path(board_key: &str) -> PathBuf {
+    crate::state::config_dir()
+        .join("commits")
+        .join(format!("{}.json", merge::content_hash(board_key)))
+}
+
+fn load_store(board_key: &str) -> Result<AdvancedCommits, String> {
+    let path = store_path(board_key);
+
+    if !path.exists() {
+        return Ok(AdvancedCommits::default());
+    }
+
+    let content = fs::read_to_string(&path)
+        .map_err(|e| format!("Failed to read advanced commits: {}", e))?;
+
+    serde_json::from_str(&content).map_err(|e| format!("Failed to parse advanced commits: {}", e))
+}
+
+fn save_store(board_key: &str, store: &AdvancedCommits) -> Result<(), String> {
+    let path = store_path(board_key);
+
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create commits directory: {}", e))?;
+    }
+
+    let content = serde_json::to_string(store)
+        .map_err(|e| format!("Failed to serialize advanced commits: {}", e))?;
+
+    fs::write(&path, content).map_err(|e| format!("Failed to write advanced commits: {}", e))
+}
+
+/// Advance the cards of tasks referenced by commits that landed on the default branch to the
+/// column set in `commitAdvanceColumn`. Does nothing when that setting is off, the board has
+/// no such column or the project is not a git repository.
+pub fn advance_tasks_from_commits(
+    project_path: &str,
+    board: &BoardConfig,
+    versions: &merge::TasksMdVersions,
+) -> Result<CommitAdvanceResult, String> {
+    let unchanged = CommitAdvanceResult {
+        advanced: vec![],
+        write: None,
+    };
+
+    let Some(target) = settings::get_settings().ok().and_then(|s| s.commit_advance_column) else {
+        return Ok(unchanged);
+    };
+    if board.check_column(&target).is_err() || !Path::new(project_path).join(".git").exists() {
+        return Ok(unchanged);
+    }
+
+    let _guard = COMMITS_LOCK.lock().map_err(|e| e.to_string())?;
+
+    let commits = read_commits(project_path)?;
+    let store_key = board.history_key(project_path);
+    let mut store = load_store(&store_key)?;
+
+    let current = tasks::load_board(project_path, board)?;
+    let mut preview = current.clone();
+    let (would_advance, used) =
+        advance_tasks(&mut preview, board, &link_commits(&current, &commits), &target, &store.hashes);
+    if used.is_empty() {
+        return Ok(unchanged);
+    }
+
+    let mut advanced = Vec::new();
+    let write = if would_advance.is_empty() {
+        None
+    } else {
+        Some(tasks::mutate_board(project_path, board, None, versions, true, |tasks| {
+            let linked = link_commits(tasks, &commits);
+            advanced = advance_tasks(tasks, board, &linked, &target, &store.hashes).0;
+            Ok(())
+        })?)
+    };
+
+    store.hashes.extend(used);
+    save_store(&store_key, &store)?;
+
+    Ok(CommitAdvanceResult { advanced, write })
+}
+
+/// Commits that reference TASKS.md tasks, per task. With `task_id` (a stable id or subject)
+/// only that task's commits are returned.
+#[tauri::command]
+pub fn get_task_commits(
+    project_path: String,
+    board: Option<String>,
+    task_id: Option<String>,
+    state: tauri::State<'_, crate::state::AppState>,
+) -> Result<Vec<TaskCommits>, String> {
+    let board = boards::board_config(&state, &project_path, board.as_deref())?;
+    let tasks = tasks::load_board(&project_path, &board)?;
+    let commits = read_commits(&project_path)?;
+    let linked = link_commits(&tasks, &commits);
+
+    let only = match &task_id {
+        Some(reference) => Some(dependencies::resolve_task_ref(&tasks, reference)?),
+        None => None,
+    };
+
+    Ok(tasks
+        .iter()
+        .zip(linked)
+        .enumerate()
+        .filter(|(i, (_, commits))| only.map(|o| o == *i).unwrap_or(!commits.is_empty()))
+        .map(|(_, (task, commits))| TaskCommits {
+            task: task_key(task).to_string(),
+            subject: task.subject.clone(),
+            commits,
+        })
+        .collect())
+}
+
+/// Scan the log now and advance cards as the scheduler would
+#[tauri::command]
+pub fn advance_tasks_from_git(
+    project_path: String,
+    board: Option<String>,
+    state: tauri::State<'_, crate::state::AppState>,
+    versions: tauri::State<'_, merge::TasksMdVersions>,
+) -> Result<CommitAdvanceResult, String> {
+    let board = boards::board_config(&state, &project_path, board.as_deref())?;
+    if let Some(target) = settings::get_settings().ok().and_then(|s| s.commit_advance_column) {
+        board.check_column(&target)?;
+    }
+    advance_tasks_from_commits(&project_path, &board, &versions)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn logged(hash: &str, message: &str, on_default_branch: bool) -> LoggedCommit {
+        LoggedCommit {
+            commit: TaskCommit {
+                hash: hash.to_string(),
+                short_hash: hash.to_string(),
+                subject: message.lines().next().unwrap_or_default().to_string(),
+                author: "dev".to_string(),
+                timestamp: 0,
+                on_default_branch,
+            },
+            message: message.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_task_references() {
+        let message = "Fix login redirect id:auth-1\n\nTask: Write docs\ntask: auth-1\n";
+        assert_eq!(task_references(message), vec!["Write docs", "auth-1"]);
+        assert!(task_references("Bump deps; valid:nothing").is_empty());
+    }
+
+    #[test]
+    fn test_parse_log() {
+        let output = "abc\x1fa\x1fdev\x1f1700000000\x1fFirst line\n\nTask: Docs\n\x1e\ndef\x1fd\x1fdev\x1f1700000100\x1fOther\n\x1e\n";
+        let default_hashes: HashSet<String> = ["abc".to_string()].into_iter().collect();
+
+        let commits = parse_log(output, &default_hashes);
+        assert_eq!(commits.len(), 2);
+        assert_eq!(commits[0].commit.subject, "First line");
+        assert_eq!(commits[0].commit.timestamp, 1_700_000_000_000);
+        assert!(commits[0].commit.on_default_branch);
+        assert!(!commits[1].commit.on_default_branch);
+        assert_eq!(commits[0].message, "First line\n\nTask: Docs");
+    }
+
+    #[test]
+    fn test_link_and_advance() {
+        let content = "# TASKS\n\n## In Progress\n- [ ] Login id:auth\n- [ ] Write docs\n\n## Done\n- [x] Old id:old\n";
+        let mut tasks = tasks::parse_tasks_md(content).unwrap();
+        let commits = vec![
+            logged("c3", "WIP on a branch\n\nTask: Write docs", false),
+            logged("c2", "Reopen id:old", true),
+            logged("c1", "Login works id:auth", true),
+        ];
+
+        let linked = link_commits(&tasks, &commits);
+        assert_eq!(linked[0].len(), 1);
+        assert_eq!(linked[1][0].hash, "c3");
+        assert_eq!(linked[2][0].hash, "c2");
+
+        let (advanced, used) = advance_tasks(&mut tasks, &BoardConfig::default(), &linked, "review", &HashSet::new());
+        assert_eq!(advanced, vec!["Login"]);
+        assert_eq!(used, vec!["c1", "c2"]);
+        assert_eq!(tasks[0].column, "review");
+        assert_eq!(tasks[1].column, "in_progress");
+        assert_eq!(tasks[2].column, "done");
+
+        // Moved back by hand: the same commit doesn't advance it again
+        tasks[0].column = "in_progress".to_string();
+        let already_used: HashSet<String> = used.into_iter().collect();
+        let (advanced, _) = advance_tasks(&mut tasks, &BoardConfig::default(), &linked, "review", &already_used);
+        assert!(advanced.is_empty());
+    }
+}
@@ -0,0 +1,368 @@
+use serde::{Deserialize, Serialize};
+
+use super::boards;
+use super::merge;
+use super::tasks::{self, TasksMdTask};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MissingDependency {
+    #[serde(rename = "taskSubject")]
+    pub task_subject: String,
+    pub reference: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DependencyReport {
+    pub missing: Vec<MissingDependency>,
+    /// References whose subject slug matches more than one task
+    pub ambiguous: Vec<MissingDependency>,
+    /// Each cycle as the subjects of the tasks that form it
+    pub cycles: Vec<Vec<String>>,
+    /// Unfinished tasks whose upstream tasks are all done
+    pub startable: Vec<String>,
+    /// Unfinished tasks waiting on at least one unfinished upstream task, or on a reference
+    /// that is missing or ambiguous
+    pub blocked: Vec<String>,
+}
+
+/// Lowercase a subject and collapse everything that isn't alphanumeric into single dashes,
+/// so "Set up CI" can be referenced as `blocked-by:set-up-ci`
+pub fn slugify(subject: &str) -> String {
+    let mut slug = String::new();
+    for c in subject.chars() {
+        if c.is_alphanumeric() {
+            slug.extend(c.to_lowercase());
+        } else if !slug.is_empty() && !slug.ends_with('-') {
+            slug.push('-');
+        }
+    }
+    slug.trim_end_matches('-').to_string()
+}
+
+/// Indices of the tasks a reference could mean: the task with that stable id, otherwise every
+/// task whose subject slug matches
+fn ref_matches(tasks: &[TasksMdTask], reference: &str) -> Vec<usize> {
+    if let Some(index) = tasks.iter().position(|t| t.stable_id.as_deref() == Some(reference)) {
+        return vec![index];
+    }
+
+    let reference = slugify(reference);
+    (0..tasks.len())
+        .filter(|&i| slugify(&tasks[i].subject) == reference)
+        .collect()
+}
+
+/// Resolve a reference to a task index: stable ids win over subject slugs, and a slug shared
+/// by several tasks is an error rather than a guess
+pub fn resolve_task_ref(tasks: &[TasksMdTask], reference: &str) -> Result<usize, String> {
+    match ref_matches(tasks, reference).as_slice() {
+        [index] => Ok(*index),
+        [] => Err(format!("No task matches '{}'", reference)),
+        several => {
+            let subjects: Vec<&str> = several.iter().map(|&i| tasks[i].subject.as_str()).collect();
+            Err(format!(
+                "'{}' matches several tasks ({}); refer to one by its id: token",
+                reference,
+                subjects.join(", ")
+            ))
+        }
+    }
+}
+
+fn is_done(task: &TasksMdTask) -> bool {
+    task.column == "done" || task.completed
+}
+
+fn upstream(tasks: &[TasksMdTask], index: usize) -> Vec<usize> {
+    tasks[index]
+        .blocked_by
+        .iter()
+        .filter_map(|r| resolve_task_ref(tasks, r).ok())
+        .collect()
+}
+
+/// Whether any of a task's `blocked-by:` references is missing or ambiguous
+fn has_unresolved(tasks: &[TasksMdTask], index: usize) -> bool {
+    tasks[index].blocked_by.iter().any(|r| resolve_task_ref(tasks, r).is_err())
+}
+
+/// Find dependency cycles as groups of task indices (strongly connected components with more
+/// than one task, or a task that references itself)
+pub fn find_cycles(tasks: &[TasksMdTask]) -> Vec<Vec<usize>> {
+    let edges: Vec<Vec<usize>> = (0..tasks.len()).map(|i| upstream(tasks, i)).collect();
+    find_graph_cycles(&edges)
+}
+
+/// Cycles in a graph given as adjacency lists, in the same form as `find_cycles`
+pub fn find_graph_cycles(edges: &[Vec<usize>]) -> Vec<Vec<usize>> {
+    struct Tarjan {
+        edges: Vec<Vec<usize>>,
+        index: Vec<Option<usize>>,
+        lowlink: Vec<usize>,
+        on_stack: Vec<bool>,
+        stack: Vec<usize>,
+        next_index: usize,
+        cycles: Vec<Vec<usize>>,
+    }
+
+    impl Tarjan {
+        fn visit(&mut self, v: usize) {
+            self.index[v] = Some(self.next_index);
+            self.lowlink[v] = self.next_index;
+            self.next_index += 1;
+            self.stack.push(v);
+            self.on_stack[v] = true;
+
+            for w in self.edges[v].clone() {
+                match self.index[w] {
+                    None => {
+                        self.visit(w);
+                        self.lowlink[v] = self.lowlink[v].min(self.lowlink[w]);
+                    }
+                    Some(w_index) if self.on_stack[w] => {
+                        self.lowlink[v] = self.lowlink[v].min(w_index);
+                    }
+                    _ => {}
+                }
+            }
+
+            if Some(self.lowlink[v]) == self.index[v] {
+                let mut component = Vec::new();
+                while let Some(w) = self.stack.pop() {
+                    self.on_stack[w] = false;
+                    component.push(w);
+                    if w == v {
+                        break;
+                    }
+                }
+                if component.len() > 1 || self.edges[v].contains(&v) {
+                    component.reverse();
+                    self.cycles.push(component);
+                }
+            }
+        }
+    }
+
+    let n = edges.len();
+    let mut tarjan = Tarjan {
+        edges: edges.to_vec(),
+        index: vec![None; n],
+        lowlink: vec![0; n],
+        on_stack: vec![false; n],
+        stack: Vec::new(),
+        next_index: 0,
+        cycles: Vec::new(),
+    };
+
+    for v in 0..n {
+        if tarjan.index[v].is_none() {
+            tarjan.visit(v);
+        }
+    }
+
+    tarjan.cycles
+}
+
+/// Reject an edit that leaves the given task inside a dependency cycle
+pub fn ensure_acyclic(tasks: &[TasksMdTask], subject: &str) -> Result<(), String> {
+    for cycle in find_cycles(tasks) {
+        if cycle.iter().any(|&i| tasks[i].subject == subject) {
+            let names: Vec<&str> = cycle.iter().map(|&i| tasks[i].subject.as_str()).collect();
+            return Err(format!("Dependency cycle: {}", names.join(" -> ")));
+        }
+    }
+    Ok(())
+}
+
+/// Whether any of a task's upstream tasks is unfinished; tasks in a cycle always are
+fn is_waiting(tasks: &[TasksMdTask], index: usize) -> bool {
+    upstream(tasks, index).iter().any(|&u| !is_done(&tasks[u]))
+}
+
+pub fn analyze_dependencies(tasks: &[TasksMdTask]) -> DependencyReport {
+    let mut missing = Vec::new();
+    let mut ambiguous = Vec::new();
+    let mut startable = Vec::new();
+    let mut blocked = Vec::new();
+
+    for (i, task) in tasks.iter().enumerate() {
+        for reference in &task.blocked_by {
+            let dependency = MissingDependency {
+                task_subject: task.subject.clone(),
+                reference: reference.clone(),
+            };
+            match ref_matches(tasks, reference).len() {
+                0 => missing.push(dependency),
+                1 => {}
+                _ => ambiguous.push(dependency),
+            }
+        }
+
+        if is_done(task) {
+            continue;
+        }
+
+        if is_waiting(tasks, i) || has_unresolved(tasks, i) {
+            blocked.push(task.subject.clone());
+        } else {
+            startable.push(task.subject.clone());
+        }
+    }
+
+    let cycles = find_cycles(tasks)
+        .into_iter()
+        .map(|cycle| cycle.into_iter().map(|i| tasks[i].subject.clone()).collect())
+        .collect();
+
+    DependencyReport {
+        missing,
+        ambiguous,
+        cycles,
+        startable,
+        blocked,
+    }
+}
+
+/// Move Backlog tasks with unfinished upstream tasks into Blocked, and move Blocked tasks whose
+/// `blocked-by:` references are all done back to Backlog. Tasks without references, tasks with
+/// a reference that is missing or ambiguous, and tasks a human has already started, are left
+/// where they are. Returns whether anything moved.
+pub fn apply_auto_blocking(tasks: &mut [TasksMdTask]) -> bool {
+    let moves: Vec<(usize, &str)> = (0..tasks.len())
+        .filter(|&i| !tasks[i].blocked_by.is_empty() && !has_unresolved(tasks, i))
+        .filter_map(|i| {
+            let waiting = is_waiting(tasks, i);
+            match tasks[i].column.as_str() {
+                "backlog" if waiting => Some((i, "blocked")),
+                "blocked" if !waiting => Some((i, "backlog")),
+                _ => None,
+            }
+        })
+        .collect();
+
+    for &(i, column) in &moves {
+        tasks[i].column = column.to_string();
+    }
+
+    !moves.is_empty()
+}
+
+/// Report missing references, cycles, and which tasks are startable or blocked
+#[tauri::command]
+pub fn check_tasks_md_dependencies(
+    project_path: String,
+    board: Option<String>,
+    state: tauri::State<'_, crate::state::AppState>,
+) -> Result<DependencyReport, String> {
+    let board = boards::board_config(&state, &project_path, board.as_deref())?;
+    let tasks = tasks::load_board(&project_path, &board)?;
+    Ok(analyze_dependencies(&tasks))
+}
+
+/// Apply auto-blocking now, e.g. after a board file was edited outside the app.
+/// Returns whether any task changed column.
+#[tauri::command]
+pub fn refresh_tasks_md_blocking(
+    project_path: String,
+    board: Option<String>,
+    state: tauri::State<'_, crate::state::AppState>,
+    versions: tauri::State<'_, merge::TasksMdVersions>,
+) -> Result<bool, String> {
+    let board = boards::board_config(&state, &project_path, board.as_deref())?;
+    board.check_column("blocked")?;
+
+    // Nothing to write when no task would move
+    if !apply_auto_blocking(&mut tasks::load_board(&project_path, &board)?) {
+        return Ok(false);
+    }
+
+    let mut moved = false;
+    tasks::mutate_board(&project_path, &board, None, &versions, true, |tasks| {
+        moved = apply_auto_blocking(tasks);
+        Ok(())
+    })?;
+    Ok(moved)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_slugify() {
+        assert_eq!(slugify("Set up CI"), "set-up-ci");
+        assert_eq!(slugify("  Fix: login (again)! "), "fix-login-again");
+    }
+
+    #[test]
+    fn test_analyze_dependencies() {
+        let content = r#"# TASKS
+
+## Backlog
+- [ ] Deploy blocked-by:build
+- [ ] Docs blocked-by:nowhere
+- [ ] A blocked-by:b
+- [ ] B blocked-by:a
+
+## In Progress
+- [ ] Build id:build
+
+## Done
+- [x] Design
+"#;
+        let tasks = tasks::parse_tasks_md(content).unwrap();
+        let report = analyze_dependencies(&tasks);
+
+        assert_eq!(report.missing.len(), 1);
+        assert_eq!(report.missing[0].task_subject, "Docs");
+        assert_eq!(report.missing[0].reference, "nowhere");
+
+        assert_eq!(report.cycles, vec![vec!["A".to_string(), "B".to_string()]]);
+        assert!(report.blocked.contains(&"Deploy".to_string()));
+        assert!(report.blocked.contains(&"Docs".to_string()));
+        assert!(!report.startable.contains(&"Docs".to_string()));
+        assert!(report.startable.contains(&"Build".to_string()));
+        assert!(!report.startable.contains(&"Design".to_string()));
+    }
+
+    #[test]
+    fn test_apply_auto_blocking() {
+        let content = r#"# TASKS
+
+## Backlog
+- [ ] Deploy blocked-by:build
+
+## Blocked
+- [ ] Announce blocked-by:design
+- [ ] Waiting on vendor
+- [ ] Launch blocked-by:vendor-contract
+
+## In Progress
+- [ ] Build id:build
+
+## Done
+- [x] Design
+"#;
+        let mut tasks = tasks::parse_tasks_md(content).unwrap();
+        assert!(apply_auto_blocking(&mut tasks));
+
+        assert_eq!(tasks[0].column, "blocked");
+        assert_eq!(tasks[1].column, "backlog");
+        assert_eq!(tasks[2].column, "blocked");
+        // An unresolved reference doesn't count as done
+        assert_eq!(tasks[3].column, "blocked");
+    }
+
+    #[test]
+    fn test_resolve_ambiguous_ref() {
+        let content = "## Backlog\n- [ ] Set up CI\n- [ ] Set up: CI\n- [ ] Deploy blocked-by:set-up-ci\n";
+        let tasks = tasks::parse_tasks_md(content).unwrap();
+
+        assert!(resolve_task_ref(&tasks, "set-up-ci").unwrap_err().contains("several tasks"));
+        assert!(resolve_task_ref(&tasks, "deploy").is_ok());
+
+        let report = analyze_dependencies(&tasks);
+        assert_eq!(report.ambiguous.len(), 1);
+        assert!(report.missing.is_empty());
+    }
+}
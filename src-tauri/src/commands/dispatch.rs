@@ -0,0 +1,222 @@
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter};
+
+use super::boards;
+use super::dependencies;
+use super::merge;
+use super::settings;
+use super::tasks::{self, TasksMdSubtask, TasksMdTask, TasksMdWriteResult};
+use super::terminal::{self, TerminalManager};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DispatchResult {
+    #[serde(rename = "terminalId")]
+    pub terminal_id: String,
+    /// Whether a new terminal was started for the prompt
+    pub spawned: bool,
+    pub prompt: String,
+    pub write: TasksMdWriteResult,
+}
+
+fn render_subtask_lines(out: &mut Vec<String>, subtasks: &[TasksMdSubtask], depth: usize) {
+    for subtask in subtasks {
+        let checkbox = if subtask.completed { "[x]" } else { "[ ]" };
+        out.push(format!("{}- {} {}", "  ".repeat(depth), checkbox, subtask.subject));
+        render_subtask_lines(out, &subtask.subtasks, depth + 1);
+    }
+}
+
+/// Fill a prompt template. Placeholders: `{subject}`, `{description}`, `{subtasks}` (as a
+/// checklist), `{comments}` (as `@author: body` lines, continuation lines indented), `{tags}`,
+/// `{priority}`, `{project}`. Runs of blank lines left by empty placeholders collapse into one.
+pub fn render_prompt(template: &str, task: &TasksMdTask, project_name: &str) -> String {
+    let mut subtask_lines = Vec::new();
+    render_subtask_lines(&mut subtask_lines, &task.subtasks, 0);
+
+    let tags: Vec<String> = task.tags.iter().map(|t| format!("#{}", t)).collect();
+    let comments: Vec<String> = task
+        .comments
+        .iter()
+        .map(|c| format!("@{}: {}", c.author, c.body.lines().collect::<Vec<_>>().join("\n  ")))
+        .collect();
+
+    let filled = template
+        .replace("{subject}", &task.subject)
+        .replace("{description}", task.description.as_deref().unwrap_or(""))
+        .replace("{subtasks}", &subtask_lines.join("\n"))
+        .replace("{comments}", &comments.join("\n"))
+        .replace("{tags}", &tags.join(" "))
+        .replace("{priority}", task.priority.as_deref().unwrap_or(""))
+        .replace("{project}", project_name);
+
+    let mut lines: Vec<&str> = Vec::new();
+    for line in filled.lines() {
+        let blank = line.trim().is_empty();
+        if blank && lines.last().map(|l| l.trim().is_empty()).unwrap_or(true) {
+            continue;
+        }
+        lines.push(line.trim_end());
+    }
+    while lines.last().map(|l| l.is_empty()).unwrap_or(false) {
+        lines.pop();
+    }
+
+    lines.join("\n")
+}
+
+/// Quote a string for a POSIX shell command line
+fn shell_quote(text: &str) -> String {
+    format!("'{}'", text.replace('\'', r"'\''"))
+}
+
+/// Input that submits a prompt to an agent already running in a terminal. Bracketed paste
+/// keeps multi-line prompts together instead of submitting at the first newline.
+fn paste_input(prompt: &str) -> String {
+    format!("\x1b[200~{}\x1b[201~\r", prompt)
+}
+
+/// A terminal of the project that runs the agent directly. Shell terminals are never used:
+/// if the agent in them has exited, the prompt would run as shell commands.
+fn agent_terminal_for_project(manager: &TerminalManager, project_id: &str) -> Option<String> {
+    let terminals = manager.terminals.lock().ok()?;
+    terminals
+        .values()
+        .find(|t| t.project_id == project_id && t.agent && t.running.lock().map(|r| *r).unwrap_or(false))
+        .map(|t| t.id.clone())
+}
+
+/// Send a board task to the project's agent: move the card to In Progress, render the prompt
+/// and paste it into a terminal running the agent that an earlier dispatch started. Otherwise
+/// a terminal is spawned that runs the auto-start command with the prompt as its argument and
+/// a `task-dispatched` event tells the frontend to show it. `task_id` is a task's stable id or
+/// its subject; tasks in Done are refused. The card moves back when the prompt can't be sent.
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+pub fn dispatch_task(
+    project_id: String,
+    board: Option<String>,
+    task_id: String,
+    prompt_template: Option<String>,
+    base_version: Option<String>,
+    app: AppHandle,
+    state: tauri::State<'_, crate::state::AppState>,
+    terminals: tauri::State<'_, TerminalManager>,
+    versions: tauri::State<'_, merge::TasksMdVersions>,
+) -> Result<DispatchResult, String> {
+    let project = {
+        let projects = state.projects.lock().map_err(|e| e.to_string())?;
+        projects
+            .iter()
+            .find(|p| p.id == project_id)
+            .cloned()
+            .ok_or("Project not found")?
+    };
+
+    let board = boards::board_config(&state, &project.path, board.as_deref())?;
+    let settings = settings::get_settings().unwrap_or_default();
+    if settings.auto_start_command.trim().is_empty() {
+        return Err("Set an auto-start command before dispatching tasks".to_string());
+    }
+    let template = prompt_template.unwrap_or(settings.dispatch_prompt_template);
+
+    let mut dispatched: Option<(TasksMdTask, String)> = None;
+    let write = tasks::mutate_board(&project.path, &board, base_version, &versions, true, |tasks| {
+        let index = dependencies::resolve_task_ref(tasks, &task_id)?;
+        let task = &mut tasks[index];
+        if task.column == "done" || task.completed {
+            return Err(format!("'{}' is already done", task.subject));
+        }
+        let previous_column = task.column.clone();
+        if board.check_column("in_progress").is_ok() {
+            task.column = "in_progress".to_string();
+        }
+        dispatched = Some((task.clone(), previous_column));
+        Ok(())
+    })?;
+
+    if !write.conflicts.is_empty() {
+        return Err(format!("{} changed on disk; resolve the conflicts before dispatching", board.file));
+    }
+    let (task, previous_column) = dispatched.ok_or_else(|| format!("Task '{}' not found", task_id))?;
+
+    let prompt = render_prompt(&template, &task, &project.name);
+
+    let sent = match agent_terminal_for_project(&terminals, &project.id) {
+        Some(terminal_id) => terminal::write_to_terminal(&terminal_id, &paste_input(&prompt), &terminals)
+            .map(|_| (terminal_id, false)),
+        None => {
+            let command = format!("{} {}", settings.auto_start_command.trim(), shell_quote(&prompt));
+            terminal::spawn_terminal_process(
+                project.id.clone(),
+                project.path.clone(),
+                Some(&command),
+                app.clone(),
+                &terminals,
+            )
+            .map(|terminal_id| (terminal_id, true))
+        }
+    };
+
+    // The card only stays in In Progress once the agent actually has the prompt
+    let (terminal_id, spawned) = match sent {
+        Ok(sent) => sent,
+        Err(e) => {
+            let restored = tasks::mutate_board(&project.path, &board, None, &versions, true, |tasks| {
+                let index = dependencies::resolve_task_ref(tasks, &task_id)?;
+                if tasks[index].column == "in_progress" {
+                    tasks[index].column = previous_column;
+                }
+                Ok(())
+            });
+            if let Err(restore_error) = restored {
+                eprintln!("Failed to move '{}' back after a failed dispatch: {}", task.subject, restore_error);
+            }
+            return Err(e);
+        }
+    };
+
+    let _ = app.emit("task-dispatched", serde_json::json!({
+        "projectId": project.id,
+        "terminalId": terminal_id,
+        "subject": task.subject,
+        "spawned": spawned,
+    }));
+
+    Ok(DispatchResult {
+        terminal_id,
+        spawned,
+        prompt,
+        write,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_prompt() {
+        let mut task = TasksMdTask::new("Fix login #auth !high", None, "backlog");
+        task.subtasks = vec![TasksMdSubtask {
+            id: "s".to_string(),
+            parent_id: task.id.clone(),
+            subject: "Repro".to_string(),
+            completed: true,
+            line_number: 0,
+            subtasks: vec![],
+            position: None,
+        }];
+
+        let prompt = render_prompt(&settings::default_dispatch_prompt_template(), &task, "station");
+        assert_eq!(prompt, "Work on this task from TASKS.md: Fix login\n\n- [x] Repro");
+
+        let prompt = render_prompt("[{project}] {subject} ({priority}) {tags}", &task, "station");
+        assert_eq!(prompt, "[station] Fix login (high) #auth");
+    }
+
+    #[test]
+    fn test_shell_quote() {
+        assert_eq!(shell_quote("it's done"), r"'it'\''s done'");
+        assert_eq!(paste_input("a\nb"), "\x1b[200~a\nb\x1b[201~\r");
+    }
+}
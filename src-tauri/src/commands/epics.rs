@@ -0,0 +1,133 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use super::boards;
+use super::journal;
+use super::merge;
+use super::tasks::{self, TasksMdTask, TasksMdWriteResult};
+
+/// Completion of an epic's tasks across all columns of a board
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EpicProgress {
+    pub epic: String,
+    pub total: usize,
+    pub completed: usize,
+    pub percentage: f64,
+    /// Number of the epic's tasks per column id
+    pub columns: HashMap<String, usize>,
+}
+
+/// Progress of every epic on a board, in order of first appearance
+pub fn epic_progress(tasks: &[TasksMdTask]) -> Vec<EpicProgress> {
+    let mut epics: Vec<EpicProgress> = Vec::new();
+
+    for task in tasks {
+        let Some(epic) = &task.epic else {
+            continue;
+        };
+
+        let index = match epics.iter().position(|e| e.epic == *epic) {
+            Some(index) => index,
+            None => {
+                epics.push(EpicProgress {
+                    epic: epic.clone(),
+                    total: 0,
+                    completed: 0,
+                    percentage: 0.0,
+                    columns: HashMap::new(),
+                });
+                epics.len() - 1
+            }
+        };
+
+        let progress = &mut epics[index];
+        progress.total += 1;
+        if task.completed {
+            progress.completed += 1;
+        }
+        *progress.columns.entry(task.column.clone()).or_insert(0) += 1;
+    }
+
+    for progress in &mut epics {
+        progress.percentage = (progress.completed as f64 / progress.total as f64) * 100.0;
+    }
+
+    epics
+}
+
+/// An epic name as it can be written after `### `; blank means no epic
+fn normalize_epic(epic: Option<String>) -> Result<Option<String>, String> {
+    let Some(epic) = epic else {
+        return Ok(None);
+    };
+
+    let epic = epic.trim().trim_start_matches('#').trim();
+    if epic.contains(['\n', '\r']) {
+        return Err("Epic names must fit on one line".to_string());
+    }
+
+    Ok((!epic.is_empty()).then(|| epic.to_string()))
+}
+
+/// Progress of each epic of a board (TASKS.md unless `board` is given)
+#[tauri::command]
+pub fn get_epic_progress(
+    project_path: String,
+    board: Option<String>,
+    state: tauri::State<'_, crate::state::AppState>,
+) -> Result<Vec<EpicProgress>, String> {
+    let tasks = tasks::read_tasks_md(project_path, board, None, state)?;
+    Ok(epic_progress(&tasks))
+}
+
+/// Move a task into an epic, or out of any epic when `epic` is unset. The task keeps its column.
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+pub fn set_task_epic(
+    project_path: String,
+    board: Option<String>,
+    task_subject: String,
+    epic: Option<String>,
+    base_version: Option<String>,
+    state: tauri::State<'_, crate::state::AppState>,
+    versions: tauri::State<'_, merge::TasksMdVersions>,
+    journal: tauri::State<'_, journal::TaskJournal>,
+) -> Result<TasksMdWriteResult, String> {
+    let epic = normalize_epic(epic)?;
+    let mut operation = None;
+    let board = boards::board_config(&state, &project_path, board.as_deref())?;
+    let result = tasks::frontend_write(|| {
+        tasks::mutate_board(&project_path, &board, base_version, &versions, true, |tasks| {
+            let index = tasks::find_task_index(tasks, &task_subject)?;
+            let before = tasks[index].clone();
+            let t = &mut tasks[index];
+            t.epic = epic;
+            operation = Some(journal::TaskOperation::new("update", index, Some(before), Some(t.clone())));
+            Ok(())
+        })
+    })?;
+
+    journal.record(&project_path, &result, operation.map(|o| o.on_board(&board)));
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_epic_progress() {
+        let content = "# TASKS\n\n## Backlog\n- [ ] Loose\n### Auth\n- [ ] Signup\n\n## In Progress\n### Billing\n- [ ] Invoices\n### Auth\n- [ ] Login\n\n## Done\n### Auth\n- [x] Sessions\n";
+        let tasks = tasks::parse_tasks_md(content).unwrap();
+
+        let epics = epic_progress(&tasks);
+        assert_eq!(epics.len(), 2);
+        assert_eq!(epics[0].epic, "Auth");
+        assert_eq!((epics[0].total, epics[0].completed), (3, 1));
+        assert_eq!(epics[0].columns.get("in_progress"), Some(&1));
+        assert_eq!(epics[1].epic, "Billing");
+
+        assert_eq!(normalize_epic(Some("### Auth ".to_string())).unwrap().as_deref(), Some("Auth"));
+        assert_eq!(normalize_epic(Some("  ".to_string())).unwrap(), None);
+    }
+}
@@ -11,7 +11,7 @@ pub struct FileEntry {
     pub children: Option<Vec<FileEntry>>,
 }
 
-const HIDDEN_DIRS: &[&str] = &[
+pub(crate) const HIDDEN_DIRS: &[&str] = &[
     "node_modules",
     ".git",
     "__pycache__",
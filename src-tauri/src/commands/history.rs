@@ -0,0 +1,465 @@
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use super::boards;
+use super::merge;
+use super::tasks::{self, TasksMdFilter, TasksMdTask};
+
+/// Serializes history updates from commands and watcher threads
+static HISTORY_LOCK: Mutex<()> = Mutex::new(());
+
+pub(crate) const DAY_MS: u64 = 24 * 60 * 60 * 1000;
+
+/// A task entering a column. `from` is `None` when the task first appeared and `to` is `None`
+/// when it was removed from TASKS.md.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TaskTransition {
+    /// Stable id of the task if it has one, otherwise its subject
+    pub task: String,
+    pub subject: String,
+    pub from: Option<String>,
+    pub to: Option<String>,
+    /// Milliseconds since the Unix epoch
+    pub timestamp: u64,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct TaskHistory {
+    /// Last known column of every task, to detect transitions in edits made outside the app
+    columns: HashMap<String, String>,
+    transitions: Vec<TaskTransition>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TaskDuration {
+    pub task: String,
+    pub subject: String,
+    #[serde(rename = "startedAt")]
+    pub started_at: u64,
+    #[serde(rename = "finishedAt")]
+    pub finished_at: u64,
+    #[serde(rename = "durationMs")]
+    pub duration_ms: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WeeklyThroughput {
+    /// Monday 00:00 UTC of the week, in milliseconds since the Unix epoch
+    #[serde(rename = "weekStart")]
+    pub week_start: u64,
+    pub completed: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ColumnTime {
+    pub column: String,
+    #[serde(rename = "totalMs")]
+    pub total_ms: u64,
+    #[serde(rename = "averageMs")]
+    pub average_ms: u64,
+    /// Number of separate stays in the column
+    pub visits: usize,
+}
+
+pub(crate) fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+fn task_key(task: &TasksMdTask) -> String {
+    task.stable_id.clone().unwrap_or_else(|| task.subject.clone())
+}
+
+fn history_path(project_path: &str) -> PathBuf {
+    crate::state::config_dir()
+        .join("history")
+        .join(format!("{}.json", merge::content_hash(project_path)))
+}
+
+fn load_history(project_path: &str) -> Result<Option<TaskHistory>, String> {
+    let path = history_path(project_path);
+
+    if !path.exists() {
+        return Ok(None);
+    }
+
+    let content = fs::read_to_string(&path)
+        .map_err(|e| format!("Failed to read task history: {}", e))?;
+
+    serde_json::from_str(&content)
+        .map(Some)
+        .map_err(|e| format!("Failed to parse task history: {}", e))
+}
+
+fn save_history(project_path: &str, history: &TaskHistory) -> Result<(), String> {
+    let path = history_path(project_path);
+
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create history directory: {}", e))?;
+    }
+
+    let content = serde_json::to_string(history)
+        .map_err(|e| format!("Failed to serialize task history: {}", e))?;
+
+    fs::write(&path, content).map_err(|e| format!("Failed to write task history: {}", e))
+}
+
+/// Diff tasks against the last known columns and append a transition for every change
+fn diff_columns(history: &mut TaskHistory, tasks: &[TasksMdTask], timestamp: u64) {
+    let mut seen = HashSet::new();
+
+    for task in tasks {
+        let key = task_key(task);
+        let previous = history.columns.get(&key).cloned();
+
+        if previous.as_deref() != Some(task.column.as_str()) {
+            history.transitions.push(TaskTransition {
+                task: key.clone(),
+                subject: task.subject.clone(),
+                from: previous,
+                to: Some(task.column.clone()),
+                timestamp,
+            });
+            history.columns.insert(key.clone(), task.column.clone());
+        }
+
+        seen.insert(key);
+    }
+
+    let removed: Vec<String> = history
+        .columns
+        .keys()
+        .filter(|k| !seen.contains(*k))
+        .cloned()
+        .collect();
+
+    for key in removed {
+        let from = history.columns.remove(&key);
+        let subject = history
+            .transitions
+            .iter()
+            .rev()
+            .find(|t| t.task == key)
+            .map(|t| t.subject.clone())
+            .unwrap_or_else(|| key.clone());
+
+        history.transitions.push(TaskTransition {
+            task: key,
+            subject,
+            from,
+            to: None,
+            timestamp,
+        });
+    }
+}
+
+/// Record column transitions between the last known state of a project's tasks and `tasks`.
+/// The first snapshot of a project only establishes the baseline. Recording the same state
+/// twice is a no-op, so our own writes seen again by the watcher are not double counted.
+pub fn record_transitions(project_path: &str, tasks: &[TasksMdTask]) -> Result<(), String> {
+    let _guard = HISTORY_LOCK.lock().map_err(|e| e.to_string())?;
+
+    let history = match load_history(project_path)? {
+        Some(mut history) => {
+            let before = history.transitions.len();
+            diff_columns(&mut history, tasks, now_ms());
+            if history.transitions.len() == before {
+                return Ok(());
+            }
+            history
+        }
+        None => TaskHistory {
+            columns: tasks.iter().map(|t| (task_key(t), t.column.clone())).collect(),
+            transitions: vec![],
+        },
+    };
+
+    save_history(project_path, &history)
+}
+
+/// When each task last entered Done, keyed by stable id or subject
+pub(crate) fn completion_times(project_path: &str) -> Result<HashMap<String, u64>, String> {
+    let history = {
+        let _guard = HISTORY_LOCK.lock().map_err(|e| e.to_string())?;
+        load_history(project_path)?.unwrap_or_default()
+    };
+
+    let mut completed = HashMap::new();
+    for transition in history.transitions {
+        if transition.to.as_deref() == Some("done") {
+            completed.insert(transition.task, transition.timestamp);
+        }
+    }
+
+    Ok(completed)
+}
+
+/// Transitions of a board grouped by task, limited to tasks matching the filter (matched
+/// against the current board file, so removed tasks only appear without a filter)
+fn transitions_by_task(
+    state: &crate::state::AppState,
+    project_path: &str,
+    board: Option<String>,
+    filter: Option<TasksMdFilter>,
+) -> Result<BTreeMap<String, Vec<TaskTransition>>, String> {
+    let board = boards::board_config(state, project_path, board.as_deref())?;
+    let history = {
+        let _guard = HISTORY_LOCK.lock().map_err(|e| e.to_string())?;
+        load_history(&board.history_key(project_path))?.unwrap_or_default()
+    };
+
+    let allowed: Option<HashSet<String>> = match filter {
+        Some(filter) => {
+            let tasks = tasks::load_board(project_path, &board)?;
+            Some(tasks.iter().filter(|t| filter.matches(t)).map(task_key).collect())
+        }
+        None => None,
+    };
+
+    let mut by_task: BTreeMap<String, Vec<TaskTransition>> = BTreeMap::new();
+    for transition in history.transitions {
+        if allowed.as_ref().map(|a| a.contains(&transition.task)).unwrap_or(true) {
+            by_task.entry(transition.task.clone()).or_default().push(transition);
+        }
+    }
+
+    Ok(by_task)
+}
+
+/// Time from the first entry into `start` (or the task's creation when `start` is `None`) to
+/// the latest entry into Done, for tasks that reached Done
+fn durations_to_done(
+    by_task: &BTreeMap<String, Vec<TaskTransition>>,
+    start: Option<&str>,
+) -> Vec<TaskDuration> {
+    let mut durations = Vec::new();
+
+    for (task, transitions) in by_task {
+        let Some(finished) = transitions.iter().rev().find(|t| t.to.as_deref() == Some("done")) else {
+            continue;
+        };
+
+        let started = transitions.iter().find(|t| {
+            t.timestamp <= finished.timestamp
+                && match start {
+                    Some(column) => t.to.as_deref() == Some(column),
+                    None => t.from.is_none(),
+                }
+        });
+
+        if let Some(started) = started {
+            durations.push(TaskDuration {
+                task: task.clone(),
+                subject: finished.subject.clone(),
+                started_at: started.timestamp,
+                finished_at: finished.timestamp,
+                duration_ms: finished.timestamp - started.timestamp,
+            });
+        }
+    }
+
+    durations.sort_by_key(|d| d.finished_at);
+    durations
+}
+
+/// Start of the Monday-based UTC week containing a timestamp (the epoch fell on a Thursday)
+fn week_start(timestamp: u64) -> u64 {
+    let days = timestamp / DAY_MS;
+    days.saturating_sub((days + 3) % 7) * DAY_MS
+}
+
+fn weekly_throughput(by_task: &BTreeMap<String, Vec<TaskTransition>>) -> Vec<WeeklyThroughput> {
+    let mut weeks: BTreeMap<u64, usize> = BTreeMap::new();
+
+    for transition in by_task.values().flatten() {
+        if transition.to.as_deref() == Some("done") {
+            *weeks.entry(week_start(transition.timestamp)).or_default() += 1;
+        }
+    }
+
+    weeks
+        .into_iter()
+        .map(|(week_start, completed)| WeeklyThroughput { week_start, completed })
+        .collect()
+}
+
+/// Total and average time spent per column; a task's current stay counts up to `now`
+fn column_times(by_task: &BTreeMap<String, Vec<TaskTransition>>, now: u64) -> Vec<ColumnTime> {
+    let mut totals: BTreeMap<String, (u64, usize)> = BTreeMap::new();
+
+    for transitions in by_task.values() {
+        for (i, transition) in transitions.iter().enumerate() {
+            let Some(column) = &transition.to else {
+                continue;
+            };
+            let left_at = transitions.get(i + 1).map(|t| t.timestamp).unwrap_or(now);
+            let entry = totals.entry(column.clone()).or_default();
+            entry.0 += left_at.saturating_sub(transition.timestamp);
+            entry.1 += 1;
+        }
+    }
+
+    totals
+        .into_iter()
+        .map(|(column, (total_ms, visits))| ColumnTime {
+            column,
+            total_ms,
+            average_ms: total_ms / visits as u64,
+            visits,
+        })
+        .collect()
+}
+
+/// Get the recorded column transitions of a project's tasks, oldest first
+#[tauri::command]
+pub fn get_task_history(
+    project_path: String,
+    board: Option<String>,
+    filter: Option<TasksMdFilter>,
+    state: tauri::State<'_, crate::state::AppState>,
+) -> Result<Vec<TaskTransition>, String> {
+    let mut transitions: Vec<TaskTransition> = transitions_by_task(&state, &project_path, board, filter)?
+        .into_values()
+        .flatten()
+        .collect();
+    transitions.sort_by_key(|t| t.timestamp);
+    Ok(transitions)
+}
+
+/// Cycle time: from first entering In Progress to reaching Done
+#[tauri::command]
+pub fn get_task_cycle_times(
+    project_path: String,
+    board: Option<String>,
+    filter: Option<TasksMdFilter>,
+    state: tauri::State<'_, crate::state::AppState>,
+) -> Result<Vec<TaskDuration>, String> {
+    let by_task = transitions_by_task(&state, &project_path, board, filter)?;
+    Ok(durations_to_done(&by_task, Some("in_progress")))
+}
+
+/// Lead time: from the task appearing on the board to reaching Done
+#[tauri::command]
+pub fn get_task_lead_times(
+    project_path: String,
+    board: Option<String>,
+    filter: Option<TasksMdFilter>,
+    state: tauri::State<'_, crate::state::AppState>,
+) -> Result<Vec<TaskDuration>, String> {
+    let by_task = transitions_by_task(&state, &project_path, board, filter)?;
+    Ok(durations_to_done(&by_task, None))
+}
+
+/// Number of tasks reaching Done per week
+#[tauri::command]
+pub fn get_task_throughput(
+    project_path: String,
+    board: Option<String>,
+    filter: Option<TasksMdFilter>,
+    state: tauri::State<'_, crate::state::AppState>,
+) -> Result<Vec<WeeklyThroughput>, String> {
+    let by_task = transitions_by_task(&state, &project_path, board, filter)?;
+    Ok(weekly_throughput(&by_task))
+}
+
+/// Time tasks spend in each column
+#[tauri::command]
+pub fn get_task_column_times(
+    project_path: String,
+    board: Option<String>,
+    filter: Option<TasksMdFilter>,
+    state: tauri::State<'_, crate::state::AppState>,
+) -> Result<Vec<ColumnTime>, String> {
+    let by_task = transitions_by_task(&state, &project_path, board, filter)?;
+    Ok(column_times(&by_task, now_ms()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn transition(task: &str, from: Option<&str>, to: Option<&str>, timestamp: u64) -> TaskTransition {
+        TaskTransition {
+            task: task.to_string(),
+            subject: task.to_string(),
+            from: from.map(String::from),
+            to: to.map(String::from),
+            timestamp,
+        }
+    }
+
+    #[test]
+    fn test_diff_columns() {
+        let mut history = TaskHistory::default();
+        history.columns.insert("Moved".to_string(), "backlog".to_string());
+        history.columns.insert("Gone".to_string(), "review".to_string());
+
+        let tasks = vec![
+            TasksMdTask::new("Moved", None, "in_progress"),
+            TasksMdTask::new("Added", None, "backlog"),
+        ];
+        diff_columns(&mut history, &tasks, 10);
+
+        assert_eq!(history.transitions.len(), 3);
+        assert_eq!(history.transitions[0].from.as_deref(), Some("backlog"));
+        assert_eq!(history.transitions[0].to.as_deref(), Some("in_progress"));
+        assert_eq!(history.transitions[1].from, None);
+        assert_eq!(history.transitions[2].task, "Gone");
+        assert_eq!(history.transitions[2].to, None);
+
+        // Same state again records nothing
+        diff_columns(&mut history, &tasks, 20);
+        assert_eq!(history.transitions.len(), 3);
+    }
+
+    #[test]
+    fn test_metrics() {
+        let mut by_task = BTreeMap::new();
+        by_task.insert(
+            "A".to_string(),
+            vec![
+                transition("A", None, Some("backlog"), 0),
+                transition("A", Some("backlog"), Some("in_progress"), 1_000),
+                transition("A", Some("in_progress"), Some("review"), 3_000),
+                transition("A", Some("review"), Some("done"), 7_000),
+            ],
+        );
+        by_task.insert(
+            "B".to_string(),
+            vec![
+                transition("B", None, Some("review"), 5_000),
+            ],
+        );
+
+        let cycle = durations_to_done(&by_task, Some("in_progress"));
+        assert_eq!(cycle.len(), 1);
+        assert_eq!(cycle[0].duration_ms, 6_000);
+
+        let lead = durations_to_done(&by_task, None);
+        assert_eq!(lead[0].duration_ms, 7_000);
+
+        let times = column_times(&by_task, 10_000);
+        let review = times.iter().find(|c| c.column == "review").unwrap();
+        assert_eq!(review.total_ms, 4_000 + 5_000);
+        assert_eq!(review.visits, 2);
+        assert_eq!(review.average_ms, 4_500);
+
+        let throughput = weekly_throughput(&by_task);
+        assert_eq!(throughput.len(), 1);
+        assert_eq!(throughput[0].completed, 1);
+    }
+
+    #[test]
+    fn test_week_start() {
+        // 2024-01-03 (a Wednesday) belongs to the week starting Monday 2024-01-01
+        assert_eq!(week_start(1_704_283_200_000), 1_704_067_200_000);
+        assert_eq!(week_start(1_704_067_200_000), 1_704_067_200_000);
+    }
+}
@@ -0,0 +1,947 @@
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::sync::LazyLock;
+use uuid::Uuid;
+
+use super::boards::{self, BoardConfig};
+use super::comments::TaskComment;
+use super::dependencies::{self, slugify};
+use super::merge;
+use super::templates;
+use super::tasks::{self, TasksMdSubtask, TasksMdTask, TasksMdWriteResult};
+
+const CSV_HEADER: &[&str] = &[
+    "subject",
+    "description",
+    "column",
+    "epic",
+    "completed",
+    "tags",
+    "priority",
+    "assignee",
+    "estimate",
+    "due",
+];
+
+/// todo.txt priorities, mapped onto `!priority` values
+const TODO_TXT_PRIORITIES: &[(char, &str)] = &[
+    ('A', "critical"),
+    ('B', "high"),
+    ('C', "medium"),
+    ('D', "low"),
+];
+
+/// A `blocked-by:` reference: a stable id or slug
+static REFERENCE_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"^[\w.-]+$").unwrap());
+
+/// Task shape of the structured JSON format
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExportedTask {
+    pub subject: String,
+    #[serde(rename = "stableId", default, skip_serializing_if = "Option::is_none")]
+    pub stable_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(default = "default_column")]
+    pub column: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub epic: Option<String>,
+    #[serde(default)]
+    pub completed: bool,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub priority: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub assignee: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub estimate: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub due: Option<String>,
+    #[serde(rename = "blockedBy", default)]
+    pub blocked_by: Vec<String>,
+    #[serde(default)]
+    pub subtasks: Vec<ExportedSubtask>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub comments: Vec<TaskComment>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExportedSubtask {
+    pub subject: String,
+    #[serde(default)]
+    pub completed: bool,
+    #[serde(default)]
+    pub subtasks: Vec<ExportedSubtask>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct ExportDocument {
+    version: u32,
+    tasks: Vec<ExportedTask>,
+}
+
+fn default_column() -> String {
+    "backlog".to_string()
+}
+
+/// An imported task that was left out because the board can't hold it as given
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InvalidImport {
+    pub subject: String,
+    pub reason: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImportPreview {
+    /// Tasks that were (or, in a dry run, would be) added
+    pub tasks: Vec<TasksMdTask>,
+    /// Subjects skipped because a task with the same subject already exists
+    pub duplicates: Vec<String>,
+    /// Tasks skipped because of a column or metadata value the board can't store
+    pub invalid: Vec<InvalidImport>,
+    /// Set when the import was written
+    pub result: Option<TasksMdWriteResult>,
+}
+
+fn export_subtasks(subtasks: &[TasksMdSubtask]) -> Vec<ExportedSubtask> {
+    subtasks
+        .iter()
+        .map(|s| ExportedSubtask {
+            subject: s.subject.clone(),
+            completed: s.completed,
+            subtasks: export_subtasks(&s.subtasks),
+        })
+        .collect()
+}
+
+fn import_subtasks(subtasks: &[ExportedSubtask], parent_id: &str) -> Vec<TasksMdSubtask> {
+    subtasks
+        .iter()
+        .map(|s| {
+            let id = Uuid::new_v4().to_string();
+            TasksMdSubtask {
+                subtasks: import_subtasks(&s.subtasks, &id),
+                position: None,
+                id,
+                parent_id: parent_id.to_string(),
+                subject: s.subject.clone(),
+                completed: s.completed,
+                line_number: 0,
+            }
+        })
+        .collect()
+}
+
+impl From<&TasksMdTask> for ExportedTask {
+    fn from(task: &TasksMdTask) -> Self {
+        Self {
+            subject: task.subject.clone(),
+            stable_id: task.stable_id.clone(),
+            description: task.description.clone(),
+            column: task.column.clone(),
+            epic: task.epic.clone(),
+            completed: task.completed,
+            tags: task.tags.clone(),
+            priority: task.priority.clone(),
+            assignee: task.assignee.clone(),
+            estimate: task.estimate.clone(),
+            due: task.due.clone(),
+            blocked_by: task.blocked_by.clone(),
+            subtasks: export_subtasks(&task.subtasks),
+            comments: task.comments.clone(),
+        }
+    }
+}
+
+impl From<ExportedTask> for TasksMdTask {
+    fn from(exported: ExportedTask) -> Self {
+        let mut task = TasksMdTask::new(&exported.subject, exported.description, &exported.column);
+        task.completed = exported.completed || exported.column == "done";
+        task.epic = exported.epic;
+        task.tags.extend(exported.tags);
+        task.priority = exported.priority.or(task.priority);
+        task.assignee = exported.assignee.or(task.assignee);
+        task.estimate = exported.estimate.or(task.estimate);
+        task.due = exported.due.or(task.due);
+        task.stable_id = exported.stable_id.or(task.stable_id);
+        task.blocked_by.extend(exported.blocked_by);
+        task.subtasks = import_subtasks(&exported.subtasks, &task.id);
+        task.comments = exported.comments;
+        task
+    }
+}
+
+/// Quote a CSV field when it contains a separator, quote or line break
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// Parse CSV into rows of fields; quoted fields may contain commas, quotes and line breaks
+fn parse_csv(data: &str) -> Vec<Vec<String>> {
+    let mut rows = Vec::new();
+    let mut row = Vec::new();
+    let mut field = String::new();
+    let mut in_quotes = false;
+    let mut chars = data.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match (c, in_quotes) {
+            ('"', true) if chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            ('"', true) => in_quotes = false,
+            ('"', false) if field.is_empty() => in_quotes = true,
+            (',', false) => row.push(std::mem::take(&mut field)),
+            ('\r', false) => {}
+            ('\n', false) => {
+                row.push(std::mem::take(&mut field));
+                rows.push(std::mem::take(&mut row));
+            }
+            _ => field.push(c),
+        }
+    }
+
+    if !field.is_empty() || !row.is_empty() {
+        row.push(field);
+        rows.push(row);
+    }
+
+    rows.retain(|r| r.iter().any(|f| !f.trim().is_empty()));
+    rows
+}
+
+fn export_csv(tasks: &[TasksMdTask]) -> String {
+    let mut out = CSV_HEADER.join(",");
+    out.push('\n');
+
+    for task in tasks {
+        let fields = [
+            task.subject.clone(),
+            task.description.clone().unwrap_or_default(),
+            task.column.clone(),
+            task.epic.clone().unwrap_or_default(),
+            task.completed.to_string(),
+            task.tags.join(";"),
+            task.priority.clone().unwrap_or_default(),
+            task.assignee.clone().unwrap_or_default(),
+            task.estimate.clone().unwrap_or_default(),
+            task.due.clone().unwrap_or_default(),
+        ];
+        let fields: Vec<String> = fields.iter().map(|f| csv_field(f)).collect();
+        out.push_str(&fields.join(","));
+        out.push('\n');
+    }
+
+    out
+}
+
+fn import_csv(data: &str) -> Result<Vec<TasksMdTask>, String> {
+    let mut rows = parse_csv(data).into_iter();
+    let header: Vec<String> = rows
+        .next()
+        .ok_or("CSV is empty")?
+        .iter()
+        .map(|h| h.trim().to_lowercase())
+        .collect();
+
+    let subject_col = header
+        .iter()
+        .position(|h| h == "subject" || h == "title")
+        .ok_or("CSV needs a 'subject' column")?;
+    let col = |name: &str| header.iter().position(|h| h == name);
+    let (description_col, column_col, epic_col, completed_col) =
+        (col("description"), col("column"), col("epic"), col("completed"));
+    let (tags_col, priority_col, assignee_col, estimate_col, due_col) = (
+        col("tags"),
+        col("priority"),
+        col("assignee"),
+        col("estimate"),
+        col("due"),
+    );
+
+    let mut tasks = Vec::new();
+
+    for row in rows {
+        let get = |i: Option<usize>| {
+            i.and_then(|i| row.get(i))
+                .map(|v| v.trim().to_string())
+                .filter(|v| !v.is_empty())
+        };
+
+        let Some(subject) = get(Some(subject_col)) else {
+            continue;
+        };
+
+        let column = get(column_col).unwrap_or_else(default_column);
+        let completed = get(completed_col)
+            .map(|c| matches!(c.to_lowercase().as_str(), "true" | "yes" | "x" | "1"))
+            .unwrap_or(false);
+
+        tasks.push(TasksMdTask::from(ExportedTask {
+            stable_id: None,
+            subject,
+            description: get(description_col),
+            column,
+            epic: get(epic_col),
+            completed,
+            tags: get(tags_col)
+                .map(|t| {
+                    t.split([';', ' '])
+                        .filter(|t| !t.is_empty())
+                        .map(String::from)
+                        .collect()
+                })
+                .unwrap_or_default(),
+            priority: get(priority_col).map(|p| p.to_lowercase()),
+            assignee: get(assignee_col),
+            estimate: get(estimate_col),
+            due: get(due_col),
+            blocked_by: vec![],
+            subtasks: vec![],
+            comments: vec![],
+        }));
+    }
+
+    Ok(tasks)
+}
+
+fn export_json(tasks: &[TasksMdTask]) -> Result<String, String> {
+    let document = ExportDocument {
+        version: 1,
+        tasks: tasks.iter().map(ExportedTask::from).collect(),
+    };
+
+    serde_json::to_string_pretty(&document).map_err(|e| format!("Failed to serialize tasks: {}", e))
+}
+
+fn import_json(data: &str) -> Result<Vec<TasksMdTask>, String> {
+    // Accept the full document or a bare array of tasks
+    let exported: Vec<ExportedTask> = match serde_json::from_str::<ExportDocument>(data) {
+        Ok(document) => document.tasks,
+        Err(_) => serde_json::from_str(data).map_err(|e| format!("Failed to parse JSON: {}", e))?,
+    };
+
+    Ok(exported.into_iter().map(TasksMdTask::from).collect())
+}
+
+/// One todo.txt line per task: `x (A) Subject +tag @assignee column:review estimate:3h due:2024-06-01`.
+/// Descriptions and subtasks have no todo.txt equivalent and are left out.
+fn export_todo_txt(tasks: &[TasksMdTask]) -> String {
+    let mut out = String::new();
+
+    for task in tasks {
+        let mut parts: Vec<String> = Vec::new();
+
+        if task.completed {
+            parts.push("x".to_string());
+        }
+        if let Some(priority) = &task.priority {
+            if let Some((letter, _)) = TODO_TXT_PRIORITIES.iter().find(|(_, p)| p == priority) {
+                parts.push(format!("({})", letter));
+            }
+        }
+        parts.push(task.subject.clone());
+        parts.extend(task.tags.iter().map(|t| format!("+{}", t)));
+        if let Some(assignee) = &task.assignee {
+            parts.push(format!("@{}", assignee));
+        }
+        parts.push(format!("column:{}", task.column));
+        if let Some(estimate) = &task.estimate {
+            parts.push(format!("estimate:{}", estimate));
+        }
+        if let Some(due) = &task.due {
+            parts.push(format!("due:{}", due));
+        }
+
+        out.push_str(&parts.join(" "));
+        out.push('\n');
+    }
+
+    out
+}
+
+fn import_todo_txt(data: &str) -> Vec<TasksMdTask> {
+    let mut tasks = Vec::new();
+
+    for line in data.lines().map(str::trim).filter(|l| !l.is_empty()) {
+        let mut words: Vec<&str> = line.split_whitespace().collect();
+
+        let completed = words.first() == Some(&"x");
+        if completed {
+            words.remove(0);
+        }
+
+        let mut priority = None;
+        if let Some(word) = words.first() {
+            let letters: Vec<char> = word.chars().collect();
+            if letters.len() == 3 && letters[0] == '(' && letters[2] == ')' {
+                priority = TODO_TXT_PRIORITIES
+                    .iter()
+                    .find(|(letter, _)| *letter == letters[1])
+                    .map(|(_, p)| p.to_string());
+                words.remove(0);
+            }
+        }
+
+        // Completion and creation dates carry no meaning on the board
+        while words.first().map(|w| is_todo_txt_date(w)).unwrap_or(false) {
+            words.remove(0);
+        }
+
+        let mut subject_words = Vec::new();
+        let mut tags = Vec::new();
+        let mut assignee = None;
+        let mut column = None;
+        let mut estimate = None;
+        let mut due = None;
+
+        for word in words {
+            if let Some(tag) = word.strip_prefix('+').filter(|t| !t.is_empty()) {
+                tags.push(tag.to_string());
+            } else if let Some(context) = word.strip_prefix('@').filter(|c| !c.is_empty()) {
+                assignee = Some(context.to_string());
+            } else if let Some(value) = word.strip_prefix("column:") {
+                column = Some(value.to_string());
+            } else if let Some(value) = word.strip_prefix("estimate:") {
+                estimate = Some(value.to_string());
+            } else if let Some(value) = word.strip_prefix("due:") {
+                due = Some(value.to_string());
+            } else {
+                subject_words.push(word);
+            }
+        }
+
+        if subject_words.is_empty() {
+            continue;
+        }
+
+        let column =
+            column.unwrap_or_else(|| if completed { "done" } else { "backlog" }.to_string());
+
+        tasks.push(TasksMdTask::from(ExportedTask {
+            stable_id: None,
+            subject: subject_words.join(" "),
+            description: None,
+            column,
+            epic: None,
+            completed,
+            tags,
+            priority,
+            assignee,
+            estimate,
+            due,
+            blocked_by: vec![],
+            subtasks: vec![],
+            comments: vec![],
+        }));
+    }
+
+    tasks
+}
+
+fn is_todo_txt_date(word: &str) -> bool {
+    let bytes = word.as_bytes();
+    bytes.len() == 10
+        && bytes[4] == b'-'
+        && bytes[7] == b'-'
+        && bytes
+            .iter()
+            .enumerate()
+            .all(|(i, b)| i == 4 || i == 7 || b.is_ascii_digit())
+}
+
+/// Label used for a board column on GitHub issues
+fn column_label(column: &str) -> String {
+    format!("status: {}", column.replace('_', " "))
+}
+
+/// Issues in the shape of the GitHub REST API, with tags, priority and column as labels
+fn export_github(tasks: &[TasksMdTask]) -> Result<String, String> {
+    let issues: Vec<Value> = tasks
+        .iter()
+        .map(|task| {
+            let mut labels: Vec<Value> = task
+                .tags
+                .iter()
+                .map(|t| serde_json::json!({ "name": t }))
+                .collect();
+            if let Some(priority) = &task.priority {
+                labels.push(serde_json::json!({ "name": format!("priority: {}", priority) }));
+            }
+            if task.column != "backlog" && task.column != "done" {
+                labels.push(serde_json::json!({ "name": column_label(&task.column) }));
+            }
+
+            let assignees: Vec<Value> = task
+                .assignee
+                .iter()
+                .map(|a| serde_json::json!({ "login": a }))
+                .collect();
+
+            serde_json::json!({
+                "title": task.subject,
+                "body": task.description,
+                "state": if task.column == "done" { "closed" } else { "open" },
+                "labels": labels,
+                "assignees": assignees,
+            })
+        })
+        .collect();
+
+    serde_json::to_string_pretty(&issues).map_err(|e| format!("Failed to serialize issues: {}", e))
+}
+
+fn import_github(data: &str, board: &BoardConfig) -> Result<Vec<TasksMdTask>, String> {
+    let issues: Vec<Value> =
+        serde_json::from_str(data).map_err(|e| format!("Failed to parse GitHub issues: {}", e))?;
+
+    let mut tasks = Vec::new();
+
+    for issue in issues {
+        // The issues API also lists pull requests
+        if issue.get("pull_request").is_some() {
+            continue;
+        }
+
+        let Some(title) = issue
+            .get("title")
+            .and_then(Value::as_str)
+            .filter(|t| !t.trim().is_empty())
+        else {
+            continue;
+        };
+
+        let closed = issue.get("state").and_then(Value::as_str) == Some("closed");
+        let mut column = if closed { "done" } else { "backlog" }.to_string();
+        let mut tags = Vec::new();
+        let mut priority = None;
+
+        let labels = issue
+            .get("labels")
+            .and_then(Value::as_array)
+            .cloned()
+            .unwrap_or_default();
+        for label in labels {
+            // Labels are objects from the API but plain strings from `gh issue list --json`
+            let Some(name) = label.get("name").and_then(Value::as_str).or(label.as_str()) else {
+                continue;
+            };
+
+            if let Some(p) = name.strip_prefix("priority:") {
+                priority = Some(p.trim().to_lowercase());
+            } else if let Some(status) = name.strip_prefix("status:") {
+                let id = status.trim().replace(' ', "_");
+                let status = if board.column_rank(&id) < board.column_rank("") {
+                    Some(id)
+                } else {
+                    board.heading_to_column(status)
+                };
+                if let Some(status) = status.filter(|_| !closed) {
+                    column = status;
+                }
+            } else {
+                tags.push(name.replace(' ', "-"));
+            }
+        }
+
+        let assignee = issue
+            .get("assignees")
+            .and_then(Value::as_array)
+            .and_then(|a| a.first().cloned())
+            .or_else(|| issue.get("assignee").cloned())
+            .and_then(|a| a.get("login").and_then(Value::as_str).map(String::from));
+
+        // The milestone's due date, from `due_on` (API) or `dueOn` (gh)
+        let due = issue
+            .get("milestone")
+            .and_then(|m| m.get("due_on").or_else(|| m.get("dueOn")))
+            .and_then(Value::as_str)
+            .and_then(|d| d.get(..10))
+            .map(String::from);
+
+        tasks.push(TasksMdTask::from(ExportedTask {
+            stable_id: None,
+            subject: title.trim().to_string(),
+            description: issue
+                .get("body")
+                .and_then(Value::as_str)
+                .map(|b| b.trim().to_string())
+                .filter(|b| !b.is_empty()),
+            column,
+            epic: None,
+            completed: closed,
+            tags,
+            priority,
+            assignee,
+            estimate: None,
+            due,
+            blocked_by: vec![],
+            subtasks: vec![],
+            comments: vec![],
+        }));
+    }
+
+    Ok(tasks)
+}
+
+fn parse_import(format: &str, data: &str, board: &BoardConfig) -> Result<Vec<TasksMdTask>, String> {
+    match format {
+        "csv" => import_csv(data),
+        "json" => import_json(data),
+        "todotxt" => Ok(import_todo_txt(data)),
+        "github" => import_github(data, board),
+        _ => Err(format!("Unknown import format '{}'", format)),
+    }
+}
+
+/// Normalize an imported task against the board: its column may also be given as a column
+/// heading, and metadata must be written so that the next parse reads it back as metadata.
+/// Tasks without a column default to Backlog, or to the first column of boards without one.
+fn normalize_import(task: &mut TasksMdTask, board: &BoardConfig) -> Result<(), String> {
+    if task.subject.contains(['\n', '\r']) {
+        return Err("Subject spans several lines".to_string());
+    }
+
+    let columns = board.columns();
+    let column = if columns.iter().any(|c| c.id == task.column) {
+        task.column.clone()
+    } else if let Some(column) = board.heading_to_column(&task.column) {
+        column
+    } else if task.column == default_column() && !columns.is_empty() {
+        columns[0].id.clone()
+    } else {
+        return Err(format!("Unknown column '{}'", task.column));
+    };
+    task.completed = task.completed || column == "done";
+    task.column = column;
+
+    if let Some(priority) = &task.priority {
+        let normalized = priority.trim().trim_start_matches('!').to_lowercase();
+        if !tasks::PRIORITIES.contains(&normalized.as_str()) {
+            return Err(format!("Unknown priority '{}'", priority));
+        }
+        task.priority = Some(normalized);
+    }
+
+    for tag in &mut task.tags {
+        let normalized = tag.trim().trim_start_matches('#').to_string();
+        if !tasks::TAG_RE.is_match(&format!("#{}", normalized)) {
+            return Err(format!("Invalid tag '{}'", tag));
+        }
+        *tag = normalized;
+    }
+
+    if let Some(assignee) = &task.assignee {
+        let normalized = assignee.trim().trim_start_matches('@').to_string();
+        if !tasks::ASSIGNEE_RE.is_match(&format!("@{}", normalized)) {
+            return Err(format!("Invalid assignee '{}'", assignee));
+        }
+        task.assignee = Some(normalized);
+    }
+
+    if let Some(estimate) = &task.estimate {
+        let normalized = estimate.trim().trim_start_matches('~').to_lowercase();
+        if !tasks::ESTIMATE_RE.is_match(&format!("~{}", normalized)) {
+            return Err(format!("Invalid estimate '{}', expected e.g. 30m, 2h, 1.5d or 1w", estimate));
+        }
+        task.estimate = Some(normalized);
+    }
+
+    if let Some(due) = &task.due {
+        if templates::parse_date(due).is_none() {
+            return Err(format!("Invalid due date '{}', expected YYYY-MM-DD", due));
+        }
+    }
+
+    if let Some(stable_id) = task.stable_id.as_ref().filter(|id| !REFERENCE_RE.is_match(id)) {
+        return Err(format!("Invalid id '{}'", stable_id));
+    }
+
+    if let Some(reference) = task.blocked_by.iter().find(|r| !REFERENCE_RE.is_match(r)) {
+        return Err(format!("Invalid blocked-by reference '{}'", reference));
+    }
+
+    Ok(())
+}
+
+/// Split imported tasks into new ones and duplicates of existing or earlier imported tasks,
+/// matched by subject or stable id
+fn dedupe(existing: &[TasksMdTask], imported: Vec<TasksMdTask>) -> (Vec<TasksMdTask>, Vec<String>) {
+    let mut seen: Vec<String> = existing.iter().map(|t| slugify(&t.subject)).collect();
+    let mut seen_ids: Vec<String> = existing.iter().filter_map(|t| t.stable_id.clone()).collect();
+    let mut fresh = Vec::new();
+    let mut duplicates = Vec::new();
+
+    for task in imported {
+        let slug = slugify(&task.subject);
+        if seen.contains(&slug) || task.stable_id.as_ref().is_some_and(|id| seen_ids.contains(id)) {
+            duplicates.push(task.subject);
+        } else {
+            seen.push(slug);
+            seen_ids.extend(task.stable_id.clone());
+            fresh.push(task);
+        }
+    }
+
+    (fresh, duplicates)
+}
+
+/// Export one of a project's boards as "csv", "json", "todotxt" or "github" (issues JSON)
+#[tauri::command]
+pub fn export_tasks(
+    project_path: String,
+    board: Option<String>,
+    format: String,
+    state: tauri::State<'_, crate::state::AppState>,
+) -> Result<String, String> {
+    let board = boards::board_config(&state, &project_path, board.as_deref())?;
+    let tasks = tasks::load_board(&project_path, &board)?;
+
+    match format.as_str() {
+        "csv" => Ok(export_csv(&tasks)),
+        "json" => export_json(&tasks),
+        "todotxt" => Ok(export_todo_txt(&tasks)),
+        "github" => export_github(&tasks),
+        _ => Err(format!("Unknown export format '{}'", format)),
+    }
+}
+
+/// Import tasks into one of a project's boards, skipping subjects that already exist.
+/// With `dry_run` nothing is written and the preview shows what would be added.
+#[tauri::command]
+pub fn import_tasks(
+    project_path: String,
+    board: Option<String>,
+    format: String,
+    data: String,
+    dry_run: Option<bool>,
+    state: tauri::State<'_, crate::state::AppState>,
+    versions: tauri::State<'_, merge::TasksMdVersions>,
+) -> Result<ImportPreview, String> {
+    let board = boards::board_config(&state, &project_path, board.as_deref())?;
+    let existing = if board.path(&project_path).exists() {
+        tasks::load_board(&project_path, &board)?
+    } else {
+        vec![]
+    };
+
+    let mut invalid = Vec::new();
+    let mut imported = Vec::new();
+    for mut task in parse_import(&format, &data, &board)? {
+        match normalize_import(&mut task, &board) {
+            Ok(()) => imported.push(task),
+            Err(reason) => invalid.push(InvalidImport {
+                subject: task.subject,
+                reason,
+            }),
+        }
+    }
+    let (deduped, duplicates) = dedupe(&existing, imported);
+
+    // Imported `blocked-by:` references must not close a dependency cycle
+    let mut board_tasks = existing;
+    let mut fresh = Vec::new();
+    for task in deduped {
+        board_tasks.push(task.clone());
+        match dependencies::ensure_acyclic(&board_tasks, &task.subject) {
+            Ok(()) => fresh.push(task),
+            Err(reason) => {
+                board_tasks.pop();
+                invalid.push(InvalidImport {
+                    subject: task.subject,
+                    reason,
+                });
+            }
+        }
+    }
+
+    if dry_run.unwrap_or(false) || fresh.is_empty() {
+        return Ok(ImportPreview {
+            tasks: fresh,
+            duplicates,
+            invalid,
+            result: None,
+        });
+    }
+
+    let to_add = fresh.clone();
+    let result = tasks::frontend_write(|| {
+        tasks::mutate_board(&project_path, &board, None, &versions, false, |tasks| {
+            tasks.extend(to_add);
+            fresh.iter().try_for_each(|task| dependencies::ensure_acyclic(tasks, &task.subject))
+        })
+    })?;
+
+    Ok(ImportPreview {
+        tasks: fresh,
+        duplicates,
+        invalid,
+        result: Some(result),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> Vec<TasksMdTask> {
+        let mut first = TasksMdTask::new(
+            "Fix login, again #auth !high @agent ~2h",
+            Some("Users see \"500\"\nafter redirect".to_string()),
+            "in_progress",
+        );
+        first.subtasks = import_subtasks(
+            &[ExportedSubtask {
+                subject: "Repro".to_string(),
+                completed: true,
+                subtasks: vec![],
+            }],
+            &first.id,
+        );
+        vec![first, TasksMdTask::new("Ship 1.0", None, "done")]
+    }
+
+    #[test]
+    fn test_csv_round_trip() {
+        let tasks = import_csv(&export_csv(&sample())).unwrap();
+        assert_eq!(tasks.len(), 2);
+        assert_eq!(tasks[0].subject, "Fix login, again");
+        assert_eq!(
+            tasks[0].description.as_deref(),
+            Some("Users see \"500\"\nafter redirect")
+        );
+        assert_eq!(tasks[0].tags, vec!["auth"]);
+        assert_eq!(tasks[0].priority.as_deref(), Some("high"));
+        assert_eq!(tasks[0].column, "in_progress");
+        assert!(tasks[1].completed);
+    }
+
+    #[test]
+    fn test_json_round_trip() {
+        let tasks = import_json(&export_json(&sample()).unwrap()).unwrap();
+        assert_eq!(tasks[0].estimate.as_deref(), Some("2h"));
+        assert_eq!(tasks[0].subtasks.len(), 1);
+        assert_eq!(tasks[0].subtasks[0].parent_id, tasks[0].id);
+
+        let mut commented = TasksMdTask::new("Audit deps id:audit", None, "backlog");
+        commented.comments = vec![TaskComment {
+            author: "alice".to_string(),
+            author_kind: "human".to_string(),
+            timestamp: 1_700_000_000_000,
+            body: "Start with the lockfile".to_string(),
+            position: None,
+        }];
+        let tasks = import_json(&export_json(&[commented.clone()]).unwrap()).unwrap();
+        assert_eq!(tasks[0].stable_id.as_deref(), Some("audit"));
+        assert_eq!(tasks[0].comments, commented.comments);
+    }
+
+    #[test]
+    fn test_todo_txt() {
+        let exported = export_todo_txt(&sample());
+        assert!(exported
+            .starts_with("(B) Fix login, again +auth @agent column:in_progress estimate:2h\n"));
+
+        let tasks =
+            import_todo_txt("x 2024-05-01 2024-04-28 Pay invoice +finance\n(A) Call bank @alice\n");
+        assert_eq!(tasks[0].subject, "Pay invoice");
+        assert_eq!(tasks[0].column, "done");
+        assert_eq!(tasks[0].tags, vec!["finance"]);
+        assert_eq!(tasks[1].priority.as_deref(), Some("critical"));
+        assert_eq!(tasks[1].assignee.as_deref(), Some("alice"));
+    }
+
+    #[test]
+    fn test_github_issues() {
+        let data = r#"[
+            {"title": "Crash on start", "body": "Stack trace", "state": "open",
+             "labels": [{"name": "bug"}, {"name": "priority: high"}, {"name": "status: review"}],
+             "assignees": [{"login": "octocat"}]},
+            {"title": "Old issue", "state": "closed", "labels": ["docs"]},
+            {"title": "A PR", "state": "open", "pull_request": {}}
+        ]"#;
+        let tasks = import_github(data, &BoardConfig::default()).unwrap();
+        assert_eq!(tasks.len(), 2);
+        assert_eq!(tasks[0].column, "review");
+        assert_eq!(tasks[0].tags, vec!["bug"]);
+        assert_eq!(tasks[0].priority.as_deref(), Some("high"));
+        assert_eq!(tasks[0].assignee.as_deref(), Some("octocat"));
+        assert_eq!(tasks[1].column, "done");
+
+        let round_trip = import_github(&export_github(&sample()).unwrap(), &BoardConfig::default()).unwrap();
+        assert_eq!(round_trip[0].column, "in_progress");
+        assert_eq!(round_trip[0].assignee.as_deref(), Some("agent"));
+
+        // Status labels are read against the target board's columns and headings
+        let bugs = BoardConfig {
+            file: "BUGS.md".to_string(),
+            layout: "tasks_md".to_string(),
+            name: None,
+            columns: vec![
+                boards::ColumnConfig {
+                    id: "triage".to_string(),
+                    heading: "Triage".to_string(),
+                },
+                boards::ColumnConfig {
+                    id: "done".to_string(),
+                    heading: "Fixed".to_string(),
+                },
+            ],
+        };
+        let data = r#"[
+            {"title": "Flaky test", "state": "open", "labels": ["status: triage"]},
+            {"title": "Crash on start", "state": "open", "labels": ["status: review"]}
+        ]"#;
+        let tasks = import_github(data, &bugs).unwrap();
+        assert_eq!(tasks[0].column, "triage");
+        assert_eq!(tasks[1].column, "backlog");
+    }
+
+    #[test]
+    fn test_dedupe() {
+        let existing = vec![TasksMdTask::new("Fix login", None, "backlog")];
+        let imported = vec![
+            TasksMdTask::new("fix LOGIN", None, "backlog"),
+            TasksMdTask::new("New task", None, "backlog"),
+            TasksMdTask::new("New task", None, "review"),
+        ];
+        let (fresh, duplicates) = dedupe(&existing, imported);
+        assert_eq!(fresh.len(), 1);
+        assert_eq!(duplicates, vec!["fix LOGIN", "New task"]);
+    }
+
+    #[test]
+    fn test_normalize_import() {
+        let board = BoardConfig::default();
+        let data = "subject,column,priority,tags,estimate\n\
+                    Fix login,In Progress,!HIGH,#auth,2H\n\
+                    Ship it,In Review,,,\n\
+                    Triage,,urgent,,\n\
+                    Tag it,,,v1.0,\n\
+                    Size it,,,,3 hours\n";
+
+        let results: Vec<Result<TasksMdTask, String>> = import_csv(data)
+            .unwrap()
+            .into_iter()
+            .map(|mut task| normalize_import(&mut task, &board).map(|_| task))
+            .collect();
+
+        let task = results[0].as_ref().unwrap();
+        assert_eq!(task.column, "in_progress");
+        assert_eq!(task.priority.as_deref(), Some("high"));
+        assert_eq!(task.tags, vec!["auth"]);
+        assert_eq!(task.estimate.as_deref(), Some("2h"));
+
+        assert_eq!(results[1].as_ref().unwrap_err(), "Unknown column 'In Review'");
+        assert_eq!(results[2].as_ref().unwrap_err(), "Unknown priority 'urgent'");
+        assert_eq!(results[3].as_ref().unwrap_err(), "Invalid tag 'v1.0'");
+        assert!(results[4].as_ref().unwrap_err().starts_with("Invalid estimate '3 hours'"));
+    }
+}
@@ -0,0 +1,307 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use super::board_sources;
+use super::boards::{self, BoardConfig};
+use super::history;
+use super::merge::{self, fingerprint, task_key};
+use super::tasks::{self, TasksMdTask, TasksMdWriteResult};
+
+/// How many operations per project can be undone
+const MAX_OPERATIONS: usize = 100;
+
+/// One board mutation as the task before and after it. Adding a task has no `before`,
+/// deleting one has no `after`, so the inverse is always the same operation reversed.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TaskOperation {
+    /// "move", "add", "update" or "delete"
+    pub kind: String,
+    pub subject: String,
+    pub before: Option<TasksMdTask>,
+    pub after: Option<TasksMdTask>,
+    /// Position of the task in the file, for putting a deleted task back where it was
+    pub index: usize,
+    /// Milliseconds since the Unix epoch
+    pub timestamp: u64,
+    /// Board file the operation was made on; TASKS.md when unset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub board: Option<String>,
+}
+
+impl TaskOperation {
+    pub fn new(kind: &str, index: usize, before: Option<TasksMdTask>, after: Option<TasksMdTask>) -> Self {
+        let subject = after
+            .as_ref()
+            .or(before.as_ref())
+            .map(|t| t.subject.clone())
+            .unwrap_or_default();
+
+        Self {
+            kind: kind.to_string(),
+            subject,
+            before,
+            after,
+            index,
+            timestamp: history::now_ms(),
+            board: None,
+        }
+    }
+
+    pub fn on_board(mut self, board: &BoardConfig) -> Self {
+        self.board = (!board.is_default()).then(|| board.file.clone());
+        self
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ProjectJournal {
+    pub undo: Vec<TaskOperation>,
+    pub redo: Vec<TaskOperation>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JournalResult {
+    pub operation: TaskOperation,
+    pub write: TasksMdWriteResult,
+}
+
+/// Undo and redo stacks of board mutations per project, for this session
+pub struct TaskJournal {
+    journals: Mutex<HashMap<String, ProjectJournal>>,
+}
+
+impl TaskJournal {
+    pub fn new() -> Self {
+        Self {
+            journals: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Record an operation that was written; a new operation clears the redo stack. The task
+    /// after it is taken from what was written, since merging or auto-blocking may have changed
+    /// it after the mutation, and undo only applies to a task that still looks like `after`.
+    pub fn record(&self, project_path: &str, result: &TasksMdWriteResult, operation: Option<TaskOperation>) {
+        let Some(mut operation) = operation else {
+            return;
+        };
+        if !result.conflicts.is_empty() {
+            return;
+        }
+        if let Some(after) = &mut operation.after {
+            if let Some(written) = result.tasks.iter().find(|t| task_key(t) == task_key(after)) {
+                *after = written.clone();
+            }
+        }
+
+        if let Ok(mut journals) = self.journals.lock() {
+            let journal = journals.entry(project_path.to_string()).or_default();
+            journal.undo.push(operation);
+            if journal.undo.len() > MAX_OPERATIONS {
+                journal.undo.remove(0);
+            }
+            journal.redo.clear();
+        }
+    }
+}
+
+impl Default for TaskJournal {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Replace `from` with `to` in the task list. Fails without touching anything when the task
+/// no longer looks like `from`, i.e. the file diverged since the operation.
+fn apply(tasks: &mut Vec<TasksMdTask>, from: Option<&TasksMdTask>, to: Option<&TasksMdTask>, index: usize) -> Result<(), String> {
+    match from {
+        Some(from) => {
+            let position = tasks
+                .iter()
+                .position(|t| task_key(t) == task_key(from))
+                .ok_or_else(|| format!("'{}' is no longer in TASKS.md", from.subject))?;
+            if fingerprint(&tasks[position]) != fingerprint(from) {
+                return Err(format!("'{}' has changed since", from.subject));
+            }
+
+            match to {
+                Some(to) => tasks[position] = to.clone(),
+                None => {
+                    tasks.remove(position);
+                }
+            }
+        }
+        None => {
+            let Some(to) = to else {
+                return Ok(());
+            };
+            if tasks.iter().any(|t| task_key(t) == task_key(to)) {
+                return Err(format!("'{}' already exists", to.subject));
+            }
+            tasks.insert(index.min(tasks.len()), to.clone());
+        }
+    }
+
+    Ok(())
+}
+
+/// Pop an operation from one stack, apply it (reversed for undo) and push it onto the other.
+/// An operation that no longer applies is dropped so it can't block the rest of the stack;
+/// one that failed for another reason, like an unreadable file, goes back to be retried.
+fn replay(
+    state: &crate::state::AppState,
+    project_path: &str,
+    undo: bool,
+    versions: &merge::TasksMdVersions,
+    journal: &TaskJournal,
+) -> Result<JournalResult, String> {
+    let operation = {
+        let mut journals = journal.journals.lock().map_err(|e| e.to_string())?;
+        let entry = journals.entry(project_path.to_string()).or_default();
+        let stack = if undo { &mut entry.undo } else { &mut entry.redo };
+        stack.pop().ok_or(if undo { "Nothing to undo" } else { "Nothing to redo" })?
+    };
+
+    let (from, to) = if undo {
+        (operation.after.as_ref(), operation.before.as_ref())
+    } else {
+        (operation.before.as_ref(), operation.after.as_ref())
+    };
+
+    let mut diverged = false;
+    let written = boards::find_board(state, project_path, operation.board.as_deref()).and_then(|board| {
+        if !board.is_tasks_md() {
+            return board_sources::replay_move(project_path, &board, from, to, versions, &mut diverged);
+        }
+        tasks::mutate_board(project_path, &board, None, versions, true, |tasks| {
+            apply(tasks, from, to, operation.index).inspect_err(|_| diverged = true)
+        })
+    });
+
+    let push = |undo_stack: bool| {
+        if let Ok(mut journals) = journal.journals.lock() {
+            let entry = journals.entry(project_path.to_string()).or_default();
+            let stack = if undo_stack { &mut entry.undo } else { &mut entry.redo };
+            stack.push(operation.clone());
+        }
+    };
+
+    let write = match written {
+        Ok(write) => write,
+        Err(e) => {
+            if !diverged {
+                push(undo);
+            }
+            let action = if undo { "undo" } else { "redo" };
+            return Err(format!("Cannot {} {} of '{}': {}", action, operation.kind, operation.subject, e));
+        }
+    };
+    push(!undo);
+
+    Ok(JournalResult { operation, write })
+}
+
+/// Revert the last move, add, update or delete made on a project's board
+#[tauri::command]
+pub fn undo_task_operation(
+    project_path: String,
+    state: tauri::State<'_, crate::state::AppState>,
+    versions: tauri::State<'_, merge::TasksMdVersions>,
+    journal: tauri::State<'_, TaskJournal>,
+) -> Result<JournalResult, String> {
+    tasks::frontend_write(|| replay(&state, &project_path, true, &versions, &journal))
+}
+
+/// Re-apply the last undone operation
+#[tauri::command]
+pub fn redo_task_operation(
+    project_path: String,
+    state: tauri::State<'_, crate::state::AppState>,
+    versions: tauri::State<'_, merge::TasksMdVersions>,
+    journal: tauri::State<'_, TaskJournal>,
+) -> Result<JournalResult, String> {
+    tasks::frontend_write(|| replay(&state, &project_path, false, &versions, &journal))
+}
+
+/// The undo and redo stacks of a project, most recent last
+#[tauri::command]
+pub fn get_task_journal(
+    project_path: String,
+    journal: tauri::State<'_, TaskJournal>,
+) -> Result<ProjectJournal, String> {
+    let journals = journal.journals.lock().map_err(|e| e.to_string())?;
+    Ok(journals.get(&project_path).cloned().unwrap_or_default())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONTENT: &str = r#"# TASKS
+
+## Backlog
+- [ ] First
+- [ ] Second
+"#;
+
+    #[test]
+    fn test_apply_and_reverse() {
+        let mut tasks = tasks::parse_tasks_md(CONTENT).unwrap();
+        let original = tasks.clone();
+
+        // Delete "First", then undo it
+        let deleted = tasks[0].clone();
+        apply(&mut tasks, Some(&deleted), None, 0).unwrap();
+        assert_eq!(tasks.len(), 1);
+        apply(&mut tasks, None, Some(&deleted), 0).unwrap();
+        assert_eq!(tasks[0].subject, "First");
+
+        // Move "Second", then undo it
+        let mut moved = tasks[1].clone();
+        moved.column = "done".to_string();
+        apply(&mut tasks, Some(&original[1]), Some(&moved), 1).unwrap();
+        apply(&mut tasks, Some(&moved), Some(&original[1]), 1).unwrap();
+        assert_eq!(tasks[1].column, "backlog");
+    }
+
+    #[test]
+    fn test_apply_refuses_diverged_tasks() {
+        let mut tasks = tasks::parse_tasks_md(CONTENT).unwrap();
+        let before = tasks[0].clone();
+        let mut after = before.clone();
+        after.column = "review".to_string();
+
+        // Someone else moved the task elsewhere in the meantime
+        tasks[0].column = "blocked".to_string();
+        assert!(apply(&mut tasks, Some(&after), Some(&before), 0).is_err());
+        assert_eq!(tasks[0].column, "blocked");
+
+        // Re-adding a task that exists again
+        let second = tasks[1].clone();
+        assert!(apply(&mut tasks, None, Some(&second), 0).is_err());
+    }
+
+    #[test]
+    fn test_record_keeps_the_written_task() {
+        let tasks = tasks::parse_tasks_md(CONTENT).unwrap();
+        let mut after = tasks[0].clone();
+        after.column = "review".to_string();
+
+        // Auto-blocking moved the task on after the mutation
+        let mut written = after.clone();
+        written.column = "blocked".to_string();
+        let result = TasksMdWriteResult {
+            version: String::new(),
+            merged: false,
+            conflicts: vec![],
+            tasks: vec![written, tasks[1].clone()],
+        };
+
+        let journal = TaskJournal::new();
+        journal.record("/project", &result, Some(TaskOperation::new("move", 0, Some(tasks[0].clone()), Some(after))));
+
+        let journals = journal.journals.lock().unwrap();
+        let recorded = &journals["/project"].undo[0];
+        assert_eq!(recorded.after.as_ref().unwrap().column, "blocked");
+    }
+}
@@ -0,0 +1,406 @@
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+
+use super::boards::{self, BoardConfig};
+use super::merge;
+use super::tasks::{self, TasksMdWriteResult, HEADING_PATTERN};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TasksMdDiagnostic {
+    /// 1-indexed
+    pub line: usize,
+    /// "error" when tasks are lost or mutations break, otherwise "warning"
+    pub severity: String,
+    /// "duplicate-subject", "orphan-task", "unknown-heading", "unclosed-fence",
+    /// "completed-outside-done" or "indentation"; formatting also reports "discarded-line"
+    /// and "renamed-section"
+    pub code: String,
+    pub message: String,
+    /// Whether `format_tasks_md` fixes it
+    pub fixable: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FormatResult {
+    /// The formatted file
+    pub content: String,
+    /// Diagnostics that formatting fixed
+    pub fixed: Vec<TasksMdDiagnostic>,
+    /// Diagnostics left after formatting
+    pub remaining: Vec<TasksMdDiagnostic>,
+    /// What formatting drops or renames, by line of the original file
+    pub unfixable: Vec<TasksMdDiagnostic>,
+    /// `None` for a dry run or when the file was already formatted
+    pub write: Option<TasksMdWriteResult>,
+}
+
+fn diagnostic(line: usize, severity: &str, code: &str, message: String, fixable: bool) -> TasksMdDiagnostic {
+    TasksMdDiagnostic {
+        line,
+        severity: severity.to_string(),
+        code: code.to_string(),
+        message,
+        fixable,
+    }
+}
+
+/// Leading whitespace that doesn't nest in steps of two spaces
+fn bad_indentation(line: &str) -> bool {
+    let indent: String = line.chars().take_while(|c| c.is_whitespace()).collect();
+    !line.trim().is_empty() && (indent.contains('\t') || indent.len() % 2 == 1)
+}
+
+/// Line of a code fence that is never closed, if any (0-indexed)
+fn unclosed_fence(lines: &[&str]) -> Option<usize> {
+    let mut open = None;
+    for (i, line) in lines.iter().enumerate() {
+        if line.trim().starts_with("```") {
+            open = if open.is_some() { None } else { Some(i) };
+        }
+    }
+    open
+}
+
+/// Check board content for problems that make cards vanish from the board or break
+/// subject-based mutations. Diagnostics are sorted by line.
+pub fn lint(content: &str, board: &BoardConfig) -> Result<Vec<TasksMdDiagnostic>, String> {
+    let heading_re = Regex::new(HEADING_PATTERN).unwrap();
+    let task_re = Regex::new(r"^-\s*\[([ xX])\]\s*(.+)$").unwrap();
+
+    let lines: Vec<&str> = content.lines().collect();
+    let mut diagnostics = Vec::new();
+
+    let fence = unclosed_fence(&lines);
+    if let Some(i) = fence {
+        diagnostics.push(diagnostic(
+            i + 1,
+            "error",
+            "unclosed-fence",
+            "Code fence is never closed; everything after it is hidden from the board".to_string(),
+            true,
+        ));
+    }
+
+    let mut in_code_block = false;
+    let mut in_column = false;
+
+    for (i, line) in lines.iter().enumerate() {
+        let line_number = i + 1;
+
+        if line.trim().starts_with("```") {
+            in_code_block = !in_code_block;
+            continue;
+        }
+        if in_code_block {
+            continue;
+        }
+
+        if bad_indentation(line) {
+            diagnostics.push(diagnostic(
+                line_number,
+                "warning",
+                "indentation",
+                "Indent with two spaces per level; other indentation may detach descriptions and subtasks".to_string(),
+                true,
+            ));
+        }
+
+        if let Some(caps) = heading_re.captures(line) {
+            if caps[1].len() == 2 {
+                let heading = caps[2].trim();
+                in_column = board.heading_to_column(heading).is_some();
+                if !in_column {
+                    // Formatting files the tasks into the first column but loses the section
+                    diagnostics.push(diagnostic(
+                        line_number,
+                        "warning",
+                        "unknown-heading",
+                        format!("'## {}' is not a board column; tasks under it are not shown", heading),
+                        false,
+                    ));
+                }
+            }
+            continue;
+        }
+
+        if !in_column {
+            if let Some(caps) = task_re.captures(line) {
+                diagnostics.push(diagnostic(
+                    line_number,
+                    "error",
+                    "orphan-task",
+                    format!("'{}' is outside any column and is not shown on the board", caps[2].trim()),
+                    true,
+                ));
+            }
+        }
+    }
+
+    let parsed = tasks::parse_board(content, board)?;
+    let mut first_seen: HashMap<&str, usize> = HashMap::new();
+
+    for task in &parsed {
+        match first_seen.get(task.subject.as_str()) {
+            Some(&first) => diagnostics.push(diagnostic(
+                task.line_number,
+                "error",
+                "duplicate-subject",
+                format!("Duplicate subject '{}' (first on line {}); moves and edits only reach the first", task.subject, first),
+                false,
+            )),
+            None => {
+                first_seen.insert(&task.subject, task.line_number);
+            }
+        }
+
+        if task.completed && task.column != "done" {
+            diagnostics.push(diagnostic(
+                task.line_number,
+                "warning",
+                "completed-outside-done",
+                format!("'{}' is checked but not in Done", task.subject),
+                true,
+            ));
+        }
+    }
+
+    diagnostics.sort_by_key(|d| d.line);
+    Ok(diagnostics)
+}
+
+/// Rewrite content so every task lands in a column: drop an unclosed fence, normalize
+/// indentation outside code blocks, file tasks under unknown headings or no heading into the
+/// board's first column and checked tasks into Done
+pub fn format(content: &str, board: &BoardConfig) -> Result<String, String> {
+    let lines: Vec<&str> = content.lines().collect();
+    let fence = unclosed_fence(&lines);
+
+    // Code inside fenced blocks is left as written
+    let mut in_code_block = false;
+    let normalized: Vec<String> = lines
+        .iter()
+        .enumerate()
+        .filter(|(i, _)| Some(*i) != fence)
+        .map(|(_, line)| {
+            let is_fence = line.trim().starts_with("```");
+            if in_code_block && !is_fence {
+                return line.to_string();
+            }
+            if is_fence {
+                in_code_block = !in_code_block;
+            }
+
+            let rest = line.trim_start();
+            let indent: String = line[..line.len() - rest.len()].replace('\t', "  ");
+            let width = match indent.len() {
+                1 => 2,
+                n => n - n % 2,
+            };
+            format!("{}{}", " ".repeat(width), rest)
+        })
+        .collect();
+
+    let first_column = board.columns().first().map(|c| c.id.clone()).unwrap_or_default();
+    let mut tasks = tasks::parse_checklist(&normalized.join("\n"), Some(first_column.clone()), |level, heading| {
+        if level != 2 {
+            return None;
+        }
+        Some(board.heading_to_column(heading).unwrap_or_else(|| first_column.clone()))
+    })?;
+
+    let has_done = board.check_column("done").is_ok();
+    for task in &mut tasks {
+        if task.completed && has_done {
+            task.column = "done".to_string();
+        }
+    }
+
+    Ok(tasks::render_board(&tasks, board))
+}
+
+/// What `format` loses from `content`: unknown `##` sections, whose tasks end up in the
+/// board's first column, and lines that belong to no task and are missing from `formatted`
+fn unfixable(content: &str, formatted: &str, board: &BoardConfig) -> Vec<TasksMdDiagnostic> {
+    let heading_re = Regex::new(HEADING_PATTERN).unwrap();
+    let task_re = Regex::new(r"^-\s*\[([ xX])\]\s*(.+)$").unwrap();
+    let first_heading = board.columns().first().map(|c| c.heading.clone()).unwrap_or_default();
+
+    let mut kept: HashMap<&str, usize> = HashMap::new();
+    for line in formatted.lines() {
+        *kept.entry(line.trim()).or_default() += 1;
+    }
+
+    let mut diagnostics = Vec::new();
+    for (i, line) in content.lines().enumerate() {
+        let trimmed = line.trim();
+        // Tasks are always kept, and a dropped fence is the fix for an unclosed one
+        if trimmed.is_empty() || trimmed.starts_with("```") || task_re.is_match(trimmed) {
+            continue;
+        }
+
+        if let Some(caps) = heading_re.captures(line) {
+            if caps[1].len() == 2 {
+                let heading = caps[2].trim();
+                if board.heading_to_column(heading).is_none() {
+                    diagnostics.push(diagnostic(
+                        i + 1,
+                        "warning",
+                        "renamed-section",
+                        format!("'## {}' is not a board column; its tasks were moved to {}", heading, first_heading),
+                        false,
+                    ));
+                }
+                continue;
+            }
+        }
+
+        match kept.get_mut(trimmed) {
+            Some(count) if *count > 0 => *count -= 1,
+            _ => diagnostics.push(diagnostic(
+                i + 1,
+                "error",
+                "discarded-line",
+                format!("'{}' is not part of a task and was dropped", trimmed),
+                false,
+            )),
+        }
+    }
+
+    diagnostics
+}
+
+fn read_content(project_path: &str, board: &BoardConfig) -> Result<String, String> {
+    let tasks_md_path = board.path(project_path);
+
+    if !tasks_md_path.exists() {
+        return Err(format!("{} does not exist", board.file));
+    }
+
+    fs::read_to_string(&tasks_md_path).map_err(|e| format!("Failed to read {}: {}", board.file, e))
+}
+
+/// Line-numbered diagnostics for one of a project's boards
+#[tauri::command]
+pub fn lint_tasks_md(
+    project_path: String,
+    board: Option<String>,
+    state: tauri::State<'_, crate::state::AppState>,
+) -> Result<Vec<TasksMdDiagnostic>, String> {
+    let board = boards::board_config(&state, &project_path, board.as_deref())?;
+    lint(&read_content(&project_path, &board)?, &board)
+}
+
+/// Fix what the linter can. Formatting drops lines that belong to no task, so the result is
+/// only written when `dry_run` is explicitly `false`. With a `base_version` the version the
+/// caller read is formatted, and edits made on disk since are merged in.
+#[tauri::command]
+pub fn format_tasks_md(
+    project_path: String,
+    board: Option<String>,
+    dry_run: Option<bool>,
+    base_version: Option<String>,
+    state: tauri::State<'_, crate::state::AppState>,
+    versions: tauri::State<'_, merge::TasksMdVersions>,
+) -> Result<FormatResult, String> {
+    let board = boards::board_config(&state, &project_path, board.as_deref())?;
+    let current = read_content(&project_path, &board)?;
+    let source = match &base_version {
+        Some(version) if *version != merge::content_hash(&current) => versions.get(version).ok_or_else(|| {
+            format!(
+                "{} changed since it was read and that version is no longer known; reload the board",
+                board.file
+            )
+        })?,
+        _ => current,
+    };
+
+    let before = lint(&source, &board)?;
+    let content = format(&source, &board)?;
+    let remaining = lint(&content, &board)?;
+    let unfixable = unfixable(&source, &content, &board);
+
+    let write = if dry_run.unwrap_or(true) || content == source {
+        None
+    } else {
+        let formatted = tasks::parse_board(&content, &board)?;
+        Some(tasks::frontend_write(|| {
+            tasks::mutate_board(&project_path, &board, base_version, &versions, true, |tasks| {
+                *tasks = formatted;
+                Ok(())
+            })
+        })?)
+    };
+
+    Ok(FormatResult {
+        content,
+        fixed: before.into_iter().filter(|d| d.fixable).collect(),
+        remaining,
+        unfixable,
+        write,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MALFORMED: &str = "# TASKS\n\n- [ ] Stray\n\n## Backlog\n- [ ] Fix login\n   Odd indent\n- [x] Shipped\n\n## Ideas\n- [ ] Maybe\n\n## Done\n- [ ] Fix login\n\n```\n- [ ] Hidden\n";
+
+    #[test]
+    fn test_lint() {
+        let diagnostics = lint(MALFORMED, &BoardConfig::default()).unwrap();
+        let codes: Vec<(usize, &str)> = diagnostics.iter().map(|d| (d.line, d.code.as_str())).collect();
+
+        assert_eq!(
+            codes,
+            vec![
+                (3, "orphan-task"),
+                (7, "indentation"),
+                (8, "completed-outside-done"),
+                (10, "unknown-heading"),
+                (11, "orphan-task"),
+                (14, "duplicate-subject"),
+                (16, "unclosed-fence"),
+            ]
+        );
+        assert!(diagnostics[5].message.contains("first on line 6"));
+    }
+
+    #[test]
+    fn test_format() {
+        let formatted = format(MALFORMED, &BoardConfig::default()).unwrap();
+
+        assert_eq!(
+            formatted,
+            "# TASKS\n\n## Backlog\n- [ ] Stray\n- [ ] Fix login\n  Odd indent\n- [ ] Maybe\n\n## Blocked\n\n## In Progress\n\n## Review\n\n## Done\n- [x] Shipped\n- [ ] Fix login\n- [ ] Hidden\n\n"
+        );
+
+        // Only the duplicate is left for a human
+        let remaining = lint(&formatted, &BoardConfig::default()).unwrap();
+        assert_eq!(remaining.len(), 1);
+        assert_eq!(remaining[0].code, "duplicate-subject");
+        assert!(!remaining[0].fixable);
+
+        let lost = unfixable(MALFORMED, &formatted, &BoardConfig::default());
+        let codes: Vec<(usize, &str)> = lost.iter().map(|d| (d.line, d.code.as_str())).collect();
+        assert_eq!(codes, vec![(10, "renamed-section")]);
+    }
+
+    #[test]
+    fn test_format_reports_discarded_lines() {
+        let content = "# TASKS
+
+Notes for the team
+
+## Backlog
+- [ ] One
+";
+        let formatted = format(content, &BoardConfig::default()).unwrap();
+
+        let lost = unfixable(content, &formatted, &BoardConfig::default());
+        assert_eq!(lost.len(), 1);
+        assert_eq!((lost[0].line, lost[0].code.as_str()), (3, "discarded-line"));
+    }
+}
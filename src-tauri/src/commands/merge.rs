@@ -0,0 +1,265 @@
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::sync::Mutex;
+
+use super::tasks::{self, TasksMdTask};
+
+/// How many TASKS.md versions are kept around as merge bases
+const MAX_VERSIONS: usize = 64;
+
+/// Recent TASKS.md contents keyed by version, so a mutation based on an older version can be
+/// merged with whatever changed on disk since
+pub struct TasksMdVersions {
+    contents: Mutex<VecDeque<(String, String)>>,
+}
+
+impl TasksMdVersions {
+    pub fn new() -> Self {
+        Self {
+            contents: Mutex::new(VecDeque::new()),
+        }
+    }
+
+    /// Remember a content snapshot and return its version
+    pub fn remember(&self, content: &str) -> String {
+        let version = content_hash(content);
+
+        if let Ok(mut contents) = self.contents.lock() {
+            if !contents.iter().any(|(v, _)| *v == version) {
+                contents.push_back((version.clone(), content.to_string()));
+                if contents.len() > MAX_VERSIONS {
+                    contents.pop_front();
+                }
+            }
+        }
+
+        version
+    }
+
+    pub fn get(&self, version: &str) -> Option<String> {
+        let contents = self.contents.lock().ok()?;
+        contents
+            .iter()
+            .find(|(v, _)| v == version)
+            .map(|(_, content)| content.clone())
+    }
+}
+
+impl Default for TasksMdVersions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Version of a TASKS.md file: a 64-bit FNV-1a hash of its content, as hex
+pub fn content_hash(content: &str) -> String {
+    let mut hash: u64 = 0xcbf29ce484222325;
+    for byte in content.as_bytes() {
+        hash ^= *byte as u64;
+        hash = hash.wrapping_mul(0x100000001b3);
+    }
+    format!("{:016x}", hash)
+}
+
+/// A task both sides changed differently since the common base
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TaskMergeConflict {
+    pub subject: String,
+    /// The task as the caller last saw it; `None` if both sides added it
+    pub base: Option<TasksMdTask>,
+    /// The caller's version; `None` if the caller deleted it
+    pub ours: Option<TasksMdTask>,
+    /// The version on disk; `None` if it was deleted on disk
+    pub theirs: Option<TasksMdTask>,
+}
+
+pub struct MergeOutcome {
+    pub tasks: Vec<TasksMdTask>,
+    pub conflicts: Vec<TaskMergeConflict>,
+}
+
+pub(crate) fn task_key(task: &TasksMdTask) -> &str {
+    task.stable_id.as_deref().unwrap_or(&task.subject)
+}
+
+/// Everything about a task that ends up in the file
+pub(crate) fn fingerprint(task: &TasksMdTask) -> String {
+    format!(
+        "{}\n{}\n{}",
+        task.column,
+        task.epic.as_deref().unwrap_or_default(),
+        tasks::render_task(task)
+    )
+}
+
+/// Three-way merge of task lists at task granularity. `ours` is `base` with the caller's
+/// mutation applied; `theirs` is what is on disk now. Tasks only one side touched take that
+/// side's version; tasks both sides changed differently become conflicts. The result follows
+/// the on-disk order, with tasks the caller added appended.
+pub fn merge_tasks(
+    base: &[TasksMdTask],
+    ours: &[TasksMdTask],
+    theirs: &[TasksMdTask],
+) -> MergeOutcome {
+    // Pair our tasks with base tasks by id (same parse), falling back to the task key
+    let ours_for_base: Vec<Option<usize>> = base
+        .iter()
+        .map(|b| {
+            ours.iter()
+                .position(|o| o.id == b.id)
+                .or_else(|| ours.iter().position(|o| task_key(o) == task_key(b)))
+        })
+        .collect();
+    let theirs_for_base: Vec<Option<usize>> = base
+        .iter()
+        .map(|b| theirs.iter().position(|t| task_key(t) == task_key(b)))
+        .collect();
+
+    let mut tasks = Vec::new();
+    let mut conflicts = Vec::new();
+
+    let mut conflict = |b: Option<&TasksMdTask>, o: Option<&TasksMdTask>, t: Option<&TasksMdTask>| {
+        let subject = o.or(t).or(b).map(|task| task.subject.clone()).unwrap_or_default();
+        conflicts.push(TaskMergeConflict {
+            subject,
+            base: b.cloned(),
+            ours: o.cloned(),
+            theirs: t.cloned(),
+        });
+    };
+
+    for (ti, t) in theirs.iter().enumerate() {
+        let Some(bi) = theirs_for_base.iter().position(|&x| x == Some(ti)) else {
+            // Added on disk; identical additions on our side collapse into this one
+            let added_by_us = ours
+                .iter()
+                .find(|o| task_key(o) == task_key(t) && !base.iter().any(|b| task_key(b) == task_key(o)));
+            match added_by_us {
+                Some(o) if fingerprint(o) != fingerprint(t) => conflict(None, Some(o), Some(t)),
+                _ => tasks.push(t.clone()),
+            }
+            continue;
+        };
+
+        let b = &base[bi];
+        let o = ours_for_base[bi].map(|oi| &ours[oi]);
+        let ours_changed = o.map(|o| fingerprint(o) != fingerprint(b)).unwrap_or(true);
+        let theirs_changed = fingerprint(t) != fingerprint(b);
+
+        match o {
+            _ if !ours_changed => tasks.push(t.clone()),
+            Some(o) if !theirs_changed => tasks.push(o.clone()),
+            Some(o) if fingerprint(o) == fingerprint(t) => tasks.push(t.clone()),
+            // Deleted by us and untouched on disk
+            None if !theirs_changed => {}
+            _ => {
+                conflict(Some(b), o, Some(t));
+                tasks.push(t.clone());
+            }
+        }
+    }
+
+    // Deleted on disk: gone, unless we changed it in the meantime
+    for (bi, b) in base.iter().enumerate() {
+        if theirs_for_base[bi].is_some() {
+            continue;
+        }
+        if let Some(o) = ours_for_base[bi].map(|oi| &ours[oi]) {
+            if fingerprint(o) != fingerprint(b) {
+                conflict(Some(b), Some(o), None);
+            }
+        }
+    }
+
+    // Added by us
+    for (oi, o) in ours.iter().enumerate() {
+        let from_base = ours_for_base.contains(&Some(oi));
+        let on_disk = theirs.iter().any(|t| task_key(t) == task_key(o));
+        if !from_base && !on_disk {
+            tasks.push(o.clone());
+        }
+    }
+
+    MergeOutcome { tasks, conflicts }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BASE: &str = r#"# TASKS
+
+## Backlog
+- [ ] First
+- [ ] Second
+- [ ] Third
+"#;
+
+    fn parse(content: &str) -> Vec<TasksMdTask> {
+        tasks::parse_tasks_md(content).unwrap()
+    }
+
+    #[test]
+    fn test_content_hash_is_stable() {
+        assert_eq!(content_hash(""), "cbf29ce484222325");
+        assert_ne!(content_hash("a"), content_hash("b"));
+    }
+
+    #[test]
+    fn test_merge_disjoint_edits() {
+        let base = parse(BASE);
+
+        // We move First; on disk, Third was renamed and a task was added
+        let mut ours = base.clone();
+        ours[0].column = "in_progress".to_string();
+        ours.push(TasksMdTask::new("Ours new", None, "backlog"));
+        let theirs = parse(
+            &BASE
+                .replace("Third", "Third renamed")
+                .replace("- [ ] Second\n", "- [ ] Second\n- [ ] Theirs new\n"),
+        );
+
+        let outcome = merge_tasks(&base, &ours, &theirs);
+        assert!(outcome.conflicts.is_empty());
+
+        let subjects: Vec<&str> = outcome.tasks.iter().map(|t| t.subject.as_str()).collect();
+        assert_eq!(subjects, vec!["First", "Second", "Theirs new", "Third renamed", "Ours new"]);
+        assert_eq!(outcome.tasks[0].column, "in_progress");
+    }
+
+    #[test]
+    fn test_merge_conflicting_edits() {
+        let base = parse(BASE);
+
+        let mut ours = base.clone();
+        ours[1].column = "done".to_string();
+        ours[1].completed = true;
+        ours.remove(2);
+        let theirs = parse(
+            &BASE
+                .replace("- [ ] Second", "- [ ] Second\n  Agent notes")
+                .replace("Third", "Third edited"),
+        );
+
+        let outcome = merge_tasks(&base, &ours, &theirs);
+
+        // Second changed on both sides; Third was deleted by us but renamed on disk, which
+        // reads as a delete on disk plus an addition, so only Second conflicts
+        assert_eq!(outcome.conflicts.len(), 1);
+        assert_eq!(outcome.conflicts[0].subject, "Second");
+        assert!(outcome.tasks.iter().any(|t| t.subject == "Third edited"));
+    }
+
+    #[test]
+    fn test_merge_delete_versus_edit_conflicts() {
+        let base = parse(BASE);
+
+        let mut ours = base.clone();
+        ours.remove(0);
+        let theirs = parse(&BASE.replace("- [ ] First", "- [x] First"));
+
+        let outcome = merge_tasks(&base, &ours, &theirs);
+        assert_eq!(outcome.conflicts.len(), 1);
+        assert!(outcome.conflicts[0].ours.is_none());
+    }
+}
@@ -2,5 +2,26 @@ pub mod projects;
 pub mod filesystem;
 pub mod terminal;
 pub mod tasks;
+pub mod boards;
+pub mod epics;
+pub mod comments;
+pub mod claude_tasks;
+pub mod task_graph;
+pub mod templates;
+pub mod dispatch;
+pub mod commits;
+pub mod dependencies;
+pub mod sync;
+pub mod merge;
+pub mod lint;
+pub mod journal;
+pub mod history;
+pub mod archive;
+pub mod query;
+pub mod import_export;
+pub mod board_sources;
+pub mod progress;
 pub mod settings;
 pub mod notifications;
+pub mod reminders;
+pub mod scheduler;
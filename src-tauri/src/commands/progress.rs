@@ -0,0 +1,431 @@
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+
+use super::filesystem::HIDDEN_DIRS;
+use super::tasks::{self, TaskProgress};
+
+/// Checkbox counts under one heading (not including its sub-headings)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HeadingProgress {
+    /// Heading text; empty for checkboxes before the first heading
+    pub heading: String,
+    pub level: usize,
+    #[serde(rename = "lineNumber")]
+    pub line_number: usize,
+    pub total: usize,
+    pub completed: usize,
+    #[serde(rename = "inProgress")]
+    pub in_progress: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FileProgress {
+    /// Path relative to the project root
+    pub path: String,
+    pub total: usize,
+    pub completed: usize,
+    #[serde(rename = "inProgress")]
+    pub in_progress: usize,
+    pub percentage: f64,
+    pub headings: Vec<HeadingProgress>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProgressBreakdown {
+    pub total: usize,
+    pub completed: usize,
+    #[serde(rename = "inProgress")]
+    pub in_progress: usize,
+    pub percentage: f64,
+    pub files: Vec<FileProgress>,
+}
+
+fn percentage(completed: usize, total: usize) -> f64 {
+    if total > 0 {
+        (completed as f64 / total as f64) * 100.0
+    } else {
+        0.0
+    }
+}
+
+/// Count checkboxes per heading. `-`, `*`, `+` and ordered (`1.`, `1)`) list items count;
+/// `[x]` is completed and `[-]` / `[~]` are in progress. Code blocks are skipped.
+pub fn scan_checkboxes(content: &str) -> Vec<HeadingProgress> {
+    let checkbox_re = Regex::new(r"^\s*(?:[-*+]|\d+[.)])\s*\[([ xX~-])\]").unwrap();
+    let heading_re = Regex::new(tasks::HEADING_PATTERN).unwrap();
+
+    let mut sections = vec![HeadingProgress {
+        heading: String::new(),
+        level: 0,
+        line_number: 0,
+        total: 0,
+        completed: 0,
+        in_progress: 0,
+    }];
+    let mut in_code_block = false;
+
+    for (i, line) in content.lines().enumerate() {
+        if line.trim().starts_with("```") {
+            in_code_block = !in_code_block;
+            continue;
+        }
+        if in_code_block {
+            continue;
+        }
+
+        if let Some(caps) = heading_re.captures(line) {
+            sections.push(HeadingProgress {
+                heading: caps
+                    .get(2)
+                    .map(|m| m.as_str().trim())
+                    .unwrap_or("")
+                    .to_string(),
+                level: caps.get(1).map(|m| m.as_str().len()).unwrap_or(0),
+                line_number: i + 1,
+                total: 0,
+                completed: 0,
+                in_progress: 0,
+            });
+            continue;
+        }
+
+        if let Some(caps) = checkbox_re.captures(line) {
+            let section = sections.last_mut().expect("sections starts non-empty");
+            section.total += 1;
+            match caps.get(1).map(|m| m.as_str()) {
+                Some("x") | Some("X") => section.completed += 1,
+                Some("-") | Some("~") => section.in_progress += 1,
+                _ => {}
+            }
+        }
+    }
+
+    sections.retain(|s| s.total > 0);
+    sections
+}
+
+/// Match a `/`-separated relative path against a glob: `*` and `?` stay within one path
+/// segment, `**` spans any number of segments
+pub fn glob_match(pattern: &str, path: &str) -> bool {
+    fn segment_match(pattern: &[char], text: &[char]) -> bool {
+        match (pattern.first(), text.first()) {
+            (None, None) => true,
+            (Some('*'), _) => {
+                segment_match(&pattern[1..], text)
+                    || (!text.is_empty() && segment_match(pattern, &text[1..]))
+            }
+            (Some('?'), Some(_)) => segment_match(&pattern[1..], &text[1..]),
+            (Some(p), Some(t)) if p == t => segment_match(&pattern[1..], &text[1..]),
+            _ => false,
+        }
+    }
+
+    fn path_match(pattern: &[&str], path: &[&str]) -> bool {
+        match (pattern.first(), path.first()) {
+            (None, None) => true,
+            (Some(&"**"), _) => {
+                path_match(&pattern[1..], path)
+                    || (!path.is_empty() && path_match(pattern, &path[1..]))
+            }
+            (Some(p), Some(s)) => {
+                let p: Vec<char> = p.chars().collect();
+                let s: Vec<char> = s.chars().collect();
+                segment_match(&p, &s) && path_match(&pattern[1..], &path[1..])
+            }
+            _ => false,
+        }
+    }
+
+    let pattern: Vec<&str> = pattern.split('/').filter(|s| !s.is_empty()).collect();
+    let path: Vec<&str> = path.split('/').filter(|s| !s.is_empty()).collect();
+    path_match(&pattern, &path)
+}
+
+/// Globs are relative to the project and may not climb out of it
+pub fn validate_glob(glob: &str) -> Result<(), String> {
+    let path = Path::new(glob);
+    if glob.trim().is_empty() || !path.components().all(|c| matches!(c, Component::Normal(_))) {
+        return Err(format!("Invalid progress glob '{}'", glob));
+    }
+    Ok(())
+}
+
+/// How deep the directory walk has to go; `None` when a `**` makes it unbounded
+fn walk_depth(globs: &[String]) -> Option<usize> {
+    globs.iter().try_fold(0, |depth, glob| {
+        if glob.contains("**") {
+            None
+        } else {
+            Some(depth.max(glob.matches('/').count()))
+        }
+    })
+}
+
+/// Relative paths of the project files matching any of the globs, skipping hidden and
+/// build directories
+fn matching_files(project_dir: &Path, globs: &[String]) -> Vec<String> {
+    fn walk(
+        dir: &Path,
+        relative: &str,
+        depth_left: Option<usize>,
+        globs: &[String],
+        out: &mut Vec<String>,
+    ) {
+        let Ok(entries) = fs::read_dir(dir) else {
+            return;
+        };
+
+        for entry in entries.flatten() {
+            let name = entry.file_name().to_string_lossy().to_string();
+            let path = if relative.is_empty() {
+                name.clone()
+            } else {
+                format!("{}/{}", relative, name)
+            };
+            let entry_path = entry.path();
+            // The entry's own type, so a symlinked directory isn't followed into a loop
+            let Ok(file_type) = entry.file_type() else {
+                continue;
+            };
+
+            if file_type.is_dir() {
+                if name.starts_with('.') || HIDDEN_DIRS.contains(&name.as_str()) {
+                    continue;
+                }
+                match depth_left {
+                    Some(0) => {}
+                    Some(n) => walk(&entry_path, &path, Some(n - 1), globs, out),
+                    None => walk(&entry_path, &path, None, globs, out),
+                }
+            } else if globs.iter().any(|g| glob_match(g, &path)) {
+                out.push(path);
+            }
+        }
+    }
+
+    let mut files = Vec::new();
+    walk(project_dir, "", walk_depth(globs), globs, &mut files);
+    files.sort();
+    files
+}
+
+/// The globs a project's progress is counted from: its own, or the default checklist files
+pub fn progress_globs(state: &crate::state::AppState, project_path: &str) -> Vec<String> {
+    let configured = state
+        .projects
+        .lock()
+        .ok()
+        .and_then(|projects| {
+            projects
+                .iter()
+                .find(|p| p.path == project_path)
+                .map(|p| p.progress_globs.clone())
+        })
+        .unwrap_or_default();
+
+    if configured.is_empty() {
+        tasks::TASK_FILES.iter().map(|f| f.to_string()).collect()
+    } else {
+        configured
+    }
+}
+
+pub fn progress_breakdown(
+    project_path: &str,
+    globs: &[String],
+) -> Result<ProgressBreakdown, String> {
+    let project_dir = PathBuf::from(project_path);
+
+    if !project_dir.exists() || !project_dir.is_dir() {
+        return Err("Invalid project path".to_string());
+    }
+
+    let mut files = Vec::new();
+
+    for path in matching_files(&project_dir, globs) {
+        let Ok(content) = fs::read_to_string(project_dir.join(&path)) else {
+            continue;
+        };
+
+        let headings = scan_checkboxes(&content);
+        let total = headings.iter().map(|h| h.total).sum();
+        if total == 0 {
+            continue;
+        }
+        let completed = headings.iter().map(|h| h.completed).sum();
+
+        files.push(FileProgress {
+            path,
+            total,
+            completed,
+            in_progress: headings.iter().map(|h| h.in_progress).sum(),
+            percentage: percentage(completed, total),
+            headings,
+        });
+    }
+
+    let total = files.iter().map(|f| f.total).sum();
+    let completed = files.iter().map(|f| f.completed).sum();
+
+    Ok(ProgressBreakdown {
+        total,
+        completed,
+        in_progress: files.iter().map(|f| f.in_progress).sum(),
+        percentage: percentage(completed, total),
+        files,
+    })
+}
+
+impl From<&ProgressBreakdown> for TaskProgress {
+    fn from(breakdown: &ProgressBreakdown) -> Self {
+        Self {
+            total: breakdown.total,
+            completed: breakdown.completed,
+            in_progress: breakdown.in_progress,
+            percentage: breakdown.percentage,
+        }
+    }
+}
+
+/// Checkbox progress per file and per heading, over the project's progress globs
+#[tauri::command]
+pub fn get_task_progress_breakdown(
+    project_path: String,
+    state: tauri::State<'_, crate::state::AppState>,
+) -> Result<ProgressBreakdown, String> {
+    let globs = progress_globs(&state, &project_path);
+    progress_breakdown(&project_path, &globs)
+}
+
+/// Set the globs a project's checkbox progress is counted from; an empty list restores the
+/// default checklist files
+#[tauri::command]
+pub fn set_progress_globs(
+    project_id: String,
+    globs: Vec<String>,
+    state: tauri::State<'_, crate::state::AppState>,
+) -> Result<(), String> {
+    for glob in &globs {
+        validate_glob(glob)?;
+    }
+
+    let mut projects = state.projects.lock().map_err(|e| e.to_string())?;
+    let project = projects
+        .iter_mut()
+        .find(|p| p.id == project_id)
+        .ok_or("Project not found")?;
+
+    project.progress_globs = globs.into_iter().map(|g| g.trim().to_string()).collect();
+
+    crate::state::save_projects(&projects)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn checkbox_totals(content: &str) -> (usize, usize) {
+        let sections = scan_checkboxes(content);
+        (
+            sections.iter().map(|s| s.total).sum(),
+            sections.iter().map(|s| s.completed).sum(),
+        )
+    }
+
+    #[test]
+    fn test_parse_tasks_empty() {
+        let (total, completed) = checkbox_totals("");
+        assert_eq!(total, 0);
+        assert_eq!(completed, 0);
+    }
+
+    #[test]
+    fn test_parse_tasks_simple() {
+        let content = r#"
+# Tasks
+- [ ] First task
+- [x] Second task
+- [X] Third task
+- [ ] Fourth task
+"#;
+        let (total, completed) = checkbox_totals(content);
+        assert_eq!(total, 4);
+        assert_eq!(completed, 2);
+    }
+
+    #[test]
+    fn test_parse_tasks_ignores_code_blocks() {
+        let content = r#"
+# Tasks
+- [ ] Real task
+
+```markdown
+- [ ] This is in a code block
+- [x] Also in code block
+```
+
+- [x] Another real task
+"#;
+        let (total, completed) = checkbox_totals(content);
+        assert_eq!(total, 2);
+        assert_eq!(completed, 1);
+    }
+
+    #[test]
+    fn test_scan_checkboxes() {
+        let content = r#"- [x] Before any heading
+
+# Plan
+1. [x] Ordered
+2) [ ] Ordered paren
+* [-] Star, in progress
++ [~] Plus, in progress
+
+## Later
+- [ ] Dash
+```
+- [ ] In a code block
+```
+"#;
+        let sections = scan_checkboxes(content);
+        assert_eq!(sections.len(), 3);
+
+        assert_eq!(sections[0].heading, "");
+        assert_eq!(sections[0].completed, 1);
+
+        assert_eq!(sections[1].heading, "Plan");
+        assert_eq!(sections[1].line_number, 3);
+        assert_eq!(sections[1].total, 4);
+        assert_eq!(sections[1].completed, 1);
+        assert_eq!(sections[1].in_progress, 2);
+
+        assert_eq!(sections[2].heading, "Later");
+        assert_eq!(sections[2].level, 2);
+        assert_eq!(sections[2].total, 1);
+    }
+
+    #[test]
+    fn test_glob_match() {
+        assert!(glob_match("plan.md", "plan.md"));
+        assert!(!glob_match("plan.md", "docs/plan.md"));
+        assert!(glob_match("specs/*.md", "specs/api.md"));
+        assert!(!glob_match("specs/*.md", "specs/v2/api.md"));
+        assert!(glob_match("docs/**/*.md", "docs/a.md"));
+        assert!(glob_match("docs/**/*.md", "docs/a/b/c.md"));
+        assert!(glob_match("**/TODO?.md", "x/TODO1.md"));
+        assert!(!glob_match("docs/**/*.md", "docs/a/b/c.txt"));
+    }
+
+    #[test]
+    fn test_walk_depth() {
+        assert_eq!(
+            walk_depth(&["plan.md".to_string(), "specs/*.md".to_string()]),
+            Some(1)
+        );
+        assert_eq!(walk_depth(&["docs/**/*.md".to_string()]), None);
+        assert!(validate_glob("../*.md").is_err());
+        assert!(validate_glob("/etc/*.md").is_err());
+    }
+}
@@ -3,6 +3,10 @@ use std::fs;
 use std::path::Path;
 use uuid::Uuid;
 
+use super::archive::ArchivePolicy;
+use super::boards::BoardConfig;
+use super::templates::seed_tasks;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Project {
     pub id: String,
@@ -10,6 +14,15 @@ pub struct Project {
     pub path: String,
     #[serde(rename = "hasActiveProcess", alias = "isAgentRunning")]
     pub has_active_process: bool,
+    /// Globs (relative to the project) that checkbox progress is counted from
+    #[serde(rename = "progressGlobs", default, skip_serializing_if = "Vec::is_empty")]
+    pub progress_globs: Vec<String>,
+    /// When Done tasks move to TASKS.archive.md; never when unset
+    #[serde(rename = "archivePolicy", default, skip_serializing_if = "Option::is_none")]
+    pub archive_policy: Option<ArchivePolicy>,
+    /// Board files besides TASKS.md, or TASKS.md itself with its own columns, with their layouts
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub boards: Vec<BoardConfig>,
 }
 
 #[tauri::command]
@@ -41,6 +54,9 @@ pub fn add_project(path: String, state: tauri::State<'_, crate::state::AppState>
         name,
         path,
         has_active_process: false,
+        progress_globs: Vec::new(),
+        archive_policy: None,
+        boards: Vec::new(),
     };
 
     let mut projects = state.projects.lock().map_err(|e| e.to_string())?;
@@ -63,7 +79,7 @@ pub fn add_project(path: String, state: tauri::State<'_, crate::state::AppState>
     }
 
     // Create TASKS.md if it doesn't exist
-    if let Err(e) = crate::commands::tasks::create_tasks_md(project.path.clone(), project.name.clone()) {
+    if let Err(e) = crate::commands::tasks::create_board_file(&project.path, &project.name, &BoardConfig::default()) {
         eprintln!("Failed to create TASKS.md: {}", e);
     }
 
@@ -79,7 +95,7 @@ pub fn add_project(path: String, state: tauri::State<'_, crate::state::AppState>
     Ok(project)
 }
 
-/// Initialize the Claude task directory for a new project with a starter task
+/// Initialize the Claude task directory for a new project with its starter tasks
 fn initialize_task_directory(project_id: &str, project_name: &str) -> Result<(), String> {
     let home_dir = dirs::home_dir().ok_or("Could not determine home directory")?;
     let tasks_dir = home_dir.join(".claude").join("tasks").join(project_id);
@@ -92,22 +108,26 @@ fn initialize_task_directory(project_id: &str, project_name: &str) -> Result<(),
     // Create the directory
     fs::create_dir_all(&tasks_dir).map_err(|e| format!("Failed to create tasks directory: {}", e))?;
 
-    // Create an initial starter task
-    let starter_task = serde_json::json!({
-        "id": "1",
-        "subject": format!("Set up {} project", project_name),
-        "status": "pending",
-        "description": "Initial task created by Agent Station. Update or delete as needed.",
-        "blocks": [],
-        "blockedBy": []
-    });
-
-    let task_file = tasks_dir.join("1.json");
-    let task_content = serde_json::to_string_pretty(&starter_task)
-        .map_err(|e| format!("Failed to serialize task: {}", e))?;
-
-    fs::write(&task_file, task_content)
-        .map_err(|e| format!("Failed to write task file: {}", e))?;
+    // Create the starter tasks from the seed templates
+    for (i, seed) in seed_tasks(project_name).into_iter().enumerate() {
+        let starter_task = serde_json::json!({
+            "id": (i + 1).to_string(),
+            "subject": seed.subject,
+            "status": "pending",
+            "description": seed.description.unwrap_or_else(|| {
+                "Initial task created by Agent Station. Update or delete as needed.".to_string()
+            }),
+            "blocks": [],
+            "blockedBy": []
+        });
+
+        let task_file = tasks_dir.join(format!("{}.json", i + 1));
+        let task_content = serde_json::to_string_pretty(&starter_task)
+            .map_err(|e| format!("Failed to serialize task: {}", e))?;
+
+        fs::write(&task_file, task_content)
+            .map_err(|e| format!("Failed to write task file: {}", e))?;
+    }
 
     Ok(())
 }
@@ -0,0 +1,321 @@
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::fs;
+use std::sync::Mutex;
+use std::time::SystemTime;
+
+use super::boards::{self, BoardConfig};
+use super::history;
+use super::reminders::days_until;
+use super::tasks::{self, TasksMdFilter, TasksMdTask};
+
+/// A board task tagged with the project and board file it belongs to
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProjectTask {
+    #[serde(rename = "projectId")]
+    pub project_id: String,
+    #[serde(rename = "projectName")]
+    pub project_name: String,
+    #[serde(rename = "projectPath")]
+    pub project_path: String,
+    pub board: String,
+    #[serde(flatten)]
+    pub task: TasksMdTask,
+}
+
+/// An open task with a due date, and how many days are left until it (negative when overdue)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UpcomingTask {
+    #[serde(flatten)]
+    pub task: ProjectTask,
+    #[serde(rename = "daysLeft")]
+    pub days_left: i64,
+}
+
+struct IndexEntry {
+    modified: SystemTime,
+    len: u64,
+    tasks: Vec<TasksMdTask>,
+}
+
+/// Parsed board files keyed by path, reused while the file's mtime and size match
+pub struct TaskIndex {
+    entries: Mutex<HashMap<String, IndexEntry>>,
+}
+
+impl TaskIndex {
+    pub fn new() -> Self {
+        Self {
+            entries: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Tasks of a board, parsing the file only when it changed since the last lookup
+    pub fn tasks_for(&self, project_path: &str, board: &BoardConfig) -> Result<Vec<TasksMdTask>, String> {
+        let tasks_md_path = board.path(project_path);
+        let key = tasks_md_path.to_string_lossy().to_string();
+
+        let Ok(metadata) = fs::metadata(&tasks_md_path) else {
+            let mut entries = self.entries.lock().map_err(|e| e.to_string())?;
+            entries.remove(&key);
+            return Ok(vec![]);
+        };
+        let modified = metadata.modified().unwrap_or(SystemTime::UNIX_EPOCH);
+        let len = metadata.len();
+
+        let mut entries = self.entries.lock().map_err(|e| e.to_string())?;
+
+        if let Some(entry) = entries.get(&key) {
+            if entry.modified == modified && entry.len == len {
+                return Ok(entry.tasks.clone());
+            }
+        }
+
+        let tasks = tasks::load_board(project_path, board)?;
+        entries.insert(
+            key,
+            IndexEntry {
+                modified,
+                len,
+                tasks: tasks.clone(),
+            },
+        );
+
+        Ok(tasks)
+    }
+}
+
+impl Default for TaskIndex {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn priority_rank(task: &TasksMdTask) -> usize {
+    // Unprioritized tasks rank below "low"
+    task.priority
+        .as_deref()
+        .and_then(|p| tasks::PRIORITIES.iter().position(|known| *known == p))
+        .map(|i| i + 1)
+        .unwrap_or(0)
+}
+
+/// An `~estimate` in minutes, counting 8-hour days and 5-day weeks
+fn estimate_minutes(task: &TasksMdTask) -> Option<f64> {
+    let estimate = task.estimate.as_deref()?.trim_start_matches('~');
+    let (amount, unit) = estimate.split_at(estimate.len().checked_sub(1)?);
+    let minutes = match unit {
+        "m" => 1.0,
+        "h" => 60.0,
+        "d" => 8.0 * 60.0,
+        "w" => 5.0 * 8.0 * 60.0,
+        _ => return None,
+    };
+    Some(amount.parse::<f64>().ok()? * minutes)
+}
+
+/// Position of a task's column on its own board, given the boards of the queried projects
+fn column_rank(task: &ProjectTask, boards: &[(String, BoardConfig)]) -> usize {
+    boards
+        .iter()
+        .find(|(project_id, board)| *project_id == task.project_id && board.file == task.board)
+        .map(|(_, board)| board.column_rank(&task.task.column))
+        .unwrap_or_else(|| tasks::column_rank(&task.task.column))
+}
+
+/// Compare two tasks by a sort key: "project", "column", "priority", "subject" or "estimate"
+fn compare(a: &ProjectTask, b: &ProjectTask, sort_by: &str, boards: &[(String, BoardConfig)]) -> Ordering {
+    match sort_by {
+        "project" => a.project_name.to_lowercase().cmp(&b.project_name.to_lowercase()),
+        "column" => column_rank(a, boards).cmp(&column_rank(b, boards)),
+        // Highest priority first
+        "priority" => priority_rank(&b.task).cmp(&priority_rank(&a.task)),
+        "subject" => a.task.subject.to_lowercase().cmp(&b.task.subject.to_lowercase()),
+        // Smallest first; tasks without an estimate last
+        "estimate" => match (estimate_minutes(&a.task), estimate_minutes(&b.task)) {
+            (Some(a), Some(b)) => a.partial_cmp(&b).unwrap_or(Ordering::Equal),
+            (Some(_), None) => Ordering::Less,
+            (None, Some(_)) => Ordering::Greater,
+            (None, None) => Ordering::Equal,
+        },
+        _ => Ordering::Equal,
+    }
+}
+
+/// Query the tasks of every board of every project, tagged with their project and board.
+/// Results keep project, board and file order unless `sort_by` is given; ties keep that order too.
+#[tauri::command]
+pub fn query_tasks(
+    filter: Option<TasksMdFilter>,
+    sort_by: Option<String>,
+    descending: Option<bool>,
+    state: tauri::State<'_, crate::state::AppState>,
+    index: tauri::State<'_, TaskIndex>,
+) -> Result<Vec<ProjectTask>, String> {
+    let projects = state.projects.lock().map_err(|e| e.to_string())?.clone();
+    let filter = filter.unwrap_or_default();
+
+    let mut results = Vec::new();
+    let mut queried = Vec::new();
+
+    for project in projects {
+        for board in boards::project_boards(Some(&project)).into_iter().filter(BoardConfig::is_tasks_md) {
+            let tasks = match index.tasks_for(&project.path, &board) {
+                Ok(tasks) => tasks,
+                Err(e) => {
+                    eprintln!("Failed to read {} for {}: {}", board.file, project.name, e);
+                    continue;
+                }
+            };
+
+            for task in tasks.into_iter().filter(|t| filter.matches(t)) {
+                results.push(ProjectTask {
+                    project_id: project.id.clone(),
+                    project_name: project.name.clone(),
+                    project_path: project.path.clone(),
+                    board: board.file.clone(),
+                    task,
+                });
+            }
+            queried.push((project.id.clone(), board));
+        }
+    }
+
+    if let Some(sort_by) = sort_by {
+        if descending.unwrap_or(false) {
+            results.sort_by(|a, b| compare(b, a, &sort_by, &queried));
+        } else {
+            results.sort_by(|a, b| compare(a, b, &sort_by, &queried));
+        }
+    }
+
+    Ok(results)
+}
+
+/// Open tasks across every project that are overdue or due within `within_days` (7 by
+/// default), soonest first
+#[tauri::command]
+pub fn upcoming_tasks(
+    within_days: Option<i64>,
+    state: tauri::State<'_, crate::state::AppState>,
+    index: tauri::State<'_, TaskIndex>,
+) -> Result<Vec<UpcomingTask>, String> {
+    let open = TasksMdFilter {
+        completed: Some(false),
+        ..Default::default()
+    };
+    let within_days = within_days.unwrap_or(7);
+    let now = history::now_ms();
+
+    let mut upcoming: Vec<UpcomingTask> = query_tasks(Some(open), None, None, state, index)?
+        .into_iter()
+        .filter(|t| t.task.column != "done")
+        .filter_map(|task| {
+            let days_left = days_until(task.task.due.as_deref()?, now)?;
+            (days_left <= within_days).then_some(UpcomingTask { task, days_left })
+        })
+        .collect();
+
+    upcoming.sort_by_key(|t| t.days_left);
+    Ok(upcoming)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn project_task(project: &str, subject: &str, column: &str) -> ProjectTask {
+        ProjectTask {
+            project_id: project.to_string(),
+            project_name: project.to_string(),
+            project_path: format!("/{}", project),
+            board: boards::DEFAULT_BOARD.to_string(),
+            task: TasksMdTask::new(subject, None, column),
+        }
+    }
+
+    #[test]
+    fn test_compare() {
+        let mut results = [
+            project_task("beta", "Ship it !low", "review"),
+            project_task("alpha", "Fix crash !critical", "backlog"),
+            project_task("alpha", "Tidy up", "done"),
+        ];
+
+        results.sort_by(|a, b| compare(a, b, "priority", &[]));
+        let subjects: Vec<&str> = results.iter().map(|r| r.task.subject.as_str()).collect();
+        assert_eq!(subjects, vec!["Fix crash", "Ship it", "Tidy up"]);
+
+        results.sort_by(|a, b| compare(a, b, "column", &[]));
+        assert_eq!(results[0].task.column, "backlog");
+        assert_eq!(results[2].task.column, "done");
+
+        results.sort_by(|a, b| compare(a, b, "project", &[]));
+        assert_eq!(results[2].project_name, "beta");
+
+        // Columns rank by the task's own board
+        let bugs = BoardConfig {
+            file: "BUGS.md".to_string(),
+            layout: "tasks_md".to_string(),
+            name: None,
+            columns: vec![
+                boards::ColumnConfig {
+                    id: "triage".to_string(),
+                    heading: "Triage".to_string(),
+                },
+                boards::ColumnConfig {
+                    id: "done".to_string(),
+                    heading: "Fixed".to_string(),
+                },
+            ],
+        };
+        let mut triage = project_task("alpha", "Flaky test", "triage");
+        triage.board = bugs.file.clone();
+        let mut fixed = project_task("alpha", "Crash", "done");
+        fixed.board = bugs.file.clone();
+        let queried = [("alpha".to_string(), bugs)];
+        let mut bug_results = [fixed, triage];
+        bug_results.sort_by(|a, b| compare(a, b, "column", &queried));
+        assert_eq!(bug_results[0].task.column, "triage");
+
+        // Descending keeps ties in their original order
+        results.sort_by(|a, b| compare(b, a, "project", &[]));
+        let subjects: Vec<&str> = results.iter().map(|r| r.task.subject.as_str()).collect();
+        assert_eq!(subjects, vec!["Ship it", "Fix crash", "Tidy up"]);
+    }
+
+    #[test]
+    fn test_compare_estimates() {
+        let mut results = [
+            project_task("alpha", "Someday", "backlog"),
+            project_task("alpha", "Rewrite ~1d", "backlog"),
+            project_task("alpha", "Typo ~15m", "backlog"),
+            project_task("alpha", "Migrate ~1.5h", "backlog"),
+        ];
+
+        results.sort_by(|a, b| compare(a, b, "estimate", &[]));
+        let subjects: Vec<&str> = results.iter().map(|r| r.task.subject.as_str()).collect();
+        assert_eq!(subjects, vec!["Typo", "Migrate", "Rewrite", "Someday"]);
+    }
+
+    #[test]
+    fn test_filter_by_column_text_and_completed() {
+        let description = Some("Needs benchmarks".to_string());
+        let task = TasksMdTask::new("Review the Parser changes", description, "review");
+
+        let filter = TasksMdFilter {
+            column: Some("review".to_string()),
+            text: Some("BENCHMARK".to_string()),
+            completed: Some(false),
+            ..Default::default()
+        };
+        assert!(filter.matches(&task));
+
+        let filter = TasksMdFilter {
+            completed: Some(true),
+            ..Default::default()
+        };
+        assert!(!filter.matches(&task));
+    }
+}
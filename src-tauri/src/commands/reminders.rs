@@ -0,0 +1,204 @@
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+use std::thread;
+use std::time::Duration;
+use tauri::{AppHandle, Manager};
+use tauri_plugin_notification::NotificationExt;
+
+use super::boards::{self, BoardConfig};
+use super::history::{self, DAY_MS};
+use super::merge::{self, task_key};
+use super::settings;
+use super::tasks::{self, TasksMdTask};
+use super::templates::{local_offset_ms, parse_date};
+
+/// How often due dates are checked
+const REMINDER_INTERVAL: Duration = Duration::from_secs(10 * 60);
+
+/// A notification owed for a task: due today, or past its due date
+#[derive(Debug, Clone, PartialEq)]
+pub struct Reminder {
+    pub subject: String,
+    pub due: String,
+    /// "due" or "overdue"
+    pub kind: &'static str,
+    /// Identifies the reminder so it is only sent once
+    pub key: String,
+}
+
+/// Reminders already sent for a board's tasks
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct SentReminders {
+    keys: Vec<String>,
+}
+
+/// Whole days from `now` until a YYYY-MM-DD due date by the local calendar; negative once it
+/// has passed
+pub(crate) fn days_until(due: &str, now: u64) -> Option<i64> {
+    days_until_at_offset(due, now, local_offset_ms(now))
+}
+
+fn days_until_at_offset(due: &str, now: u64, offset_ms: i64) -> Option<i64> {
+    let due_day = (parse_date(due)? / DAY_MS) as i64;
+    let today = (now as i64 + offset_ms).div_euclid(DAY_MS as i64);
+    Some(due_day - today)
+}
+
+/// Open tasks that are due today or overdue
+pub fn due_reminders(tasks: &[TasksMdTask], now: u64) -> Vec<Reminder> {
+    due_reminders_at_offset(tasks, now, local_offset_ms(now))
+}
+
+fn due_reminders_at_offset(tasks: &[TasksMdTask], now: u64, offset_ms: i64) -> Vec<Reminder> {
+    tasks
+        .iter()
+        .filter(|t| !t.completed && t.column != "done")
+        .filter_map(|task| {
+            let due = task.due.as_deref()?;
+            let kind = match days_until_at_offset(due, now, offset_ms)? {
+                0 => "due",
+                days if days < 0 => "overdue",
+                _ => return None,
+            };
+
+            Some(Reminder {
+                subject: task.subject.clone(),
+                due: due.to_string(),
+                kind,
+                key: format!("{}\u{1f}{}\u{1f}{}", task_key(task), due, kind),
+            })
+        })
+        .collect()
+}
+
+/// Keyed by the board's history key, so TASKS.md keeps the store it had before boards
+fn store_path(board_key: &str) -> PathBuf {
+    crate::state::config_dir()
+        .join("reminders")
+        .join(format!("{}.json", merge::content_hash(board_key)))
+}
+
+fn load_store(board_key: &str) -> Result<SentReminders, String> {
+    let path = store_path(board_key);
+
+    if !path.exists() {
+        return Ok(SentReminders::default());
+    }
+
+    let content = fs::read_to_string(&path)
+        .map_err(|e| format!("Failed to read sent reminders: {}", e))?;
+
+    serde_json::from_str(&content).map_err(|e| format!("Failed to parse sent reminders: {}", e))
+}
+
+fn save_store(board_key: &str, store: &SentReminders) -> Result<(), String> {
+    let path = store_path(board_key);
+
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create reminders directory: {}", e))?;
+    }
+
+    let content = serde_json::to_string(store)
+        .map_err(|e| format!("Failed to serialize sent reminders: {}", e))?;
+
+    fs::write(&path, content).map_err(|e| format!("Failed to write sent reminders: {}", e))
+}
+
+/// Reminders for a board that haven't been sent yet. Remembers them as sent, and forgets
+/// reminders for tasks that are no longer due, so a new due date notifies again.
+fn pending_reminders(project_path: &str, board: &BoardConfig, now: u64) -> Result<Vec<Reminder>, String> {
+    let reminders = due_reminders(&tasks::load_board(project_path, board)?, now);
+    let store_key = board.history_key(project_path);
+    let sent = load_store(&store_key)?;
+
+    let pending: Vec<Reminder> = reminders
+        .iter()
+        .filter(|r| !sent.keys.contains(&r.key))
+        .cloned()
+        .collect();
+
+    let keys: Vec<String> = reminders.into_iter().map(|r| r.key).collect();
+    if keys != sent.keys {
+        save_store(&store_key, &SentReminders { keys })?;
+    }
+
+    Ok(pending)
+}
+
+fn notify(app: &AppHandle, project_name: &str, reminder: &Reminder) {
+    let title = match reminder.kind {
+        "due" => "Task due today",
+        _ => "Task overdue",
+    };
+    let body = format!("{}: {} (due {})", project_name, reminder.subject, reminder.due);
+
+    if let Err(e) = app.notification().builder().title(title).body(body).show() {
+        eprintln!("Failed to show reminder notification: {}", e);
+    }
+}
+
+/// Check every board of every project for due and overdue tasks and show a desktop notification
+/// for each, once per task and due date
+pub fn start_reminder_scheduler(app: AppHandle) {
+    thread::spawn(move || loop {
+        let enabled = settings::get_settings().map(|s| s.due_reminders).unwrap_or(true);
+
+        if enabled {
+            let state = app.state::<crate::state::AppState>();
+            let projects: Vec<(String, String)> = state
+                .projects
+                .lock()
+                .map(|projects| projects.iter().map(|p| (p.name.clone(), p.path.clone())).collect())
+                .unwrap_or_default();
+
+            for (project_name, project_path) in projects {
+                for board in boards::boards_for_path(&state, &project_path) {
+                    if !board.path(&project_path).exists() {
+                        continue;
+                    }
+                    match pending_reminders(&project_path, &board, history::now_ms()) {
+                        Ok(reminders) => {
+                            for reminder in &reminders {
+                                notify(&app, &project_name, reminder);
+                            }
+                        }
+                        Err(e) => eprintln!("Failed to check due tasks in {}: {}", board.file, e),
+                    }
+                }
+            }
+        }
+
+        thread::sleep(REMINDER_INTERVAL);
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_due_reminders() {
+        let content = "# TASKS\n\n## Backlog\n- [ ] Cut release due:2024-01-01\n- [ ] Write notes due:2023-12-30\n- [ ] Plan next due:2024-01-05\n- [ ] Someday\n\n## Done\n- [x] Freeze due:2023-12-01\n";
+        let tasks = tasks::parse_tasks_md(content).unwrap();
+        assert_eq!(tasks[0].due.as_deref(), Some("2024-01-01"));
+        assert_eq!(tasks[0].subject, "Cut release");
+
+        let now = parse_date("2024-01-01").unwrap() + 12 * 3_600_000;
+        let reminders = due_reminders_at_offset(&tasks, now, 0);
+        let kinds: Vec<(&str, &str)> = reminders.iter().map(|r| (r.subject.as_str(), r.kind)).collect();
+        assert_eq!(kinds, vec![("Cut release", "due"), ("Write notes", "overdue")]);
+
+        assert_eq!(days_until_at_offset("2024-01-05", now, 0), Some(4));
+        assert_eq!(days_until_at_offset("not a date", now, 0), None);
+
+        // 20:00 UTC on the due date is still that day at UTC-8, and the next day at UTC+8
+        let evening = parse_date("2024-01-01").unwrap() + 20 * 3_600_000;
+        let hour: i64 = 3_600_000;
+        let reminders = due_reminders_at_offset(&tasks, evening, 8 * hour);
+        assert!(reminders.iter().all(|r| r.kind == "overdue"));
+        assert_eq!(days_until_at_offset("2024-01-01", evening, -8 * hour), Some(0));
+        assert_eq!(days_until_at_offset("2024-01-01", evening, 8 * hour), Some(-1));
+    }
+}
@@ -0,0 +1,55 @@
+use std::thread;
+use std::time::Duration;
+use tauri::{AppHandle, Manager};
+
+use super::archive::{self, ArchivePolicy};
+use super::boards;
+use super::commits;
+use super::history;
+use super::merge;
+use super::templates;
+
+/// How often the scheduler looks for due recurrences, new commits and tasks to archive
+const SCHEDULER_INTERVAL: Duration = Duration::from_secs(15 * 60);
+
+/// Check every project for due recurrences, commits that advance cards and archivable Done
+/// tasks now and then every few minutes
+pub fn start_scheduler(app: AppHandle) {
+    thread::spawn(move || loop {
+        let state = app.state::<crate::state::AppState>();
+        let versions = app.state::<merge::TasksMdVersions>();
+        let projects: Vec<(String, Option<ArchivePolicy>)> = state
+            .projects
+            .lock()
+            .map(|projects| {
+                projects
+                    .iter()
+                    .map(|p| (p.path.clone(), p.archive_policy.clone()))
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        for (project_path, archive_policy) in projects {
+            if let Err(e) = templates::apply_due_recurrences(&state, &versions, &project_path, history::now_ms()) {
+                eprintln!("Failed to apply recurring tasks for {}: {}", project_path, e);
+            }
+
+            for board in boards::boards_for_path(&state, &project_path) {
+                if !board.path(&project_path).exists() {
+                    continue;
+                }
+                if let Err(e) = commits::advance_tasks_from_commits(&project_path, &board, &versions) {
+                    eprintln!("Failed to advance tasks from commits for {}: {}", board.file, e);
+                }
+                if let Some(policy) = &archive_policy {
+                    let now = history::now_ms();
+                    if let Err(e) = archive::archive_done_tasks(&project_path, &board, policy, now, &versions) {
+                        eprintln!("Failed to archive tasks for {}: {}", board.file, e);
+                    }
+                }
+            }
+        }
+
+        thread::sleep(SCHEDULER_INTERVAL);
+    });
+}
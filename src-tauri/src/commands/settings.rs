@@ -2,6 +2,8 @@ use serde::{Deserialize, Serialize};
 use std::fs;
 use std::path::PathBuf;
 
+use super::boards;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Settings {
     #[serde(rename = "autoStartClaude", default)]
@@ -18,6 +20,17 @@ pub struct Settings {
     pub notification_sound: String,
     #[serde(rename = "notifyOnlyWhenUnfocused", default = "default_true")]
     pub notify_only_when_unfocused: bool,
+    #[serde(rename = "autoBlockTasks", default)]
+    pub auto_block_tasks: bool,
+    #[serde(rename = "dispatchPromptTemplate", default = "default_dispatch_prompt_template")]
+    pub dispatch_prompt_template: String,
+    /// Column id (e.g. "review" or "done") that cards move to when a commit referencing them
+    /// lands on the default branch; off when unset. Boards without the column are left alone.
+    #[serde(rename = "commitAdvanceColumn", default)]
+    pub commit_advance_column: Option<String>,
+    /// Desktop notifications for tasks whose `due:` date is today or has passed
+    #[serde(rename = "dueReminders", default = "default_true")]
+    pub due_reminders: bool,
 }
 
 fn default_auto_start_command() -> String {
@@ -28,6 +41,11 @@ fn default_zoom_level() -> f64 {
     1.0
 }
 
+/// Prompt sent to the agent when a board task is dispatched; see `dispatch::render_prompt`
+pub fn default_dispatch_prompt_template() -> String {
+    "Work on this task from TASKS.md: {subject}\n\n{description}\n\n{subtasks}".to_string()
+}
+
 fn default_true() -> bool {
     true
 }
@@ -46,6 +64,10 @@ impl Default for Settings {
             enable_sound: true,
             notification_sound: default_sound(),
             notify_only_when_unfocused: true,
+            auto_block_tasks: false,
+            dispatch_prompt_template: default_dispatch_prompt_template(),
+            commit_advance_column: None,
+            due_reminders: true,
         }
     }
 }
@@ -77,8 +99,30 @@ pub fn get_settings() -> Result<Settings, String> {
     serde_json::from_str(&content).map_err(|e| format!("Failed to parse settings: {}", e))
 }
 
+/// A commit-advance column must exist on the standard board or on a registered one
+fn validate_settings(settings: &Settings) -> Result<(), String> {
+    let Some(column) = &settings.commit_advance_column else {
+        return Ok(());
+    };
+
+    let projects = crate::state::load_projects().unwrap_or_default();
+    let known = projects
+        .iter()
+        .map(Some)
+        .chain([None])
+        .flat_map(boards::project_boards)
+        .any(|board| board.check_column(column).is_ok());
+
+    if known {
+        Ok(())
+    } else {
+        Err(format!("'{}' is not a column of any board", column))
+    }
+}
+
 #[tauri::command]
 pub fn save_settings(settings: Settings) -> Result<(), String> {
+    validate_settings(&settings)?;
     let settings_path = get_settings_path()?;
 
     let content = serde_json::to_string_pretty(&settings)
@@ -0,0 +1,568 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+
+use super::boards::{self, BoardConfig};
+use super::claude_tasks;
+use super::merge;
+use super::tasks::{self, ClaudeTask, TasksMdTask};
+
+/// A TASKS.md card paired with a Claude Code task, and the status both agreed on at the last sync
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct SyncLink {
+    #[serde(rename = "claudeTaskId")]
+    claude_task_id: String,
+    /// Stable id of the card if it has one, otherwise its subject
+    #[serde(rename = "cardKey")]
+    card_key: String,
+    /// Empty when the pair has never agreed, so any difference is a conflict
+    #[serde(rename = "lastStatus")]
+    last_status: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct SyncState {
+    /// Board file the links point into; TASKS.md when unset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    board: Option<String>,
+    links: Vec<SyncLink>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SyncConflict {
+    #[serde(rename = "claudeTaskId")]
+    pub claude_task_id: String,
+    pub subject: String,
+    /// "status", "card_missing" or "claude_task_missing"
+    pub kind: String,
+    #[serde(rename = "cardColumn")]
+    pub card_column: Option<String>,
+    #[serde(rename = "claudeStatus")]
+    pub claude_status: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SyncReport {
+    #[serde(rename = "createdClaudeTasks")]
+    pub created_claude_tasks: Vec<String>,
+    #[serde(rename = "createdCards")]
+    pub created_cards: Vec<String>,
+    #[serde(rename = "updatedClaudeTasks")]
+    pub updated_claude_tasks: Vec<String>,
+    #[serde(rename = "movedCards")]
+    pub moved_cards: Vec<String>,
+    pub conflicts: Vec<SyncConflict>,
+}
+
+/// Map a board column to the Claude Code status it represents
+fn column_to_status(column: &str) -> &'static str {
+    match column {
+        "in_progress" => "in_progress",
+        "review" | "done" => "completed",
+        _ => "pending",
+    }
+}
+
+/// Map a Claude Code status to a board column, keeping the current column when it already
+/// represents that status. Completed work lands in Review for a human to check.
+fn status_to_column(status: &str, current: Option<&str>) -> String {
+    if let Some(current) = current {
+        if column_to_status(current) == status {
+            return current.to_string();
+        }
+    }
+
+    match status {
+        "in_progress" => "in_progress",
+        "completed" => "review",
+        _ => "backlog",
+    }
+    .to_string()
+}
+
+fn card_key(card: &TasksMdTask) -> String {
+    card.stable_id.clone().unwrap_or_else(|| card.subject.clone())
+}
+
+fn find_card(cards: &[TasksMdTask], key: &str, claude_subject: Option<&str>) -> Option<usize> {
+    cards
+        .iter()
+        .position(|c| c.stable_id.as_deref() == Some(key) || c.subject == key)
+        .or_else(|| claude_subject.and_then(|s| cards.iter().position(|c| c.subject == s)))
+}
+
+fn move_card(card: &mut TasksMdTask, column: String) {
+    card.completed = column == "done";
+    card.column = column;
+}
+
+fn next_claude_id(claude: &[ClaudeTask]) -> String {
+    let max = claude.iter().filter_map(|t| t.id.parse::<u64>().ok()).max().unwrap_or(0);
+    (max + 1).to_string()
+}
+
+fn claude_task_for_card(id: String, card: &TasksMdTask) -> ClaudeTask {
+    ClaudeTask {
+        id,
+        subject: card.subject.clone(),
+        status: column_to_status(&card.column).to_string(),
+        description: card.description.clone().unwrap_or_default(),
+        blocks: vec![],
+        blocked_by: vec![],
+        owner: None,
+    }
+}
+
+/// Result of reconciling both task lists in memory
+struct Reconciled {
+    report: SyncReport,
+    /// IDs of Claude tasks that were created or changed and need writing
+    changed_claude_ids: Vec<String>,
+    cards_changed: bool,
+}
+
+/// Reconcile cards and Claude tasks against the last agreed state. A side that changed since
+/// the last sync wins; when both changed to different statuses the pair is reported as a
+/// conflict and left alone.
+fn reconcile(
+    cards: &mut Vec<TasksMdTask>,
+    claude: &mut Vec<ClaudeTask>,
+    state: &mut SyncState,
+) -> Reconciled {
+    let mut report = SyncReport::default();
+    let mut changed_claude_ids = Vec::new();
+    let mut cards_changed = false;
+    let mut linked_cards = vec![false; cards.len()];
+    let mut dropped_links = Vec::new();
+
+    for link in state.links.iter_mut() {
+        let claude_index = claude.iter().position(|t| t.id == link.claude_task_id);
+        let claude_subject = claude_index.map(|i| claude[i].subject.as_str());
+        let card_index = find_card(cards, &link.card_key, claude_subject);
+
+        match (card_index, claude_index) {
+            (Some(ci), Some(ti)) => {
+                linked_cards[ci] = true;
+                link.card_key = card_key(&cards[ci]);
+
+                let card_status = column_to_status(&cards[ci].column);
+                let claude_status = claude[ti].status.clone();
+                let card_changed = card_status != link.last_status;
+                let claude_changed = claude_status != link.last_status;
+
+                if card_status == claude_status {
+                    link.last_status = claude_status;
+                } else if card_changed && !claude_changed {
+                    claude[ti].status = card_status.to_string();
+                    changed_claude_ids.push(claude[ti].id.clone());
+                    report.updated_claude_tasks.push(claude[ti].subject.clone());
+                    link.last_status = card_status.to_string();
+                } else if claude_changed && !card_changed {
+                    let column = status_to_column(&claude_status, Some(&cards[ci].column));
+                    move_card(&mut cards[ci], column);
+                    cards_changed = true;
+                    report.moved_cards.push(cards[ci].subject.clone());
+                    link.last_status = claude_status;
+                } else {
+                    report.conflicts.push(SyncConflict {
+                        claude_task_id: link.claude_task_id.clone(),
+                        subject: cards[ci].subject.clone(),
+                        kind: "status".to_string(),
+                        card_column: Some(cards[ci].column.clone()),
+                        claude_status: Some(claude_status),
+                    });
+                }
+            }
+            (None, Some(ti)) => report.conflicts.push(SyncConflict {
+                claude_task_id: link.claude_task_id.clone(),
+                subject: claude[ti].subject.clone(),
+                kind: "card_missing".to_string(),
+                card_column: None,
+                claude_status: Some(claude[ti].status.clone()),
+            }),
+            (Some(ci), None) => {
+                linked_cards[ci] = true;
+                report.conflicts.push(SyncConflict {
+                    claude_task_id: link.claude_task_id.clone(),
+                    subject: cards[ci].subject.clone(),
+                    kind: "claude_task_missing".to_string(),
+                    card_column: Some(cards[ci].column.clone()),
+                    claude_status: None,
+                });
+            }
+            // Both sides are gone, so there is nothing left to sync
+            (None, None) => dropped_links.push(link.claude_task_id.clone()),
+        }
+    }
+
+    state.links.retain(|l| !dropped_links.contains(&l.claude_task_id));
+
+    // Claude tasks nobody has linked yet: pair with a card of the same subject, or add a card
+    let unlinked: Vec<usize> = (0..claude.len())
+        .filter(|&i| !state.links.iter().any(|l| l.claude_task_id == claude[i].id))
+        .collect();
+
+    for ti in unlinked {
+        let existing = (0..cards.len())
+            .find(|&ci| !linked_cards[ci] && cards[ci].subject == claude[ti].subject);
+
+        let (ci, last_status) = match existing {
+            Some(ci) => {
+                let card_status = column_to_status(&cards[ci].column);
+                if card_status != claude[ti].status {
+                    report.conflicts.push(SyncConflict {
+                        claude_task_id: claude[ti].id.clone(),
+                        subject: cards[ci].subject.clone(),
+                        kind: "status".to_string(),
+                        card_column: Some(cards[ci].column.clone()),
+                        claude_status: Some(claude[ti].status.clone()),
+                    });
+                    (ci, String::new())
+                } else {
+                    (ci, card_status.to_string())
+                }
+            }
+            None => {
+                let description = Some(claude[ti].description.clone()).filter(|d| !d.is_empty());
+                let column = status_to_column(&claude[ti].status, None);
+                cards.push(TasksMdTask::new(&claude[ti].subject, description, &column));
+                linked_cards.push(false);
+                cards_changed = true;
+                report.created_cards.push(claude[ti].subject.clone());
+                (cards.len() - 1, claude[ti].status.clone())
+            }
+        };
+
+        linked_cards[ci] = true;
+        state.links.push(SyncLink {
+            claude_task_id: claude[ti].id.clone(),
+            card_key: card_key(&cards[ci]),
+            last_status,
+        });
+    }
+
+    // Unfinished cards nobody has linked yet get a new Claude task
+    for ci in 0..cards.len() {
+        if linked_cards[ci] || cards[ci].column == "done" {
+            continue;
+        }
+
+        let task = claude_task_for_card(next_claude_id(claude), &cards[ci]);
+        state.links.push(SyncLink {
+            claude_task_id: task.id.clone(),
+            card_key: card_key(&cards[ci]),
+            last_status: task.status.clone(),
+        });
+        changed_claude_ids.push(task.id.clone());
+        report.created_claude_tasks.push(task.subject.clone());
+        claude.push(task);
+    }
+
+    Reconciled {
+        report,
+        changed_claude_ids,
+        cards_changed,
+    }
+}
+
+fn sync_state_path(project_id: &str) -> PathBuf {
+    crate::state::config_dir().join("sync").join(format!("{}.json", project_id))
+}
+
+fn load_sync_state(project_id: &str) -> Result<SyncState, String> {
+    let path = sync_state_path(project_id);
+
+    if !path.exists() {
+        return Ok(SyncState::default());
+    }
+
+    let content = fs::read_to_string(&path)
+        .map_err(|e| format!("Failed to read sync state: {}", e))?;
+
+    serde_json::from_str(&content).map_err(|e| format!("Failed to parse sync state: {}", e))
+}
+
+fn save_sync_state(project_id: &str, state: &SyncState) -> Result<(), String> {
+    let path = sync_state_path(project_id);
+
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create sync directory: {}", e))?;
+    }
+
+    let content = serde_json::to_string_pretty(state)
+        .map_err(|e| format!("Failed to serialize sync state: {}", e))?;
+
+    fs::write(&path, content).map_err(|e| format!("Failed to write sync state: {}", e))
+}
+
+/// Point sync links at the new ids after a task list was renumbered. The task list id is the
+/// project id.
+pub(crate) fn remap_claude_task_ids(project_id: &str, new_ids: &HashMap<String, String>) -> Result<(), String> {
+    if !sync_state_path(project_id).exists() {
+        return Ok(());
+    }
+
+    let mut state = load_sync_state(project_id)?;
+    for link in &mut state.links {
+        if let Some(new_id) = new_ids.get(&link.claude_task_id) {
+            link.claude_task_id = new_id.clone();
+        }
+    }
+
+    save_sync_state(project_id, &state)
+}
+
+/// The sync state of a project's task list, refused when its links point into another board
+fn load_board_sync_state(project_id: &str, board: &BoardConfig) -> Result<SyncState, String> {
+    let mut state = load_sync_state(project_id)?;
+    let file = (!board.is_default()).then(|| board.file.clone());
+
+    if state.board != file && !state.links.is_empty() {
+        let synced = state.board.as_deref().unwrap_or(boards::DEFAULT_BOARD);
+        return Err(format!("This task list is synced with {}, not {}", synced, board.file));
+    }
+    state.board = file;
+    Ok(state)
+}
+
+/// A board's cards and the version they were read at; a missing file has none
+fn load_cards(
+    project_path: &str,
+    board: &BoardConfig,
+    versions: &merge::TasksMdVersions,
+) -> Result<(String, Vec<TasksMdTask>), String> {
+    let path = board.path(project_path);
+    let content = if path.exists() {
+        fs::read_to_string(&path).map_err(|e| format!("Failed to read {}: {}", board.file, e))?
+    } else {
+        String::new()
+    };
+
+    Ok((versions.remember(&content), tasks::parse_board(&content, board)?))
+}
+
+/// Write synced cards over the version they were read at, merging edits made in the meantime
+fn save_cards(
+    project_path: &str,
+    board: &BoardConfig,
+    version: String,
+    cards: Vec<TasksMdTask>,
+    versions: &merge::TasksMdVersions,
+) -> Result<(), String> {
+    let result = tasks::mutate_board(project_path, board, Some(version), versions, false, |tasks| {
+        *tasks = cards;
+        Ok(())
+    })?;
+
+    if !result.conflicts.is_empty() {
+        return Err(format!("{} changed during the sync; sync again", board.file));
+    }
+    Ok(())
+}
+
+/// Sync a board (TASKS.md unless `board` is given) with the project's Claude Code task list
+/// (~/.claude/tasks/<project_id>/). Refused while an agent holds the task list's `.lock` file.
+#[tauri::command]
+pub fn sync_claude_tasks(
+    project_id: String,
+    project_path: String,
+    board: Option<String>,
+    state: tauri::State<'_, crate::state::AppState>,
+    versions: tauri::State<'_, merge::TasksMdVersions>,
+) -> Result<SyncReport, String> {
+    let board = boards::board_config(&state, &project_path, board.as_deref())?;
+    let tasks_dir = claude_tasks::unlocked_tasks_dir(&project_id)?;
+    let (version, mut cards) = load_cards(&project_path, &board, &versions)?;
+    let mut claude = tasks::read_claude_tasks(&tasks_dir);
+    let mut state = load_board_sync_state(&project_id, &board)?;
+
+    let reconciled = reconcile(&mut cards, &mut claude, &mut state);
+
+    if reconciled.cards_changed {
+        save_cards(&project_path, &board, version, cards, &versions)?;
+    }
+
+    for task in claude.iter().filter(|t| reconciled.changed_claude_ids.contains(&t.id)) {
+        claude_tasks::write_claude_task(&tasks_dir, task)?;
+    }
+
+    save_sync_state(&project_id, &state)?;
+
+    Ok(reconciled.report)
+}
+
+/// Resolve a sync conflict on a board (TASKS.md unless `board` is given) by keeping one side
+/// ("board" or "claude") and applying it to the other
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+pub fn resolve_sync_conflict(
+    project_id: String,
+    project_path: String,
+    board: Option<String>,
+    claude_task_id: String,
+    keep: String,
+    state: tauri::State<'_, crate::state::AppState>,
+    versions: tauri::State<'_, merge::TasksMdVersions>,
+) -> Result<(), String> {
+    if keep != "board" && keep != "claude" {
+        return Err(format!("Unknown side '{}', expected 'board' or 'claude'", keep));
+    }
+
+    let board = boards::board_config(&state, &project_path, board.as_deref())?;
+    let tasks_dir = claude_tasks::unlocked_tasks_dir(&project_id)?;
+    let (version, mut cards) = load_cards(&project_path, &board, &versions)?;
+    let mut claude = tasks::read_claude_tasks(&tasks_dir);
+    let mut state = load_board_sync_state(&project_id, &board)?;
+
+    let link_index = state
+        .links
+        .iter()
+        .position(|l| l.claude_task_id == claude_task_id)
+        .ok_or_else(|| format!("No sync link for Claude task '{}'", claude_task_id))?;
+
+    let claude_index = claude.iter().position(|t| t.id == claude_task_id);
+    let claude_subject = claude_index.map(|i| claude[i].subject.clone());
+    let card_index = find_card(&cards, &state.links[link_index].card_key, claude_subject.as_deref());
+    let mut cards_changed = false;
+
+    match (card_index, claude_index, keep.as_str()) {
+        (Some(ci), Some(ti), "board") => {
+            claude[ti].status = column_to_status(&cards[ci].column).to_string();
+            claude_tasks::write_claude_task(&tasks_dir, &claude[ti])?;
+            state.links[link_index].last_status = claude[ti].status.clone();
+        }
+        (Some(ci), Some(ti), _) => {
+            let column = status_to_column(&claude[ti].status, Some(&cards[ci].column));
+            move_card(&mut cards[ci], column);
+            cards_changed = true;
+            state.links[link_index].last_status = claude[ti].status.clone();
+        }
+        (None, Some(_), "board") => {
+            // The card was deleted on the board, so the Claude task goes too
+            let task_file = tasks_dir.join(format!("{}.json", claude_task_id));
+            fs::remove_file(&task_file)
+                .map_err(|e| format!("Failed to delete task file: {}", e))?;
+            state.links.remove(link_index);
+        }
+        (None, Some(ti), _) => {
+            let task = &claude[ti];
+            let description = Some(task.description.clone()).filter(|d| !d.is_empty());
+            let column = status_to_column(&task.status, None);
+            let card = TasksMdTask::new(&task.subject, description, &column);
+            state.links[link_index].card_key = card_key(&card);
+            state.links[link_index].last_status = task.status.clone();
+            cards.push(card);
+            cards_changed = true;
+        }
+        (Some(ci), None, "board") => {
+            let task = claude_task_for_card(next_claude_id(&claude), &cards[ci]);
+            claude_tasks::write_claude_task(&tasks_dir, &task)?;
+            state.links[link_index].claude_task_id = task.id.clone();
+            state.links[link_index].last_status = task.status;
+        }
+        (Some(ci), None, _) => {
+            // The Claude task was deleted, so the card goes too
+            cards.remove(ci);
+            cards_changed = true;
+            state.links.remove(link_index);
+        }
+        (None, None, _) => {
+            state.links.remove(link_index);
+        }
+    }
+
+    if cards_changed {
+        save_cards(&project_path, &board, version, cards, &versions)?;
+    }
+
+    save_sync_state(&project_id, &state)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn claude_task(id: &str, subject: &str, status: &str) -> ClaudeTask {
+        ClaudeTask {
+            id: id.to_string(),
+            subject: subject.to_string(),
+            status: status.to_string(),
+            description: String::new(),
+            blocks: vec![],
+            blocked_by: vec![],
+            owner: None,
+        }
+    }
+
+    fn link(claude_task_id: &str, card_key: &str, last_status: &str) -> SyncLink {
+        SyncLink {
+            claude_task_id: claude_task_id.to_string(),
+            card_key: card_key.to_string(),
+            last_status: last_status.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_reconcile_creates_both_sides() {
+        let mut cards = vec![
+            TasksMdTask::new("Write docs", None, "backlog"),
+            TasksMdTask::new("Old work", None, "done"),
+        ];
+        let mut claude = vec![claude_task("1", "Refactor parser", "in_progress")];
+        let mut state = SyncState::default();
+
+        let result = reconcile(&mut cards, &mut claude, &mut state);
+
+        assert_eq!(result.report.created_cards, vec!["Refactor parser"]);
+        assert_eq!(cards[2].column, "in_progress");
+        assert_eq!(result.report.created_claude_tasks, vec!["Write docs"]);
+        assert_eq!(claude[1].id, "2");
+        assert_eq!(claude[1].status, "pending");
+        assert_eq!(result.changed_claude_ids, vec!["2"]);
+        assert_eq!(state.links.len(), 2);
+    }
+
+    #[test]
+    fn test_reconcile_propagates_one_sided_changes() {
+        let mut cards = vec![
+            TasksMdTask::new("Agent step", None, "in_progress"),
+            TasksMdTask::new("Human step", None, "in_progress"),
+        ];
+        let mut claude = vec![
+            claude_task("1", "Agent step", "completed"),
+            claude_task("2", "Human step", "pending"),
+        ];
+        let mut state = SyncState {
+            board: None,
+            links: vec![link("1", "Agent step", "in_progress"), link("2", "Human step", "pending")],
+        };
+
+        let result = reconcile(&mut cards, &mut claude, &mut state);
+
+        assert_eq!(cards[0].column, "review");
+        assert_eq!(claude[1].status, "in_progress");
+        assert_eq!(result.changed_claude_ids, vec!["2"]);
+        assert!(result.cards_changed);
+        assert!(result.report.conflicts.is_empty());
+    }
+
+    #[test]
+    fn test_reconcile_reports_conflicts() {
+        let mut cards = vec![TasksMdTask::new("Shared step", None, "done")];
+        let mut claude = vec![claude_task("1", "Shared step", "in_progress")];
+        let mut state = SyncState {
+            board: None,
+            links: vec![link("1", "Shared step", "pending")],
+        };
+
+        let result = reconcile(&mut cards, &mut claude, &mut state);
+
+        assert_eq!(result.report.conflicts.len(), 1);
+        assert_eq!(result.report.conflicts[0].kind, "status");
+        assert_eq!(cards[0].column, "done");
+        assert_eq!(claude[0].status, "in_progress");
+        assert_eq!(state.links[0].last_status, "pending");
+    }
+}
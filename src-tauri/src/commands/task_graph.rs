@@ -0,0 +1,342 @@
+use serde::{Deserialize, Serialize};
+
+use super::dependencies;
+use super::tasks::{self, ClaudeTask};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TaskGraphNode {
+    pub id: String,
+    pub subject: String,
+    pub status: String,
+}
+
+/// `from` has to finish before `to` can start
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TaskGraphEdge {
+    pub from: String,
+    pub to: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DanglingReference {
+    #[serde(rename = "taskId")]
+    pub task_id: String,
+    pub reference: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TaskGraph {
+    pub nodes: Vec<TaskGraphNode>,
+    pub edges: Vec<TaskGraphEdge>,
+    /// Each cycle as task ids
+    pub cycles: Vec<Vec<String>>,
+    pub dangling: Vec<DanglingReference>,
+    /// Longest chain of unfinished tasks, first to last; cycles are left out
+    #[serde(rename = "criticalPath")]
+    pub critical_path: Vec<String>,
+    /// Unfinished tasks whose blockers are all completed
+    pub unblocked: Vec<String>,
+    /// The graph as DOT or Mermaid source, when one of those formats was requested
+    pub rendered: Option<String>,
+}
+
+/// Build the dependency graph from both `blockedBy` and `blocks`, which Claude Code keeps in
+/// step but an edited list might not
+pub fn build_task_graph(claude_tasks: &[ClaudeTask]) -> TaskGraph {
+    let index_of = |id: &str| claude_tasks.iter().position(|t| t.id == id);
+
+    let mut edges: Vec<(usize, usize)> = Vec::new();
+    let mut dangling = Vec::new();
+
+    for (i, task) in claude_tasks.iter().enumerate() {
+        let references = task
+            .blocked_by
+            .iter()
+            .map(|r| (r, true))
+            .chain(task.blocks.iter().map(|r| (r, false)));
+
+        for (reference, is_blocker) in references {
+            match index_of(reference) {
+                Some(j) => {
+                    let edge = if is_blocker { (j, i) } else { (i, j) };
+                    if !edges.contains(&edge) {
+                        edges.push(edge);
+                    }
+                }
+                None => dangling.push(DanglingReference {
+                    task_id: task.id.clone(),
+                    reference: reference.clone(),
+                }),
+            }
+        }
+    }
+
+    let n = claude_tasks.len();
+    let mut upstream: Vec<Vec<usize>> = vec![Vec::new(); n];
+    for &(from, to) in &edges {
+        upstream[to].push(from);
+    }
+
+    let cycle_groups = dependencies::find_graph_cycles(&upstream);
+    let in_cycle: Vec<bool> = (0..n).map(|i| cycle_groups.iter().any(|c| c.contains(&i))).collect();
+
+    let is_done = |i: usize| claude_tasks[i].status == "completed";
+
+    let unblocked = (0..n)
+        .filter(|&i| !is_done(i) && upstream[i].iter().all(|&u| is_done(u)))
+        .map(|i| claude_tasks[i].id.clone())
+        .collect();
+
+    TaskGraph {
+        nodes: claude_tasks
+            .iter()
+            .map(|t| TaskGraphNode {
+                id: t.id.clone(),
+                subject: t.subject.clone(),
+                status: t.status.clone(),
+            })
+            .collect(),
+        edges: edges
+            .iter()
+            .map(|&(from, to)| TaskGraphEdge {
+                from: claude_tasks[from].id.clone(),
+                to: claude_tasks[to].id.clone(),
+            })
+            .collect(),
+        cycles: cycle_groups
+            .iter()
+            .map(|c| c.iter().map(|&i| claude_tasks[i].id.clone()).collect())
+            .collect(),
+        dangling,
+        critical_path: critical_path(&upstream, &in_cycle, |i| !is_done(i))
+            .into_iter()
+            .map(|i| claude_tasks[i].id.clone())
+            .collect(),
+        unblocked,
+        rendered: None,
+    }
+}
+
+/// Longest path counting only unfinished tasks, over the acyclic part of the graph
+fn critical_path<F>(upstream: &[Vec<usize>], in_cycle: &[bool], unfinished: F) -> Vec<usize>
+where
+    F: Fn(usize) -> bool,
+{
+    fn visit<F: Fn(usize) -> bool>(
+        i: usize,
+        upstream: &[Vec<usize>],
+        in_cycle: &[bool],
+        unfinished: &F,
+        memo: &mut Vec<Option<(usize, Option<usize>)>>,
+    ) -> usize {
+        if let Some((length, _)) = memo[i] {
+            return length;
+        }
+
+        let mut best: (usize, Option<usize>) = (0, None);
+        for &u in &upstream[i] {
+            if in_cycle[u] {
+                continue;
+            }
+            let length = visit(u, upstream, in_cycle, unfinished, memo);
+            if length > best.0 {
+                best = (length, Some(u));
+            }
+        }
+
+        let length = best.0 + usize::from(unfinished(i));
+        memo[i] = Some((length, best.1));
+        length
+    }
+
+    let n = upstream.len();
+    let mut memo = vec![None; n];
+    let mut end: Option<(usize, usize)> = None;
+
+    for i in (0..n).filter(|&i| !in_cycle[i]) {
+        let length = visit(i, upstream, in_cycle, &unfinished, &mut memo);
+        if length > end.map(|(l, _)| l).unwrap_or(0) {
+            end = Some((length, i));
+        }
+    }
+
+    let mut path = Vec::new();
+    let mut current = end.map(|(_, i)| i);
+    while let Some(i) = current {
+        if unfinished(i) {
+            path.push(i);
+        }
+        current = memo[i].and_then(|(_, previous)| previous);
+    }
+    path.reverse();
+    path
+}
+
+fn escape_label(text: &str) -> String {
+    text.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+fn on_critical_path(graph: &TaskGraph, edge: &TaskGraphEdge) -> bool {
+    graph
+        .critical_path
+        .windows(2)
+        .any(|w| w[0] == edge.from && w[1] == edge.to)
+}
+
+pub fn render_dot(graph: &TaskGraph) -> String {
+    let mut out = String::from("digraph tasks {\n  rankdir=LR;\n  node [shape=box, style=rounded];\n");
+
+    for node in &graph.nodes {
+        let color = match node.status.as_str() {
+            "completed" => "palegreen",
+            "in_progress" => "lightskyblue",
+            _ => "white",
+        };
+        out.push_str(&format!(
+            "  \"{}\" [label=\"{}. {}\", style=\"rounded,filled\", fillcolor={}];\n",
+            escape_label(&node.id),
+            escape_label(&node.id),
+            escape_label(&node.subject),
+            color
+        ));
+    }
+
+    for edge in &graph.edges {
+        let attributes = if on_critical_path(graph, edge) {
+            " [color=red, penwidth=2]"
+        } else {
+            ""
+        };
+        out.push_str(&format!(
+            "  \"{}\" -> \"{}\"{};\n",
+            escape_label(&edge.from),
+            escape_label(&edge.to),
+            attributes
+        ));
+    }
+
+    out.push_str("}\n");
+    out
+}
+
+pub fn render_mermaid(graph: &TaskGraph) -> String {
+    // Mermaid node ids must be plain identifiers
+    let node_id = |id: &str| format!("t{}", id.replace(|c: char| !c.is_alphanumeric(), "_"));
+
+    let mut out = String::from("graph LR\n");
+
+    for node in &graph.nodes {
+        out.push_str(&format!(
+            "  {}[\"{}. {}\"]\n",
+            node_id(&node.id),
+            node.id,
+            node.subject.replace('"', "#quot;")
+        ));
+    }
+
+    let mut critical_links = Vec::new();
+    for (i, edge) in graph.edges.iter().enumerate() {
+        out.push_str(&format!("  {} --> {}\n", node_id(&edge.from), node_id(&edge.to)));
+        if on_critical_path(graph, edge) {
+            critical_links.push(i.to_string());
+        }
+    }
+
+    out.push_str("  classDef completed fill:#c8f7c5\n  classDef in_progress fill:#bde0fe\n");
+    for status in ["completed", "in_progress"] {
+        let ids: Vec<String> = graph
+            .nodes
+            .iter()
+            .filter(|n| n.status == status)
+            .map(|n| node_id(&n.id))
+            .collect();
+        if !ids.is_empty() {
+            out.push_str(&format!("  class {} {}\n", ids.join(","), status));
+        }
+    }
+    if !critical_links.is_empty() {
+        out.push_str(&format!(
+            "  linkStyle {} stroke:red,stroke-width:2px\n",
+            critical_links.join(",")
+        ));
+    }
+
+    out
+}
+
+/// Dependency graph of a Claude Code task list, with cycles, dangling references, the critical
+/// path and the tasks ready to start. `format` is "dot", "mermaid" or "json" (no rendering).
+#[tauri::command]
+pub fn get_task_graph(task_list_id: String, format: Option<String>) -> Result<TaskGraph, String> {
+    let tasks_dir = tasks::claude_tasks_dir(&task_list_id)?;
+    let mut graph = build_task_graph(&tasks::read_claude_tasks(&tasks_dir));
+
+    graph.rendered = match format.as_deref().unwrap_or("json") {
+        "dot" => Some(render_dot(&graph)),
+        "mermaid" => Some(render_mermaid(&graph)),
+        "json" => None,
+        other => return Err(format!("Unknown graph format '{}'", other)),
+    };
+
+    Ok(graph)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn task(id: &str, status: &str, blocked_by: &[&str]) -> ClaudeTask {
+        ClaudeTask {
+            id: id.to_string(),
+            subject: format!("Task {}", id),
+            status: status.to_string(),
+            description: String::new(),
+            blocks: vec![],
+            blocked_by: blocked_by.iter().map(|s| s.to_string()).collect(),
+            owner: None,
+        }
+    }
+
+    #[test]
+    fn test_build_task_graph() {
+        let mut tasks = vec![
+            task("1", "completed", &[]),
+            task("2", "pending", &["1"]),
+            task("3", "pending", &["2"]),
+            task("4", "pending", &["1", "99"]),
+            task("5", "pending", &["6"]),
+            task("6", "pending", &["5"]),
+        ];
+        // Declared from the other side only
+        tasks[2].blocks = vec!["7".to_string()];
+        tasks.push(task("7", "pending", &[]));
+
+        let graph = build_task_graph(&tasks);
+
+        assert_eq!(graph.cycles, vec![vec!["5".to_string(), "6".to_string()]]);
+        assert_eq!(graph.dangling.len(), 1);
+        assert_eq!(graph.dangling[0].reference, "99");
+        assert_eq!(graph.unblocked, vec!["2", "4"]);
+        assert_eq!(graph.critical_path, vec!["2", "3", "7"]);
+        assert!(graph.edges.contains(&TaskGraphEdge {
+            from: "3".to_string(),
+            to: "7".to_string()
+        }));
+    }
+
+    #[test]
+    fn test_render_formats() {
+        let tasks = vec![task("1", "completed", &[]), task("2", "pending", &["1"]), task("3", "pending", &["2"])];
+        let graph = build_task_graph(&tasks);
+
+        let dot = render_dot(&graph);
+        assert!(dot.starts_with("digraph tasks {"));
+        assert!(dot.contains("\"2\" -> \"3\" [color=red, penwidth=2];"));
+        assert!(dot.contains("\"1\" -> \"2\";"));
+
+        let mermaid = render_mermaid(&graph);
+        assert!(mermaid.contains("  t1 --> t2\n"));
+        assert!(mermaid.contains("  class t1 completed\n"));
+        assert!(mermaid.contains("  linkStyle 1 stroke:red"));
+    }
+}
@@ -1,12 +1,19 @@
 use serde::{Deserialize, Serialize};
+use std::cell::Cell;
 use std::fs;
-use std::path::Path;
-use std::sync::Mutex;
-use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{mpsc, LazyLock, Mutex};
+use std::collections::{HashMap, VecDeque};
+use std::thread;
+use std::time::Duration;
 use regex::Regex;
 use uuid::Uuid;
 use notify::{Watcher, RecursiveMode, RecommendedWatcher, Event, EventKind};
-use tauri::{AppHandle, Emitter};
+use tauri::{AppHandle, Emitter, Manager};
+
+use super::boards::{self, BoardConfig};
+use super::comments::{self, TaskComment};
+use super::{board_sources, dependencies, history, journal, merge, progress, settings, templates};
 
 // Global state for file watchers
 pub struct TasksWatcherState {
@@ -27,10 +34,65 @@ impl Default for TasksWatcherState {
     }
 }
 
+/// Quiet period after the last file event before TASKS.md is re-read; one save produces
+/// several events
+const WATCH_DEBOUNCE: Duration = Duration::from_millis(150);
+
+/// How many of the app's own TASKS.md writes are remembered for echo suppression
+const MAX_OWN_WRITES: usize = 64;
+
+/// (file path, version) of board file contents the app wrote itself, so the watcher can tell
+/// them apart from edits made elsewhere
+static OWN_WRITES: Mutex<VecDeque<(PathBuf, String)>> = Mutex::new(VecDeque::new());
+
+thread_local! {
+    /// Set while a frontend-initiated write runs on this thread
+    static FRONTEND_WRITE: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Clears `FRONTEND_WRITE` when dropped, so a panicking write doesn't leave it set
+struct FrontendWriteGuard;
+
+impl Drop for FrontendWriteGuard {
+    fn drop(&mut self) {
+        FRONTEND_WRITE.with(|flag| flag.set(false));
+    }
+}
+
+/// Run a write the frontend initiated and reloads the board after; every command that returns
+/// its write to the frontend wraps it. The watcher doesn't echo the board files written inside
+/// it; every other write, e.g. from the schedulers, dispatch or sync, is emitted as
+/// `tasks-md-changed` so the board doesn't go stale.
+pub(crate) fn frontend_write<T>(write: impl FnOnce() -> T) -> T {
+    FRONTEND_WRITE.with(|flag| flag.set(true));
+    let _guard = FrontendWriteGuard;
+    write()
+}
+
+/// Note content the app is about to write to a board file
+fn remember_own_write(path: &Path, content: &str) {
+    if let Ok(mut writes) = OWN_WRITES.lock() {
+        writes.push_back((path.to_path_buf(), merge::content_hash(content)));
+        if writes.len() > MAX_OWN_WRITES {
+            writes.pop_front();
+        }
+    }
+}
+
+fn is_own_write(path: &Path, version: &str) -> bool {
+    OWN_WRITES
+        .lock()
+        .map(|writes| writes.iter().any(|(p, v)| p == path && v == version))
+        .unwrap_or(false)
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TaskProgress {
     pub total: usize,
     pub completed: usize,
+    /// Checkboxes marked `[-]` or `[~]`
+    #[serde(rename = "inProgress", default)]
+    pub in_progress: usize,
     pub percentage: f64,
 }
 
@@ -46,7 +108,7 @@ pub struct ClaudeTask {
     pub blocks: Vec<String>,
     #[serde(rename = "blockedBy", default)]
     pub blocked_by: Vec<String>,
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub owner: Option<String>,
 }
 
@@ -63,7 +125,7 @@ pub struct ClaudeTaskProgress {
     pub is_active: bool,
 }
 
-const TASK_FILES: &[&str] = &[
+pub(crate) const TASK_FILES: &[&str] = &[
     "plan.md",
     "todo.md",
     "tasks.md",
@@ -71,82 +133,60 @@ const TASK_FILES: &[&str] = &[
     "CLAUDE.md",
 ];
 
+/// Overall checkbox progress across the project's progress globs (the checklist files in the
+/// project root unless configured otherwise)
 #[tauri::command]
-pub fn get_task_progress(project_path: String) -> Result<TaskProgress, String> {
-    let project_dir = Path::new(&project_path);
-
-    if !project_dir.exists() || !project_dir.is_dir() {
-        return Err("Invalid project path".to_string());
-    }
-
-    let mut total = 0;
-    let mut completed = 0;
-
-    for filename in TASK_FILES {
-        let file_path = project_dir.join(filename);
-        if file_path.exists() && file_path.is_file() {
-            if let Ok(content) = fs::read_to_string(&file_path) {
-                let (file_total, file_completed) = parse_tasks(&content);
-                total += file_total;
-                completed += file_completed;
-            }
-        }
-    }
-
-    let percentage = if total > 0 {
-        (completed as f64 / total as f64) * 100.0
-    } else {
-        0.0
-    };
-
-    Ok(TaskProgress {
-        total,
-        completed,
-        percentage,
-    })
+pub fn get_task_progress(
+    project_path: String,
+    state: tauri::State<'_, crate::state::AppState>,
+) -> Result<TaskProgress, String> {
+    let globs = progress::progress_globs(&state, &project_path);
+    let breakdown = progress::progress_breakdown(&project_path, &globs)?;
+    Ok(TaskProgress::from(&breakdown))
 }
 
-fn parse_tasks(content: &str) -> (usize, usize) {
-    let mut total = 0;
-    let mut completed = 0;
-    let mut in_code_block = false;
-
-    // Regex for markdown checkboxes: - [ ] or - [x] or - [X]
-    let checkbox_re = Regex::new(r"^\s*-\s*\[([ xX])\]").unwrap();
-
-    for line in content.lines() {
-        // Track code blocks
-        if line.trim().starts_with("```") {
-            in_code_block = !in_code_block;
-            continue;
-        }
+/// Directory holding a Claude Code task list: ~/.claude/tasks/<task_list_id>/
+pub(crate) fn claude_tasks_dir(task_list_id: &str) -> Result<PathBuf, String> {
+    let home_dir = dirs::home_dir().ok_or("Could not determine home directory")?;
+    Ok(home_dir.join(".claude").join("tasks").join(task_list_id))
+}
 
-        // Skip content inside code blocks
-        if in_code_block {
-            continue;
-        }
+/// Read all task JSON files (numbered: 1.json, 2.json, etc.) sorted by numeric ID
+pub(crate) fn read_claude_tasks(tasks_dir: &Path) -> Vec<ClaudeTask> {
+    let mut tasks: Vec<ClaudeTask> = Vec::new();
 
-        // Check for checkbox pattern
-        if let Some(caps) = checkbox_re.captures(line) {
-            total += 1;
-            if let Some(status) = caps.get(1) {
-                let status_char = status.as_str().chars().next().unwrap_or(' ');
-                if status_char == 'x' || status_char == 'X' {
-                    completed += 1;
+    if let Ok(entries) = fs::read_dir(tasks_dir) {
+        for entry in entries.filter_map(|e| e.ok()) {
+            let path = entry.path();
+            if path.is_file() {
+                if let Some(ext) = path.extension() {
+                    if ext == "json" {
+                        if let Ok(content) = fs::read_to_string(&path) {
+                            // Parse the task JSON
+                            if let Ok(task) = serde_json::from_str::<ClaudeTask>(&content) {
+                                tasks.push(task);
+                            }
+                        }
+                    }
                 }
             }
         }
     }
 
-    (total, completed)
+    // Sort tasks by ID (numeric order)
+    tasks.sort_by(|a, b| {
+        let a_num: i32 = a.id.parse().unwrap_or(0);
+        let b_num: i32 = b.id.parse().unwrap_or(0);
+        a_num.cmp(&b_num)
+    });
+
+    tasks
 }
 
 /// Get Claude Code task progress by reading from ~/.claude/tasks/<task_list_id>/
 #[tauri::command]
 pub fn get_claude_task_progress(task_list_id: String) -> Result<ClaudeTaskProgress, String> {
-    // Get home directory
-    let home_dir = dirs::home_dir().ok_or("Could not determine home directory")?;
-    let tasks_dir = home_dir.join(".claude").join("tasks").join(&task_list_id);
+    let tasks_dir = claude_tasks_dir(&task_list_id)?;
 
     // Check if the tasks directory exists
     if !tasks_dir.exists() || !tasks_dir.is_dir() {
@@ -166,33 +206,7 @@ pub fn get_claude_task_progress(task_list_id: String) -> Result<ClaudeTaskProgre
     let lock_file = tasks_dir.join(".lock");
     let is_active = lock_file.exists();
 
-    // Read all task JSON files (numbered: 1.json, 2.json, etc.)
-    let mut tasks: Vec<ClaudeTask> = Vec::new();
-
-    if let Ok(entries) = fs::read_dir(&tasks_dir) {
-        for entry in entries.filter_map(|e| e.ok()) {
-            let path = entry.path();
-            if path.is_file() {
-                if let Some(ext) = path.extension() {
-                    if ext == "json" {
-                        if let Ok(content) = fs::read_to_string(&path) {
-                            // Parse the task JSON
-                            if let Ok(task) = serde_json::from_str::<ClaudeTask>(&content) {
-                                tasks.push(task);
-                            }
-                        }
-                    }
-                }
-            }
-        }
-    }
-
-    // Sort tasks by ID (numeric order)
-    tasks.sort_by(|a, b| {
-        let a_num: i32 = a.id.parse().unwrap_or(0);
-        let b_num: i32 = b.id.parse().unwrap_or(0);
-        a_num.cmp(&b_num)
-    });
+    let tasks = read_claude_tasks(&tasks_dir);
 
     // Calculate progress
     let total = tasks.len();
@@ -224,63 +238,348 @@ pub struct TasksMdTask {
     pub subject: String,
     pub description: Option<String>,
     pub column: String,  // "backlog", "in_progress", "review", "done"
+    /// `###` heading the task sits under within its column
+    #[serde(default)]
+    pub epic: Option<String>,
     pub completed: bool,
     #[serde(rename = "lineNumber")]
     pub line_number: usize,
+    /// Last line of the task's block (its checkbox line, body and subtasks), as parsed
+    #[serde(skip)]
+    pub end_line_number: usize,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    #[serde(default)]
+    pub priority: Option<String>,
+    #[serde(default)]
+    pub assignee: Option<String>,
+    #[serde(default)]
+    pub estimate: Option<String>,
+    /// `due:` date, YYYY-MM-DD
+    #[serde(default)]
+    pub due: Option<String>,
+    /// Explicit `id:` token; when set it is also used as `id`, so it survives re-parsing
+    #[serde(rename = "stableId", default)]
+    pub stable_id: Option<String>,
+    /// `blocked-by:` references to stable ids or slugified subjects of upstream tasks
+    #[serde(rename = "blockedBy", default)]
+    pub blocked_by: Vec<String>,
+    /// Comment thread, stored as `> [!comment]` blocks in the task body
+    #[serde(default)]
+    pub comments: Vec<TaskComment>,
+    #[serde(default)]
+    pub subtasks: Vec<TasksMdSubtask>,
+    /// Leaf subtask completion, rolled up from `subtasks`; `None` when there are none. Finishing
+    /// every subtask doesn't complete the task itself, see `rollup_subtasks`.
+    #[serde(default)]
+    pub progress: Option<TaskProgress>,
 }
 
-/// Column name to heading mapping
-const COLUMN_HEADINGS: &[(&str, &str)] = &[
-    ("backlog", "## Backlog"),
-    ("blocked", "## Blocked"),
-    ("in_progress", "## In Progress"),
-    ("review", "## Review"),
-    ("done", "## Done"),
-];
+impl TasksMdTask {
+    /// A new, unplaced task; metadata tokens typed into the subject become structured fields
+    pub fn new(subject: &str, description: Option<String>, column: &str) -> Self {
+        let (subject, tokens) = split_inline_tokens(subject);
 
-fn heading_to_column(heading: &str) -> Option<&'static str> {
-    let heading_lower = heading.to_lowercase();
-    for (col, h) in COLUMN_HEADINGS {
-        if heading_lower == h.to_lowercase() {
-            return Some(*col);
+        Self {
+            id: tokens.stable_id.clone().unwrap_or_else(|| Uuid::new_v4().to_string()),
+            subject,
+            description,
+            column: column.to_string(),
+            epic: None,
+            completed: column == "done",
+            line_number: 0, // Will be recalculated on write
+            end_line_number: 0,
+            tags: tokens.tags,
+            priority: tokens.priority,
+            assignee: tokens.assignee,
+            estimate: tokens.estimate,
+            due: tokens.due,
+            stable_id: tokens.stable_id,
+            blocked_by: tokens.blocked_by,
+            comments: vec![],
+            subtasks: vec![],
+            progress: None,
         }
     }
-    None
 }
 
-fn column_to_heading(column: &str) -> &'static str {
-    for (col, h) in COLUMN_HEADINGS {
-        if *col == column {
-            return h;
+/// A nested checkbox under a TASKS.md task
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TasksMdSubtask {
+    pub id: String,
+    #[serde(rename = "parentId")]
+    pub parent_id: String,
+    pub subject: String,
+    pub completed: bool,
+    #[serde(rename = "lineNumber")]
+    pub line_number: usize,
+    #[serde(default)]
+    pub subtasks: Vec<TasksMdSubtask>,
+    /// Index among the task's description lines, comments and subtasks where the subtask was
+    /// read, so notes between subtasks stay where they were; new subtasks follow the one before
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub position: Option<usize>,
+}
+
+/// Filter applied by `read_tasks_md`; every field that is set must match
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TasksMdFilter {
+    #[serde(default)]
+    pub tags: Vec<String>,
+    #[serde(default)]
+    pub priority: Option<String>,
+    #[serde(default)]
+    pub assignee: Option<String>,
+    #[serde(default)]
+    pub column: Option<String>,
+    #[serde(default)]
+    pub epic: Option<String>,
+    /// Case-insensitive substring of the subject or description
+    #[serde(default)]
+    pub text: Option<String>,
+    #[serde(default)]
+    pub completed: Option<bool>,
+}
+
+impl TasksMdFilter {
+    pub fn matches(&self, task: &TasksMdTask) -> bool {
+        let tags_match = self.tags.iter().all(|tag| {
+            let tag = tag.trim_start_matches('#');
+            task.tags.iter().any(|t| t.eq_ignore_ascii_case(tag))
+        });
+        let priority_match = match &self.priority {
+            Some(p) => task.priority.as_deref() == Some(p.trim_start_matches('!').to_lowercase().as_str()),
+            None => true,
+        };
+        let assignee_match = match &self.assignee {
+            Some(a) => task
+                .assignee
+                .as_deref()
+                .map(|t| t.eq_ignore_ascii_case(a.trim_start_matches('@')))
+                .unwrap_or(false),
+            None => true,
+        };
+        let column_match = self.column.as_ref().map(|c| task.column == *c).unwrap_or(true);
+        let epic_match = match &self.epic {
+            Some(e) => task.epic.as_deref().map(|t| t.eq_ignore_ascii_case(e)).unwrap_or(false),
+            None => true,
+        };
+        let text_match = match &self.text {
+            Some(text) => {
+                let text = text.to_lowercase();
+                task.subject.to_lowercase().contains(&text)
+                    || task
+                        .description
+                        .as_ref()
+                        .map(|d| d.to_lowercase().contains(&text))
+                        .unwrap_or(false)
+            }
+            None => true,
+        };
+        let completed_match = self.completed.map(|c| task.completed == c).unwrap_or(true);
+        tags_match
+            && priority_match
+            && assignee_match
+            && column_match
+            && epic_match
+            && text_match
+            && completed_match
+    }
+}
+
+/// Recognized `!priority` values, lowest to highest
+pub(crate) const PRIORITIES: &[&str] = &["low", "medium", "high", "critical"];
+
+/// The `#tag`, `@assignee`, `~estimate`, `id:` and `blocked-by:` tokens
+pub(crate) static TAG_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"^#([A-Za-z][\w/-]*)$").unwrap());
+pub(crate) static ASSIGNEE_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"^@([\w.-]+)$").unwrap());
+pub(crate) static ESTIMATE_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"^~(\d+(?:\.\d+)?[mhdw])$").unwrap());
+static ID_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"^id:([\w.-]+)$").unwrap());
+static BLOCKED_BY_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"^blocked-by:([\w.,-]+)$").unwrap());
+
+/// Metadata tokens found at the end of a task's subject line
+#[derive(Debug, Clone, Default, PartialEq)]
+struct InlineTokens {
+    tags: Vec<String>,
+    priority: Option<String>,
+    assignee: Option<String>,
+    estimate: Option<String>,
+    due: Option<String>,
+    stable_id: Option<String>,
+    blocked_by: Vec<String>,
+}
+
+/// Split trailing `#tag`, `!priority`, `@assignee`, `~estimate`, `due:`, `id:` and `blocked-by:` tokens
+/// off a subject line.
+/// Only the trailing run of tokens is treated as metadata, so a `#` or `@` in the middle of
+/// the subject is left alone. The first word is never consumed.
+fn split_inline_tokens(text: &str) -> (String, InlineTokens) {
+    let mut tokens = InlineTokens::default();
+    let mut rest = text.trim();
+
+    while let Some(idx) = rest.rfind(char::is_whitespace) {
+        let word = &rest[idx + 1..];
+
+        if let Some(caps) = TAG_RE.captures(word) {
+            tokens.tags.push(caps[1].to_string());
+        } else if let Some(p) = word.strip_prefix('!').filter(|p| {
+            PRIORITIES.iter().any(|known| known.eq_ignore_ascii_case(p))
+        }) {
+            if tokens.priority.is_some() {
+                break;
+            }
+            tokens.priority = Some(p.to_lowercase());
+        } else if let Some(caps) = ASSIGNEE_RE.captures(word) {
+            if tokens.assignee.is_some() {
+                break;
+            }
+            tokens.assignee = Some(caps[1].to_string());
+        } else if let Some(caps) = ESTIMATE_RE.captures(word) {
+            if tokens.estimate.is_some() {
+                break;
+            }
+            tokens.estimate = Some(caps[1].to_string());
+        } else if let Some(date) = word.strip_prefix("due:").filter(|d| templates::parse_date(d).is_some()) {
+            if tokens.due.is_some() {
+                break;
+            }
+            tokens.due = Some(date.to_string());
+        } else if let Some(caps) = ID_RE.captures(word) {
+            if tokens.stable_id.is_some() {
+                break;
+            }
+            tokens.stable_id = Some(caps[1].to_string());
+        } else if let Some(caps) = BLOCKED_BY_RE.captures(word) {
+            // Pushed in reverse so the final reverse restores the written order
+            for reference in caps[1].rsplit(',').filter(|r| !r.is_empty()) {
+                tokens.blocked_by.push(reference.to_string());
+            }
+        } else {
+            break;
         }
+
+        rest = rest[..idx].trim_end();
+    }
+
+    // Tokens were collected right to left
+    tokens.tags.reverse();
+    tokens.blocked_by.reverse();
+
+    (rest.to_string(), tokens)
+}
+
+/// Render a task's subject line text, appending its metadata tokens in canonical order
+fn format_task_text(task: &TasksMdTask) -> String {
+    let mut text = task.subject.clone();
+    for tag in &task.tags {
+        text.push_str(&format!(" #{}", tag.trim_start_matches('#')));
+    }
+    if let Some(priority) = &task.priority {
+        text.push_str(&format!(" !{}", priority.trim_start_matches('!')));
+    }
+    if let Some(assignee) = &task.assignee {
+        text.push_str(&format!(" @{}", assignee.trim_start_matches('@')));
+    }
+    if let Some(estimate) = &task.estimate {
+        text.push_str(&format!(" ~{}", estimate.trim_start_matches('~')));
+    }
+    if let Some(due) = &task.due {
+        text.push_str(&format!(" due:{}", due));
+    }
+    if let Some(stable_id) = &task.stable_id {
+        text.push_str(&format!(" id:{}", stable_id));
+    }
+    if !task.blocked_by.is_empty() {
+        text.push_str(&format!(" blocked-by:{}", task.blocked_by.join(",")));
     }
-    "## Backlog"
+    text
 }
 
-/// Read and parse TASKS.md file from a project directory
+/// Column name to heading mapping of the standard board
+pub(crate) const COLUMN_HEADINGS: &[(&str, &str)] = &[
+    ("backlog", "## Backlog"),
+    ("blocked", "## Blocked"),
+    ("in_progress", "## In Progress"),
+    ("review", "## Review"),
+    ("done", "## Done"),
+];
+
+/// Position of a column on the board, for sorting; unknown columns sort last
+pub(crate) fn column_rank(column: &str) -> usize {
+    COLUMN_HEADINGS
+        .iter()
+        .position(|(col, _)| *col == column)
+        .unwrap_or(COLUMN_HEADINGS.len())
+}
+
+/// Read and parse a board file (TASKS.md unless `board` is given) from a project directory,
+/// optionally filtered by metadata
 #[tauri::command]
-pub fn read_tasks_md(project_path: String) -> Result<Vec<TasksMdTask>, String> {
-    let tasks_md_path = Path::new(&project_path).join("TASKS.md");
+pub fn read_tasks_md(
+    project_path: String,
+    board: Option<String>,
+    filter: Option<TasksMdFilter>,
+    state: tauri::State<'_, crate::state::AppState>,
+) -> Result<Vec<TasksMdTask>, String> {
+    let board = boards::board_config(&state, &project_path, board.as_deref())?;
+    let tasks_md_path = board.path(&project_path);
 
     if !tasks_md_path.exists() {
         return Ok(vec![]);
     }
 
     let content = fs::read_to_string(&tasks_md_path)
-        .map_err(|e| format!("Failed to read TASKS.md: {}", e))?;
+        .map_err(|e| format!("Failed to read {}: {}", board.file, e))?;
+
+    let tasks = parse_board(&content, &board)?;
+
+    Ok(match filter {
+        Some(filter) => tasks.into_iter().filter(|t| filter.matches(t)).collect(),
+        None => tasks,
+    })
+}
+
+/// Parse content with the standard columns
+#[cfg(test)]
+pub(crate) fn parse_tasks_md(content: &str) -> Result<Vec<TasksMdTask>, String> {
+    parse_board(content, &BoardConfig::default())
+}
 
-    parse_tasks_md(&content)
+/// Parse a board file, mapping its `##` headings through the board's columns
+pub(crate) fn parse_board(content: &str, board: &BoardConfig) -> Result<Vec<TasksMdTask>, String> {
+    parse_checklist(content, None, |level, heading| {
+        if level != 2 {
+            return None;
+        }
+        board.heading_to_column(heading)
+    })
 }
 
-fn parse_tasks_md(content: &str) -> Result<Vec<TasksMdTask>, String> {
+/// Markdown heading: `# Title`, `## Backlog`, `### Phase 1`, ...
+pub(crate) const HEADING_PATTERN: &str = r"^(#{1,6})\s+(.+)$";
+
+/// Parse checkbox tasks with their descriptions and subtasks from any markdown file.
+/// `heading_column` is called for every heading (level, text) outside code blocks; returning
+/// `Some` switches the column for the tasks that follow, `None` keeps the current one. Tasks
+/// seen while there is no current column are skipped. A heading one level below the last
+/// column heading (`### Auth` under `## Backlog`) puts the tasks after it in that epic.
+pub(crate) fn parse_checklist<F>(
+    content: &str,
+    initial_column: Option<String>,
+    mut heading_column: F,
+) -> Result<Vec<TasksMdTask>, String>
+where
+    F: FnMut(usize, &str) -> Option<String>,
+{
     let mut tasks: Vec<TasksMdTask> = Vec::new();
-    let mut current_column: Option<&str> = None;
+    let mut current_column: Option<String> = initial_column;
+    let mut column_level: Option<usize> = None;
+    let mut current_epic: Option<String> = None;
     let mut in_code_block = false;
 
     // Regex for task checkbox: - [ ] or - [x] or - [X]
     let task_re = Regex::new(r"^(\s*)-\s*\[([ xX])\]\s*(.+)$").unwrap();
-    // Regex for section heading: ## Backlog, ## In Progress, etc.
-    let heading_re = Regex::new(r"^##\s+(.+)$").unwrap();
+    let heading_re = Regex::new(HEADING_PATTERN).unwrap();
 
     let lines: Vec<&str> = content.lines().collect();
     let mut i = 0;
@@ -303,40 +602,103 @@ fn parse_tasks_md(content: &str) -> Result<Vec<TasksMdTask>, String> {
 
         // Check for section heading
         if let Some(caps) = heading_re.captures(line) {
-            let heading_text = caps.get(1).map(|m| m.as_str()).unwrap_or("");
-            let full_heading = format!("## {}", heading_text);
-            if let Some(col) = heading_to_column(&full_heading) {
+            let level = caps.get(1).map(|m| m.as_str().len()).unwrap_or(0);
+            let heading_text = caps.get(2).map(|m| m.as_str().trim()).unwrap_or("");
+            if let Some(col) = heading_column(level, heading_text) {
                 current_column = Some(col);
+                column_level = Some(level);
+                current_epic = None;
+            } else if let Some(column_level) = column_level {
+                if level == column_level + 1 {
+                    current_epic = Some(heading_text.to_string());
+                } else if level <= column_level {
+                    current_epic = None;
+                }
             }
             i += 1;
             continue;
         }
 
         // Check for task checkbox
-        if let Some(current_col) = current_column {
+        if let Some(current_col) = &current_column {
             if let Some(caps) = task_re.captures(line) {
                 let status_char = caps.get(2).map(|m| m.as_str()).unwrap_or(" ");
                 let completed = status_char == "x" || status_char == "X";
-                let subject = caps.get(3).map(|m| m.as_str().trim().to_string()).unwrap_or_default();
+                let text = caps.get(3).map(|m| m.as_str()).unwrap_or_default();
+                let (subject, tokens) = split_inline_tokens(text);
 
-                // Look ahead for description (indented content on following lines)
-                let mut description_lines: Vec<String> = Vec::new();
+                let indent = caps.get(1).map(|m| m.as_str().len()).unwrap_or(0);
+
+                // Look ahead for the body and nested checkboxes (indented content on following lines)
+                let body_indent = " ".repeat(indent + 2);
+                // `None` marks where a subtask line sits among the body lines
+                let mut body_lines: Vec<Option<String>> = Vec::new();
+                let mut raw_subtasks: Vec<RawSubtask> = Vec::new();
+                let mut in_body_fence = false;
                 let mut j = i + 1;
                 while j < lines.len() {
                     let next_line = lines[j];
-                    // If the line is indented more than the task line, it's description
-                    if next_line.starts_with("  ") && !next_line.trim().is_empty()
-                        && !task_re.is_match(next_line)
-                        && !heading_re.is_match(next_line)
-                        && !next_line.trim().starts_with("```")
-                    {
-                        description_lines.push(next_line.trim().to_string());
+                    let is_body_line = next_line.starts_with(&body_indent);
+
+                    if in_body_fence || (is_body_line && next_line.trim().starts_with("```")) {
+                        // Everything inside a fenced block of the body is kept verbatim
+                        if next_line.trim().is_empty() {
+                            body_lines.push(Some(String::new()));
+                        } else if is_body_line {
+                            body_lines.push(Some(next_line[body_indent.len()..].to_string()));
+                        } else {
+                            break;
+                        }
+                        if next_line.trim().starts_with("```") {
+                            in_body_fence = !in_body_fence;
+                        }
+                        j += 1;
+                    } else if next_line.trim().is_empty() {
+                        // Blank lines belong to the body only when more of it follows
+                        let rest = lines[j..].iter().find(|l| !l.trim().is_empty());
+                        let continues = rest
+                            .map(|l| l.starts_with(&body_indent) && !task_re.is_match(l))
+                            .unwrap_or(false);
+                        if !continues || body_lines.is_empty() {
+                            break;
+                        }
+                        body_lines.push(Some(String::new()));
+                        j += 1;
+                    } else if let Some(sub_caps) = task_re.captures(next_line) {
+                        // Checkboxes indented deeper than the task line are subtasks
+                        let sub_indent = sub_caps.get(1).map(|m| m.as_str().len()).unwrap_or(0);
+                        if sub_indent <= indent {
+                            break;
+                        }
+                        let sub_status = sub_caps.get(2).map(|m| m.as_str()).unwrap_or(" ");
+                        raw_subtasks.push(RawSubtask {
+                            indent: sub_indent,
+                            subject: sub_caps.get(3).map(|m| m.as_str().trim().to_string()).unwrap_or_default(),
+                            completed: sub_status == "x" || sub_status == "X",
+                            line_number: j + 1,
+                            position: 0,
+                        });
+                        body_lines.push(None);
+                        j += 1;
+                    } else if is_body_line {
+                        // Indented past the checkbox: part of the body, minus that indentation
+                        body_lines.push(Some(next_line[body_indent.len()..].to_string()));
                         j += 1;
                     } else {
                         break;
                     }
                 }
 
+                let (description_lines, comments, subtask_positions) = comments::split_comments(body_lines);
+                for (raw, position) in raw_subtasks.iter_mut().zip(subtask_positions) {
+                    raw.position = position;
+                }
+
+                let id = tokens.stable_id.clone().unwrap_or_else(|| Uuid::new_v4().to_string());
+                let mut pos = 0;
+                let mut subtasks = build_subtasks(&raw_subtasks, &mut pos, indent, &id);
+                let progress = rollup_subtasks(&mut subtasks);
+
                 let description = if description_lines.is_empty() {
                     None
                 } else {
@@ -344,12 +706,24 @@ fn parse_tasks_md(content: &str) -> Result<Vec<TasksMdTask>, String> {
                 };
 
                 tasks.push(TasksMdTask {
-                    id: Uuid::new_v4().to_string(),
+                    id,
                     subject,
                     description,
-                    column: current_col.to_string(),
+                    column: current_col.clone(),
+                    epic: current_epic.clone(),
                     completed,
                     line_number,
+                    end_line_number: j,
+                    tags: tokens.tags,
+                    priority: tokens.priority,
+                    assignee: tokens.assignee,
+                    estimate: tokens.estimate,
+                    due: tokens.due,
+                    stable_id: tokens.stable_id,
+                    blocked_by: tokens.blocked_by,
+                    comments,
+                    subtasks,
+                    progress,
                 });
 
                 i = j;
@@ -363,201 +737,839 @@ fn parse_tasks_md(content: &str) -> Result<Vec<TasksMdTask>, String> {
     Ok(tasks)
 }
 
-/// Write tasks back to TASKS.md file
-#[tauri::command]
-pub fn write_tasks_md(project_path: String, tasks: Vec<TasksMdTask>) -> Result<(), String> {
-    let tasks_md_path = Path::new(&project_path).join("TASKS.md");
-
-    let mut content = String::from("# TASKS\n\n");
-
-    // Group tasks by column
-    let columns = ["backlog", "blocked", "in_progress", "review", "done"];
-
-    for col in columns {
-        let heading = column_to_heading(col);
-        content.push_str(heading);
-        content.push('\n');
+/// A nested checkbox line before it is placed in the subtask tree
+struct RawSubtask {
+    indent: usize,
+    subject: String,
+    completed: bool,
+    line_number: usize,
+    position: usize,
+}
 
-        let col_tasks: Vec<&TasksMdTask> = tasks.iter().filter(|t| t.column == col).collect();
+/// Build the subtask tree from checkbox lines, nesting each under the nearest shallower line
+fn build_subtasks(
+    raw: &[RawSubtask],
+    pos: &mut usize,
+    parent_indent: usize,
+    parent_id: &str,
+) -> Vec<TasksMdSubtask> {
+    let mut subtasks = Vec::new();
+
+    while *pos < raw.len() && raw[*pos].indent > parent_indent {
+        let item = &raw[*pos];
+        *pos += 1;
+
+        let id = Uuid::new_v4().to_string();
+        let children = build_subtasks(raw, pos, item.indent, &id);
+
+        subtasks.push(TasksMdSubtask {
+            id,
+            parent_id: parent_id.to_string(),
+            subject: item.subject.clone(),
+            completed: item.completed,
+            line_number: item.line_number,
+            subtasks: children,
+            position: Some(item.position),
+        });
+    }
 
-        for task in col_tasks {
-            let checkbox = if task.completed { "[x]" } else { "[ ]" };
-            content.push_str(&format!("- {} {}\n", checkbox, task.subject));
+    subtasks
+}
 
-            if let Some(desc) = &task.description {
-                for line in desc.lines() {
-                    content.push_str(&format!("  {}\n", line));
+/// Complete each subtask whose children all are, stopping below the task; leaf progress or `None`
+fn rollup_subtasks(subtasks: &mut [TasksMdSubtask]) -> Option<TaskProgress> {
+    fn count_leaves(subtasks: &mut [TasksMdSubtask]) -> (usize, usize) {
+        let mut total = 0;
+        let mut completed = 0;
+        for subtask in subtasks.iter_mut() {
+            if subtask.subtasks.is_empty() {
+                total += 1;
+                if subtask.completed {
+                    completed += 1;
                 }
+            } else {
+                let (t, c) = count_leaves(&mut subtask.subtasks);
+                subtask.completed = t == c;
+                total += t;
+                completed += c;
             }
         }
-
-        content.push('\n');
+        (total, completed)
     }
 
-    fs::write(&tasks_md_path, content)
-        .map_err(|e| format!("Failed to write TASKS.md: {}", e))?;
+    if subtasks.is_empty() {
+        return None;
+    }
 
-    Ok(())
+    let (total, completed) = count_leaves(subtasks);
+    Some(TaskProgress {
+        total,
+        completed,
+        in_progress: 0,
+        percentage: (completed as f64 / total as f64) * 100.0,
+    })
 }
 
-/// Create a new TASKS.md file with default structure
-#[tauri::command]
-pub fn create_tasks_md(project_path: String, project_name: String) -> Result<(), String> {
-    let tasks_md_path = Path::new(&project_path).join("TASKS.md");
-
-    // Don't overwrite existing file
-    if tasks_md_path.exists() {
-        return Ok(());
+/// Subtask checkbox lines in file order, relative to the task's body, with their positions
+fn subtask_lines(out: &mut Vec<(Option<usize>, String)>, subtasks: &[TasksMdSubtask], depth: usize) {
+    for subtask in subtasks {
+        let checkbox = if subtask.completed { "[x]" } else { "[ ]" };
+        out.push((subtask.position, format!("{}- {} {}", "  ".repeat(depth), checkbox, subtask.subject)));
+        subtask_lines(out, &subtask.subtasks, depth + 1);
     }
+}
 
-    let content = format!(r#"# TASKS
-
-## Backlog
-- [ ] Set up {} project
-
-## Blocked
+/// A task body without the task's indentation: the description with comments and subtasks
+/// put back where they were read. Comments without a position go after the description, and
+/// subtasks without one follow the subtask before them. A blank line is kept between a comment
+/// and a description line starting with `>`, which would otherwise be read back as part of the
+/// comment. Blank lines at either end are dropped, since a blank line right below the task
+/// would end its body.
+fn render_body(task: &TasksMdTask) -> Vec<String> {
+    // (position, lines, is a comment); comments come first so they win ties
+    let mut items: Vec<(usize, Vec<String>, bool)> = task
+        .comments
+        .iter()
+        .map(|c| (c.position.unwrap_or(usize::MAX), comments::render_comment(c), true))
+        .collect();
 
-## In Progress
+    let mut subtasks = Vec::new();
+    subtask_lines(&mut subtasks, &task.subtasks, 0);
+    let mut position = subtasks.iter().find_map(|(p, _)| *p).unwrap_or(usize::MAX);
+    for (own, line) in subtasks {
+        position = own.map_or(position, |p| p.max(position));
+        items.push((position, vec![line], false));
+    }
+    items.sort_by_key(|(position, _, _)| *position);
+
+    let mut description = task
+        .description
+        .as_deref()
+        .map(|d| d.split('\n').collect::<Vec<_>>())
+        .unwrap_or_default()
+        .into_iter();
+    let mut lines: Vec<String> = Vec::new();
+    let mut after_comment = false;
+
+    fn push_description(lines: &mut Vec<String>, line: &str, after_comment: &mut bool) {
+        if *after_comment && line.starts_with('>') {
+            lines.push(String::new());
+        }
+        *after_comment = false;
+        lines.push(line.to_string());
+    }
 
-## Review
+    let mut index = 0;
+    for (position, item_lines, is_comment) in items {
+        while index < position {
+            let Some(line) = description.next() else {
+                break;
+            };
+            push_description(&mut lines, line, &mut after_comment);
+            index += 1;
+        }
+        lines.extend(item_lines);
+        after_comment = is_comment;
+        index += 1;
+    }
+    for line in description {
+        push_description(&mut lines, line, &mut after_comment);
+    }
 
-## Done
+    while lines.first().map(|l| l.trim().is_empty()).unwrap_or(false) {
+        lines.remove(0);
+    }
+    while lines.last().map(|l| l.trim().is_empty()).unwrap_or(false) {
+        lines.pop();
+    }
 
-"#, project_name);
+    lines
+}
 
-    fs::write(&tasks_md_path, content)
-        .map_err(|e| format!("Failed to create TASKS.md: {}", e))?;
+/// Result of a TASKS.md mutation. When `conflicts` is non-empty nothing was written and
+/// `version` is the version currently on disk.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TasksMdWriteResult {
+    pub version: String,
+    /// Whether the mutation was merged with changes made since `base_version`
+    pub merged: bool,
+    pub conflicts: Vec<merge::TaskMergeConflict>,
+    /// Tasks as written, after merging and auto-blocking; empty when nothing was written
+    #[serde(skip)]
+    pub tasks: Vec<TasksMdTask>,
+}
 
-    Ok(())
+/// Parsed tasks together with the version of the content they came from
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TasksMdSnapshot {
+    pub tasks: Vec<TasksMdTask>,
+    pub version: String,
 }
 
-/// Move a task to a different column in TASKS.md by subject (since IDs are ephemeral)
+/// Read TASKS.md along with its version, to pass back as `base_version` on mutations
 #[tauri::command]
-pub fn move_task_in_tasks_md(
+pub fn read_tasks_md_snapshot(
     project_path: String,
-    task_subject: String,
-    new_column: String,
-) -> Result<(), String> {
-    let tasks_md_path = Path::new(&project_path).join("TASKS.md");
-
-    if !tasks_md_path.exists() {
-        return Err("TASKS.md does not exist".to_string());
-    }
+    board: Option<String>,
+    state: tauri::State<'_, crate::state::AppState>,
+    versions: tauri::State<'_, merge::TasksMdVersions>,
+) -> Result<TasksMdSnapshot, String> {
+    let board = boards::board_config(&state, &project_path, board.as_deref())?;
+    let tasks_md_path = board.path(&project_path);
+
+    let content = if tasks_md_path.exists() {
+        fs::read_to_string(&tasks_md_path)
+            .map_err(|e| format!("Failed to read {}: {}", board.file, e))?
+    } else {
+        String::new()
+    };
 
-    let content = fs::read_to_string(&tasks_md_path)
-        .map_err(|e| format!("Failed to read TASKS.md: {}", e))?;
+    Ok(TasksMdSnapshot {
+        tasks: parse_board(&content, &board)?,
+        version: versions.remember(&content),
+    })
+}
 
-    let mut tasks = parse_tasks_md(&content)?;
+/// Apply a mutation to a board file and write the result.
+///
+/// Without a `base_version` the mutation applies to the file as it is now. With one, it applies
+/// to the content the caller based it on; if the file has changed since, the result is merged
+/// with the on-disk tasks, and conflicting tasks are returned instead of written.
+pub(crate) fn mutate_board<F>(
+    project_path: &str,
+    board: &BoardConfig,
+    base_version: Option<String>,
+    versions: &merge::TasksMdVersions,
+    require_existing: bool,
+    mutate: F,
+) -> Result<TasksMdWriteResult, String>
+where
+    F: FnOnce(&mut Vec<TasksMdTask>) -> Result<(), String>,
+{
+    let tasks_md_path = board.path(project_path);
+
+    if require_existing && !tasks_md_path.exists() {
+        return Err(format!("{} does not exist", board.file));
+    }
 
-    // Find the task by subject and update its column
-    let task = tasks.iter_mut().find(|t| t.subject == task_subject);
-    if let Some(t) = task {
-        t.column = new_column.clone();
-        // Update completed status based on column
-        t.completed = new_column == "done";
+    let current = if tasks_md_path.exists() {
+        fs::read_to_string(&tasks_md_path)
+            .map_err(|e| format!("Failed to read {}: {}", board.file, e))?
     } else {
-        return Err(format!("Task '{}' not found", task_subject));
-    }
+        String::new()
+    };
+    let current_version = merge::content_hash(&current);
+
+    let base = match base_version {
+        Some(version) if version != current_version => Some(versions.get(&version).ok_or_else(|| {
+            format!(
+                "{} changed since it was read and that version is no longer known; reload the board",
+                board.file
+            )
+        })?),
+        _ => None,
+    };
 
-    // Write back
-    write_tasks_md(project_path, tasks)
+    let base_tasks = parse_board(base.as_deref().unwrap_or(&current), board)?;
+    let mut tasks = base_tasks.clone();
+    mutate(&mut tasks)?;
+
+    let merged = base.is_some();
+    if merged {
+        let outcome = merge::merge_tasks(&base_tasks, &tasks, &parse_board(&current, board)?);
+        if !outcome.conflicts.is_empty() {
+            return Ok(TasksMdWriteResult {
+                version: versions.remember(&current),
+                merged: false,
+                conflicts: outcome.conflicts,
+                tasks: vec![],
+            });
+        }
+        tasks = outcome.tasks;
+    }
+
+    let content = save_board(project_path, board, &mut tasks)?;
+
+    Ok(TasksMdWriteResult {
+        version: versions.remember(&content),
+        merged,
+        conflicts: vec![],
+        tasks,
+    })
 }
 
-/// Add a new task to TASKS.md
+/// Write tasks back to a board file (TASKS.md unless `board` is given)
 #[tauri::command]
-pub fn add_task_to_tasks_md(
+pub fn write_tasks_md(
     project_path: String,
-    subject: String,
-    description: Option<String>,
-    column: String,
+    board: Option<String>,
+    tasks: Vec<TasksMdTask>,
+    base_version: Option<String>,
+    state: tauri::State<'_, crate::state::AppState>,
+    versions: tauri::State<'_, merge::TasksMdVersions>,
+) -> Result<TasksMdWriteResult, String> {
+    let board = boards::board_config(&state, &project_path, board.as_deref())?;
+    frontend_write(|| {
+        mutate_board(&project_path, &board, base_version, &versions, false, |current| {
+            *current = tasks;
+            Ok(())
+        })
+    })
+}
+
+/// Render tasks and write them to a board file, returning the written content
+pub(crate) fn write_board_file(
+    project_path: &str,
+    board: &BoardConfig,
+    tasks: &[TasksMdTask],
+) -> Result<String, String> {
+    let content = render_board(tasks, board);
+    write_board_content(project_path, board, &content, tasks)?;
+    Ok(content)
+}
+
+/// Write a board file of any layout whose content holds `tasks`, and record their transitions
+pub(crate) fn write_board_content(
+    project_path: &str,
+    board: &BoardConfig,
+    content: &str,
+    tasks: &[TasksMdTask],
 ) -> Result<(), String> {
-    let tasks_md_path = Path::new(&project_path).join("TASKS.md");
+    let tasks_md_path = board.path(project_path);
+
+    if FRONTEND_WRITE.with(Cell::get) {
+        remember_own_write(&tasks_md_path, content);
+    }
+    fs::write(&tasks_md_path, content)
+        .map_err(|e| format!("Failed to write {}: {}", board.file, e))?;
+
+    if let Err(e) = history::record_transitions(&board.history_key(project_path), tasks) {
+        eprintln!("Failed to record task transitions: {}", e);
+    }
 
-    // Read existing tasks or start with empty list
-    let mut tasks = if tasks_md_path.exists() {
-        let content = fs::read_to_string(&tasks_md_path)
-            .map_err(|e| format!("Failed to read TASKS.md: {}", e))?;
-        parse_tasks_md(&content)?
+    Ok(())
+}
+
+/// Write tasks after a board mutation, moving dependent tasks in or out of Blocked first when
+/// auto-blocking is enabled in settings and the board has a Blocked column. `tasks` is left
+/// as written.
+pub(crate) fn save_board(
+    project_path: &str,
+    board: &BoardConfig,
+    tasks: &mut [TasksMdTask],
+) -> Result<String, String> {
+    let has_blocked = board.columns().iter().any(|c| c.id == "blocked");
+    if has_blocked && settings::get_settings().map(|s| s.auto_block_tasks).unwrap_or(false) {
+        dependencies::apply_auto_blocking(tasks);
+    }
+
+    write_board_file(project_path, board, tasks)
+}
+
+/// Render one task's lines: the checkbox line, its description, comments and subtasks
+pub(crate) fn render_task(task: &TasksMdTask) -> String {
+    let mut content = String::new();
+
+    let checkbox = if task.completed { "[x]" } else { "[ ]" };
+    content.push_str(&format!("- {} {}\n", checkbox, format_task_text(task)));
+
+    for line in render_body(task) {
+        if line.trim().is_empty() {
+            content.push('\n');
+        } else {
+            content.push_str(&format!("  {}\n", line));
+        }
+    }
+
+    content
+}
+
+/// Render tasks with the standard columns
+#[cfg(test)]
+pub(crate) fn render_tasks_md(tasks: &[TasksMdTask]) -> String {
+    render_board(tasks, &BoardConfig::default())
+}
+
+/// Render a board file: its title, then every column's heading and tasks in board order.
+/// Within a column, tasks without an epic come first, then each epic's `###` heading and
+/// tasks in order of first appearance.
+pub(crate) fn render_board(tasks: &[TasksMdTask], board: &BoardConfig) -> String {
+    let mut content = format!("# {}\n\n", board.title());
+
+    // Group tasks by column
+    for column in board.columns() {
+        content.push_str(&format!("## {}\n", column.heading));
+
+        let col_tasks: Vec<&TasksMdTask> = tasks.iter().filter(|t| t.column == column.id).collect();
+
+        for task in col_tasks.iter().filter(|t| t.epic.is_none()) {
+            content.push_str(&render_task(task));
+        }
+
+        let mut epics: Vec<&str> = Vec::new();
+        for epic in col_tasks.iter().filter_map(|t| t.epic.as_deref()) {
+            if !epics.contains(&epic) {
+                epics.push(epic);
+            }
+        }
+
+        for epic in epics {
+            content.push_str(&format!("### {}\n", epic));
+            for task in col_tasks.iter().filter(|t| t.epic.as_deref() == Some(epic)) {
+                content.push_str(&render_task(task));
+            }
+        }
+
+        content.push('\n');
+    }
+
+    content
+}
+
+/// Create a new TASKS.md file with default structure, or an empty board file when `board` is given
+#[tauri::command]
+pub fn create_tasks_md(
+    project_path: String,
+    project_name: String,
+    board: Option<String>,
+    state: tauri::State<'_, crate::state::AppState>,
+) -> Result<(), String> {
+    let board = boards::board_config(&state, &project_path, board.as_deref())?;
+    create_board_file(&project_path, &project_name, &board)
+}
+
+/// Write a new board file unless it exists; TASKS.md starts with the seed tasks
+pub(crate) fn create_board_file(project_path: &str, project_name: &str, board: &BoardConfig) -> Result<(), String> {
+    let tasks_md_path = board.path(project_path);
+
+    // Don't overwrite existing file
+    if tasks_md_path.exists() {
+        return Ok(());
+    }
+
+    let content = if board.is_default() {
+        render_board(&templates::seed_tasks(project_name), board)
     } else {
-        vec![]
+        render_board(&[], board)
     };
 
-    // Create new task
-    let new_task = TasksMdTask {
-        id: Uuid::new_v4().to_string(),
-        subject,
-        description,
-        column: column.clone(),
-        completed: column == "done",
-        line_number: 0, // Will be recalculated on write
-    };
+    if let Some(parent) = tasks_md_path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create directory for {}: {}", board.file, e))?;
+    }
+
+    fs::write(&tasks_md_path, content)
+        .map_err(|e| format!("Failed to create {}: {}", board.file, e))?;
+
+    Ok(())
+}
+
+pub(crate) fn find_task_index(tasks: &[TasksMdTask], subject: &str) -> Result<usize, String> {
+    tasks
+        .iter()
+        .position(|t| t.subject == subject)
+        .ok_or_else(|| format!("Task '{}' not found", subject))
+}
+
+/// Move a task to a different column of a board by subject (since IDs are ephemeral)
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+pub fn move_task_in_tasks_md(
+    project_path: String,
+    board: Option<String>,
+    task_subject: String,
+    new_column: String,
+    base_version: Option<String>,
+    state: tauri::State<'_, crate::state::AppState>,
+    versions: tauri::State<'_, merge::TasksMdVersions>,
+    journal: tauri::State<'_, journal::TaskJournal>,
+) -> Result<TasksMdWriteResult, String> {
+    let mut operation = None;
+    let board = boards::board_config(&state, &project_path, board.as_deref())?;
+    board.check_column(&new_column)?;
+    let result = frontend_write(|| {
+        mutate_board(&project_path, &board, base_version, &versions, true, |tasks| {
+            // Find the task by subject and update its column
+            let index = find_task_index(tasks, &task_subject)?;
+            let before = tasks[index].clone();
+            let t = &mut tasks[index];
+            t.column = new_column.clone();
+            // Update completed status based on column
+            t.completed = new_column == "done";
+            operation = Some(journal::TaskOperation::new("move", index, Some(before), Some(t.clone())));
+            Ok(())
+        })
+    })?;
+
+    journal.record(&project_path, &result, operation.map(|o| o.on_board(&board)));
+    Ok(result)
+}
 
-    tasks.push(new_task);
+/// Add a new task to a board
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+pub fn add_task_to_tasks_md(
+    project_path: String,
+    board: Option<String>,
+    subject: String,
+    description: Option<String>,
+    column: String,
+    base_version: Option<String>,
+    state: tauri::State<'_, crate::state::AppState>,
+    versions: tauri::State<'_, merge::TasksMdVersions>,
+    journal: tauri::State<'_, journal::TaskJournal>,
+) -> Result<TasksMdWriteResult, String> {
+    let mut operation = None;
+    // Writing creates the file if it doesn't exist
+    let board = boards::board_config(&state, &project_path, board.as_deref())?;
+    board.check_column(&column)?;
+    let result = frontend_write(|| {
+        mutate_board(&project_path, &board, base_version, &versions, false, |tasks| {
+            // Create new task
+            let new_task = TasksMdTask::new(&subject, description, &column);
+
+            let subject = new_task.subject.clone();
+            operation = Some(journal::TaskOperation::new("add", tasks.len(), None, Some(new_task.clone())));
+            tasks.push(new_task);
+            dependencies::ensure_acyclic(tasks, &subject)
+        })
+    })?;
+
+    journal.record(&project_path, &result, operation.map(|o| o.on_board(&board)));
+    Ok(result)
+}
 
-    // Write back (this will create the file if it doesn't exist)
-    write_tasks_md(project_path, tasks)
+/// Remove metadata from a task by name: "tags", "priority", "assignee", "estimate", "due" or
+/// "blockedBy"
+fn clear_metadata(task: &mut TasksMdTask, fields: &[String]) -> Result<(), String> {
+    for field in fields {
+        match field.as_str() {
+            "tags" => task.tags.clear(),
+            "priority" => task.priority = None,
+            "assignee" => task.assignee = None,
+            "estimate" => task.estimate = None,
+            "due" => task.due = None,
+            "blockedBy" => task.blocked_by.clear(),
+            other => return Err(format!("Unknown task metadata '{}'", other)),
+        }
+    }
+    Ok(())
 }
 
-/// Update a task in TASKS.md by finding it by its old subject
+/// Update a task on a board by finding it by its old subject. Tokens in the new subject
+/// replace the task's metadata and absent ones are kept; metadata named in `clear` is removed
+/// first.
 #[tauri::command]
+#[allow(clippy::too_many_arguments)]
 pub fn update_task_in_tasks_md(
     project_path: String,
+    board: Option<String>,
     old_subject: String,
     new_subject: String,
     new_description: Option<String>,
-) -> Result<(), String> {
-    let tasks_md_path = Path::new(&project_path).join("TASKS.md");
+    clear: Option<Vec<String>>,
+    base_version: Option<String>,
+    state: tauri::State<'_, crate::state::AppState>,
+    versions: tauri::State<'_, merge::TasksMdVersions>,
+    journal: tauri::State<'_, journal::TaskJournal>,
+) -> Result<TasksMdWriteResult, String> {
+    let mut operation = None;
+    let board = boards::board_config(&state, &project_path, board.as_deref())?;
+    let result = frontend_write(|| {
+        mutate_board(&project_path, &board, base_version, &versions, true, |tasks| {
+            // Find the task by old subject and update it
+            let index = find_task_index(tasks, &old_subject)?;
+            let before = tasks[index].clone();
+            let t = &mut tasks[index];
+            clear_metadata(t, clear.as_deref().unwrap_or_default())?;
+
+            // Tokens in the new subject override the existing metadata; absent ones are kept
+            let (subject, tokens) = split_inline_tokens(&new_subject);
+            t.subject = subject;
+            t.description = new_description;
+            if !tokens.tags.is_empty() {
+                t.tags = tokens.tags;
+            }
+            if tokens.priority.is_some() {
+                t.priority = tokens.priority;
+            }
+            if tokens.assignee.is_some() {
+                t.assignee = tokens.assignee;
+            }
+            if tokens.estimate.is_some() {
+                t.estimate = tokens.estimate;
+            }
+            if tokens.due.is_some() {
+                t.due = tokens.due;
+            }
+            if tokens.stable_id.is_some() {
+                t.stable_id = tokens.stable_id;
+            }
+            if !tokens.blocked_by.is_empty() {
+                t.blocked_by = tokens.blocked_by;
+            }
+
+            let subject = t.subject.clone();
+            operation = Some(journal::TaskOperation::new("update", index, Some(before), Some(t.clone())));
+            dependencies::ensure_acyclic(tasks, &subject)
+        })
+    })?;
+
+    journal.record(&project_path, &result, operation.map(|o| o.on_board(&board)));
+    Ok(result)
+}
+
+/// Delete a task from a board by subject
+#[tauri::command]
+pub fn delete_task_from_tasks_md(
+    project_path: String,
+    board: Option<String>,
+    task_subject: String,
+    base_version: Option<String>,
+    state: tauri::State<'_, crate::state::AppState>,
+    versions: tauri::State<'_, merge::TasksMdVersions>,
+    journal: tauri::State<'_, journal::TaskJournal>,
+) -> Result<TasksMdWriteResult, String> {
+    let mut operation = None;
+    let board = boards::board_config(&state, &project_path, board.as_deref())?;
+    let result = frontend_write(|| {
+        mutate_board(&project_path, &board, base_version, &versions, true, |tasks| {
+            if let Some(index) = tasks.iter().position(|t| t.subject == task_subject) {
+                operation = Some(journal::TaskOperation::new("delete", index, Some(tasks[index].clone()), None));
+            }
+            // Filter out the task with matching subject
+            tasks.retain(|t| t.subject != task_subject);
+            Ok(())
+        })
+    })?;
+
+    journal.record(&project_path, &result, operation.map(|o| o.on_board(&board)));
+    Ok(result)
+}
+
+/// Read and parse an existing board file, failing if the file is missing
+pub(crate) fn load_board(project_path: &str, board: &BoardConfig) -> Result<Vec<TasksMdTask>, String> {
+    let tasks_md_path = board.path(project_path);
 
     if !tasks_md_path.exists() {
-        return Err("TASKS.md does not exist".to_string());
+        return Err(format!("{} does not exist", board.file));
     }
 
     let content = fs::read_to_string(&tasks_md_path)
-        .map_err(|e| format!("Failed to read TASKS.md: {}", e))?;
+        .map_err(|e| format!("Failed to read {}: {}", board.file, e))?;
 
-    let mut tasks = parse_tasks_md(&content)?;
+    parse_board(&content, board)
+}
 
-    // Find the task by old subject and update it
-    let task = tasks.iter_mut().find(|t| t.subject == old_subject);
-    if let Some(t) = task {
-        t.subject = new_subject;
-        t.description = new_description;
-    } else {
-        return Err(format!("Task '{}' not found", old_subject));
+/// Resolve the sibling list and index addressed by a subtask path (child indices from the task)
+fn subtask_siblings<'a>(
+    subtasks: &'a mut Vec<TasksMdSubtask>,
+    path: &[usize],
+) -> Result<(&'a mut Vec<TasksMdSubtask>, usize), String> {
+    let (&index, parents) = path
+        .split_last()
+        .ok_or("Subtask path is empty")?;
+
+    let mut siblings = subtasks;
+    for &p in parents {
+        siblings = &mut siblings
+            .get_mut(p)
+            .ok_or_else(|| format!("Subtask path {:?} not found", path))?
+            .subtasks;
     }
 
-    // Write back
-    write_tasks_md(project_path, tasks)
+    if index >= siblings.len() {
+        return Err(format!("Subtask path {:?} not found", path));
+    }
+
+    Ok((siblings, index))
 }
 
-/// Delete a task from TASKS.md by subject
+fn set_subtask_completed(subtask: &mut TasksMdSubtask, completed: bool) {
+    subtask.completed = completed;
+    for child in subtask.subtasks.iter_mut() {
+        set_subtask_completed(child, completed);
+    }
+}
+
+/// Add a subtask under a task, or under one of its subtasks when `parent_path` is given
 #[tauri::command]
-pub fn delete_task_from_tasks_md(
+#[allow(clippy::too_many_arguments)]
+pub fn add_subtask_to_tasks_md(
     project_path: String,
+    board: Option<String>,
     task_subject: String,
-) -> Result<(), String> {
-    let tasks_md_path = Path::new(&project_path).join("TASKS.md");
+    parent_path: Option<Vec<usize>>,
+    subject: String,
+    base_version: Option<String>,
+    state: tauri::State<'_, crate::state::AppState>,
+    versions: tauri::State<'_, merge::TasksMdVersions>,
+    journal: tauri::State<'_, journal::TaskJournal>,
+) -> Result<TasksMdWriteResult, String> {
+    let mut operation = None;
+    let board = boards::board_config(&state, &project_path, board.as_deref())?;
+    let result = frontend_write(|| {
+        mutate_board(&project_path, &board, base_version, &versions, true, |tasks| {
+            let index = find_task_index(tasks, &task_subject)?;
+            let before = tasks[index].clone();
+            let task = &mut tasks[index];
+
+            let (siblings, parent_id) = match parent_path.as_deref() {
+                Some(path) if !path.is_empty() => {
+                    let (siblings, index) = subtask_siblings(&mut task.subtasks, path)?;
+                    let parent = &mut siblings[index];
+                    (&mut parent.subtasks, parent.id.clone())
+                }
+                _ => (&mut task.subtasks, task.id.clone()),
+            };
+
+            siblings.push(TasksMdSubtask {
+                id: Uuid::new_v4().to_string(),
+                parent_id,
+                subject,
+                completed: false,
+                line_number: 0, // Will be recalculated on write
+                subtasks: vec![],
+                position: None,
+            });
+
+            task.progress = rollup_subtasks(&mut task.subtasks);
+            operation = Some(journal::TaskOperation::new("update", index, Some(before), Some(task.clone())));
+            Ok(())
+        })
+    })?;
+
+    journal.record(&project_path, &result, operation.map(|o| o.on_board(&board)));
+    Ok(result)
+}
 
-    if !tasks_md_path.exists() {
-        return Err("TASKS.md does not exist".to_string());
-    }
+/// Toggle a subtask's checkbox; toggling a subtask with children applies to all of them
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+pub fn toggle_subtask_in_tasks_md(
+    project_path: String,
+    board: Option<String>,
+    task_subject: String,
+    subtask_path: Vec<usize>,
+    base_version: Option<String>,
+    state: tauri::State<'_, crate::state::AppState>,
+    versions: tauri::State<'_, merge::TasksMdVersions>,
+    journal: tauri::State<'_, journal::TaskJournal>,
+) -> Result<TasksMdWriteResult, String> {
+    let mut operation = None;
+    let board = boards::board_config(&state, &project_path, board.as_deref())?;
+    let result = frontend_write(|| {
+        mutate_board(&project_path, &board, base_version, &versions, true, |tasks| {
+            let index = find_task_index(tasks, &task_subject)?;
+            let before = tasks[index].clone();
+            let task = &mut tasks[index];
+
+            let (siblings, index) = subtask_siblings(&mut task.subtasks, &subtask_path)?;
+            let subtask = &mut siblings[index];
+            let completed = !subtask.completed;
+            set_subtask_completed(subtask, completed);
+
+            task.progress = rollup_subtasks(&mut task.subtasks);
+            operation = Some(journal::TaskOperation::new("update", index, Some(before), Some(task.clone())));
+            Ok(())
+        })
+    })?;
+
+    journal.record(&project_path, &result, operation.map(|o| o.on_board(&board)));
+    Ok(result)
+}
 
-    let content = fs::read_to_string(&tasks_md_path)
-        .map_err(|e| format!("Failed to read TASKS.md: {}", e))?;
+/// Move a subtask to a new position among its siblings
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+pub fn reorder_subtask_in_tasks_md(
+    project_path: String,
+    board: Option<String>,
+    task_subject: String,
+    subtask_path: Vec<usize>,
+    new_index: usize,
+    base_version: Option<String>,
+    state: tauri::State<'_, crate::state::AppState>,
+    versions: tauri::State<'_, merge::TasksMdVersions>,
+    journal: tauri::State<'_, journal::TaskJournal>,
+) -> Result<TasksMdWriteResult, String> {
+    let mut operation = None;
+    let board = boards::board_config(&state, &project_path, board.as_deref())?;
+    let result = frontend_write(|| {
+        mutate_board(&project_path, &board, base_version, &versions, true, |tasks| {
+            let index = find_task_index(tasks, &task_subject)?;
+            let before = tasks[index].clone();
+            let task = &mut tasks[index];
+
+            let (siblings, index) = subtask_siblings(&mut task.subtasks, &subtask_path)?;
+            let subtask = siblings.remove(index);
+            let new_index = new_index.min(siblings.len());
+            siblings.insert(new_index, subtask);
+            operation = Some(journal::TaskOperation::new("update", index, Some(before), Some(task.clone())));
+            Ok(())
+        })
+    })?;
+
+    journal.record(&project_path, &result, operation.map(|o| o.on_board(&board)));
+    Ok(result)
+}
+
+/// Payload of `tasks-md-changed`: the tasks that differ from the previous read of a board file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TasksMdChange {
+    #[serde(rename = "projectId")]
+    pub project_id: String,
+    #[serde(rename = "projectPath")]
+    pub project_path: String,
+    /// Board file, relative to the project
+    pub board: String,
+    pub version: String,
+    pub added: Vec<TasksMdTask>,
+    pub updated: Vec<TasksMdTask>,
+    pub removed: Vec<TasksMdTask>,
+}
 
-    let tasks = parse_tasks_md(&content)?;
+/// Tasks added, changed (including moved) and removed between two reads, matched by stable
+/// id or subject
+pub(crate) fn diff_tasks(
+    old: &[TasksMdTask],
+    new: &[TasksMdTask],
+) -> (Vec<TasksMdTask>, Vec<TasksMdTask>, Vec<TasksMdTask>) {
+    let find = |tasks: &[TasksMdTask], task: &TasksMdTask| {
+        tasks.iter().find(|t| merge::task_key(t) == merge::task_key(task)).cloned()
+    };
 
-    // Filter out the task with matching subject
-    let filtered_tasks: Vec<TasksMdTask> = tasks
-        .into_iter()
-        .filter(|t| t.subject != task_subject)
-        .collect();
+    let mut added = Vec::new();
+    let mut updated = Vec::new();
+    for task in new {
+        match find(old, task) {
+            None => added.push(task.clone()),
+            Some(previous) if merge::fingerprint(&previous) != merge::fingerprint(task) => {
+                updated.push(task.clone())
+            }
+            Some(_) => {}
+        }
+    }
+
+    let removed = old.iter().filter(|t| find(new, t).is_none()).cloned().collect();
 
-    // Write back
-    write_tasks_md(project_path, filtered_tasks)
+    (added, updated, removed)
 }
 
-/// Start watching a project's TASKS.md file for changes
+/// Content and tasks of a board file as it is now; a missing file reads as empty
+fn read_board_state(project_path: &str, board: &BoardConfig) -> (String, Vec<TasksMdTask>) {
+    let content = fs::read_to_string(board.path(project_path)).unwrap_or_default();
+    let tasks = board_sources::source_for(board)
+        .and_then(|source| source.parse(&content))
+        .map(|(_, tasks)| tasks)
+        .unwrap_or_default();
+    (content, tasks)
+}
+
+/// Watch all of a project's board files. Bursts of file events are debounced into one re-read
+/// per board, writes the frontend made itself are not echoed back, and `tasks-md-changed`
+/// carries the board and the tasks that changed on it. If the project directory is deleted or renamed the
+/// watcher stops and emits `tasks-md-watch-stopped`.
 #[tauri::command]
 pub fn watch_tasks_md(
     project_id: String,
@@ -565,32 +1577,28 @@ pub fn watch_tasks_md(
     app: AppHandle,
     state: tauri::State<'_, TasksWatcherState>,
 ) -> Result<(), String> {
-    let project_id_clone = project_id.clone();
-    let project_path_clone = project_path.clone();
+    let boards = boards::all_boards_for_path(&app.state::<crate::state::AppState>(), &project_path);
+    let board_files: Vec<String> = boards.iter().map(|b| b.file.clone()).collect();
+
+    // Each message names a board file that changed; an empty one only means "check the directory"
+    let (sender, receiver) = mpsc::channel::<String>();
+    let project_dir = PathBuf::from(&project_path);
 
-    // Create the watcher
     let mut watcher = notify::recommended_watcher(move |res: Result<Event, notify::Error>| {
         match res {
             Ok(event) => {
-                // Only emit for modify/create events on TASKS.md
-                match event.kind {
-                    EventKind::Modify(_) | EventKind::Create(_) => {
-                        // Check if this event is for TASKS.md
-                        let is_tasks_md = event.paths.iter().any(|p| {
-                            p.file_name()
-                                .map(|n| n.to_string_lossy() == "TASKS.md")
-                                .unwrap_or(false)
-                        });
+                if !matches!(event.kind, EventKind::Modify(_) | EventKind::Create(_) | EventKind::Remove(_)) {
+                    return;
+                }
 
-                        if is_tasks_md {
-                            // Emit event to frontend
-                            let _ = app.emit("tasks-md-changed", serde_json::json!({
-                                "projectId": project_id_clone,
-                                "projectPath": project_path_clone,
-                            }));
-                        }
+                for file in &board_files {
+                    if event.paths.iter().any(|p| p.ends_with(file)) {
+                        let _ = sender.send(file.clone());
                     }
-                    _ => {}
+                }
+
+                if !project_dir.is_dir() {
+                    let _ = sender.send(String::new());
                 }
             }
             Err(e) => {
@@ -599,10 +1607,93 @@ pub fn watch_tasks_md(
         }
     }).map_err(|e| format!("Failed to create watcher: {}", e))?;
 
-    // Watch the project directory (not just TASKS.md, to catch file creation)
+    // Watch the project directory (not just TASKS.md, to catch file creation), plus the
+    // directories of boards kept further down
     watcher.watch(Path::new(&project_path), RecursiveMode::NonRecursive)
         .map_err(|e| format!("Failed to watch directory: {}", e))?;
 
+    let mut board_dirs: Vec<PathBuf> = boards
+        .iter()
+        .filter_map(|b| b.path(&project_path).parent().map(Path::to_path_buf))
+        .filter(|dir| dir.as_path() != Path::new(&project_path) && dir.is_dir())
+        .collect();
+    board_dirs.sort();
+    board_dirs.dedup();
+    for dir in board_dirs {
+        if let Err(e) = watcher.watch(&dir, RecursiveMode::NonRecursive) {
+            eprintln!("Failed to watch {}: {}", dir.display(), e);
+        }
+    }
+
+    let mut known: HashMap<String, (String, Vec<TasksMdTask>)> = boards
+        .iter()
+        .map(|board| {
+            let (content, tasks) = read_board_state(&project_path, board);
+            (board.file.clone(), (merge::content_hash(&content), tasks))
+        })
+        .collect();
+    let project_id_clone = project_id.clone();
+
+    // Ends when the watcher, and with it the sender, is dropped
+    thread::spawn(move || {
+        while let Ok(file) = receiver.recv() {
+            let mut pending = vec![file];
+            while let Ok(file) = receiver.recv_timeout(WATCH_DEBOUNCE) {
+                pending.push(file);
+            }
+
+            if !Path::new(&project_path).is_dir() {
+                let _ = app.emit("tasks-md-watch-stopped", serde_json::json!({
+                    "projectId": project_id_clone,
+                    "projectPath": project_path,
+                }));
+                if let Ok(mut watchers) = app.state::<TasksWatcherState>().watchers.lock() {
+                    watchers.remove(&project_id_clone);
+                }
+                break;
+            }
+
+            for board in boards.iter().filter(|b| pending.contains(&b.file)) {
+                let (content, current_tasks) = read_board_state(&project_path, board);
+                let current_version = merge::content_hash(&content);
+                let Some((version, known_tasks)) = known.get_mut(&board.file) else {
+                    continue;
+                };
+                if current_version == *version {
+                    continue;
+                }
+
+                let (added, updated, removed) = diff_tasks(known_tasks, &current_tasks);
+                let own_write = is_own_write(&board.path(&project_path), &current_version);
+
+                *version = current_version;
+                *known_tasks = current_tasks;
+
+                if own_write {
+                    continue;
+                }
+
+                // Catch column changes made outside the app
+                if let Err(e) = history::record_transitions(&board.history_key(&project_path), known_tasks) {
+                    eprintln!("Failed to record task transitions: {}", e);
+                }
+
+                // Keep the version usable as a `base_version` for the next mutation
+                app.state::<merge::TasksMdVersions>().remember(&content);
+
+                let _ = app.emit("tasks-md-changed", TasksMdChange {
+                    project_id: project_id_clone.clone(),
+                    project_path: project_path.clone(),
+                    board: board.file.clone(),
+                    version: version.clone(),
+                    added,
+                    updated,
+                    removed,
+                });
+            }
+        }
+    });
+
     // Store the watcher
     let mut watchers = state.watchers.lock().map_err(|e| e.to_string())?;
     watchers.insert(project_id, watcher);
@@ -610,7 +1701,7 @@ pub fn watch_tasks_md(
     Ok(())
 }
 
-/// Stop watching a project's TASKS.md file
+/// Stop watching a project's board files
 #[tauri::command]
 pub fn unwatch_tasks_md(
     project_id: String,
@@ -625,45 +1716,6 @@ pub fn unwatch_tasks_md(
 mod tests {
     use super::*;
 
-    #[test]
-    fn test_parse_tasks_empty() {
-        let (total, completed) = parse_tasks("");
-        assert_eq!(total, 0);
-        assert_eq!(completed, 0);
-    }
-
-    #[test]
-    fn test_parse_tasks_simple() {
-        let content = r#"
-# Tasks
-- [ ] First task
-- [x] Second task
-- [X] Third task
-- [ ] Fourth task
-"#;
-        let (total, completed) = parse_tasks(content);
-        assert_eq!(total, 4);
-        assert_eq!(completed, 2);
-    }
-
-    #[test]
-    fn test_parse_tasks_ignores_code_blocks() {
-        let content = r#"
-# Tasks
-- [ ] Real task
-
-```markdown
-- [ ] This is in a code block
-- [x] Also in code block
-```
-
-- [x] Another real task
-"#;
-        let (total, completed) = parse_tasks(content);
-        assert_eq!(total, 2);
-        assert_eq!(completed, 1);
-    }
-
     #[test]
     fn test_parse_tasks_md_basic() {
         let content = r#"# TASKS
@@ -752,4 +1804,287 @@ mod tests {
         assert_eq!(tasks[4].subject, "Finished task");
         assert_eq!(tasks[4].column, "done");
     }
+
+    #[test]
+    fn test_parse_tasks_md_inline_metadata() {
+        let content = r#"# TASKS
+
+## Backlog
+- [ ] Fix login redirect #backend #auth !high @agent ~3h
+- [ ] Email @alice about #42
+- [ ] Plain task
+"#;
+        let tasks = parse_tasks_md(content).unwrap();
+        assert_eq!(tasks.len(), 3);
+
+        assert_eq!(tasks[0].subject, "Fix login redirect");
+        assert_eq!(tasks[0].tags, vec!["backend", "auth"]);
+        assert_eq!(tasks[0].priority, Some("high".to_string()));
+        assert_eq!(tasks[0].assignee, Some("agent".to_string()));
+        assert_eq!(tasks[0].estimate, Some("3h".to_string()));
+
+        // Only the trailing run of tokens is metadata
+        assert_eq!(tasks[1].subject, "Email @alice about #42");
+        assert!(tasks[1].tags.is_empty());
+        assert_eq!(tasks[1].assignee, None);
+
+        assert_eq!(tasks[2].subject, "Plain task");
+        assert_eq!(tasks[2].priority, None);
+    }
+
+    #[test]
+    fn test_inline_metadata_round_trip() {
+        let content = r#"# TASKS
+
+## Backlog
+- [ ] Fix login redirect #backend #auth !high @agent ~3h
+
+## Blocked
+
+## In Progress
+
+## Review
+
+## Done
+
+"#;
+        let tasks = parse_tasks_md(content).unwrap();
+        assert_eq!(render_tasks_md(&tasks), content);
+    }
+
+    #[test]
+    fn test_clear_metadata() {
+        let mut task = parse_tasks_md("## Backlog\n- [ ] Fix login #backend !high @agent ~3h due:2024-01-05\n")
+            .unwrap()
+            .remove(0);
+
+        clear_metadata(&mut task, &["tags".to_string(), "due".to_string(), "priority".to_string()]).unwrap();
+        assert_eq!(format_task_text(&task), "Fix login @agent ~3h");
+        assert!(clear_metadata(&mut task, &["color".to_string()]).is_err());
+    }
+
+    #[test]
+    fn test_epic_headings_round_trip() {
+        let content = r#"# TASKS
+
+## Backlog
+- [ ] Loose end
+### Auth
+- [ ] Signup form
+- [ ] Password reset
+
+## Blocked
+
+## In Progress
+### Billing
+- [ ] Invoices
+
+## Review
+
+## Done
+
+"#;
+        let mut tasks = parse_tasks_md(content).unwrap();
+        assert_eq!(tasks[0].epic, None);
+        assert_eq!(tasks[1].epic.as_deref(), Some("Auth"));
+        assert_eq!(tasks[3].epic.as_deref(), Some("Billing"));
+        assert_eq!(render_tasks_md(&tasks), content);
+
+        // A task moved to another column keeps its epic
+        tasks[2].column = "in_progress".to_string();
+        let moved = parse_tasks_md(&render_tasks_md(&tasks)).unwrap();
+        let reset = moved.iter().find(|t| t.subject == "Password reset").unwrap();
+        assert_eq!((reset.column.as_str(), reset.epic.as_deref()), ("in_progress", Some("Auth")));
+    }
+
+    #[test]
+    fn test_markdown_body_round_trip() {
+        let content = r#"# TASKS
+
+## Backlog
+- [ ] Add a config loader
+  Read settings from `config.toml`, see [the spec](docs/config.md).
+
+  > Keep the defaults backwards compatible.
+
+  ```rust
+  fn load() -> Config {
+      // - [ ] not a subtask
+
+      Config::default()
+  }
+  ```
+    Indented note
+  - [ ] Write tests
+- [ ] Next task
+
+## Blocked
+
+## In Progress
+
+## Review
+
+## Done
+
+"#;
+        let tasks = parse_tasks_md(content).unwrap();
+        assert_eq!(tasks.len(), 2);
+        assert_eq!(tasks[0].subtasks.len(), 1);
+
+        let description = tasks[0].description.as_deref().unwrap();
+        assert!(description.starts_with("Read settings"));
+        assert!(description.contains("\n\n> Keep the defaults"));
+        assert!(description.contains("    // - [ ] not a subtask\n\n    Config::default()"));
+        assert!(description.ends_with("```\n  Indented note"));
+
+        assert_eq!(render_tasks_md(&tasks), content);
+    }
+
+    #[test]
+    fn test_parse_tasks_md_nested_subtasks() {
+        let content = r#"# TASKS
+
+## In Progress
+- [ ] Ship release
+  Coordinate with QA
+  - [x] Write changelog
+  - [ ] Publish builds
+    - [x] macOS
+    - [ ] Windows
+- [ ] Next task
+"#;
+        let tasks = parse_tasks_md(content).unwrap();
+        assert_eq!(tasks.len(), 2);
+
+        let task = &tasks[0];
+        assert_eq!(task.description, Some("Coordinate with QA".to_string()));
+        assert_eq!(task.subtasks.len(), 2);
+        assert_eq!(task.subtasks[0].subject, "Write changelog");
+        assert_eq!(task.subtasks[0].parent_id, task.id);
+        assert_eq!(task.subtasks[1].subtasks.len(), 2);
+        assert_eq!(task.subtasks[1].subtasks[0].parent_id, task.subtasks[1].id);
+        assert!(!task.subtasks[1].completed);
+
+        let progress = task.progress.as_ref().unwrap();
+        assert_eq!(progress.total, 3);
+        assert_eq!(progress.completed, 2);
+
+        assert_eq!(tasks[1].subject, "Next task");
+        assert!(tasks[1].progress.is_none());
+    }
+
+    #[test]
+    fn test_subtask_rollup_completes_parent() {
+        let content = r#"# TASKS
+
+## Backlog
+- [ ] Parent
+  - [ ] Group
+    - [x] One
+    - [x] Two
+"#;
+        let tasks = parse_tasks_md(content).unwrap();
+        assert!(tasks[0].subtasks[0].completed);
+        assert!(render_tasks_md(&tasks).contains("  - [x] Group\n    - [x] One\n"));
+
+        // The task itself stays open until it is moved to Done
+        assert!(!tasks[0].completed);
+        assert_eq!(tasks[0].progress.as_ref().unwrap().percentage, 100.0);
+    }
+
+    #[test]
+    fn test_notes_between_subtasks_keep_their_place() {
+        let board = "\n## Blocked\n\n## In Progress\n\n## Review\n\n## Done\n\n";
+        let content = format!(
+            "# TASKS\n\n## Backlog\n- [ ] A\n  - [ ] sub\n  notes\n- [ ] B\n  - [x] First\n    Under first\n    - [x] Nested\n  Between\n  - [ ] Second\n{}",
+            board
+        );
+        let tasks = parse_tasks_md(&content).unwrap();
+
+        assert_eq!(tasks[0].description.as_deref(), Some("notes"));
+        assert_eq!(tasks[1].description.as_deref(), Some("  Under first\nBetween"));
+        assert_eq!(tasks[1].subtasks.len(), 2);
+        assert_eq!(render_tasks_md(&tasks), content);
+
+        // A subtask added later goes after the last one
+        let mut tasks = tasks;
+        let mut added = tasks[0].subtasks[0].clone();
+        added.subject = "sub 2".to_string();
+        added.position = None;
+        tasks[0].subtasks.push(added);
+        assert!(render_task(&tasks[0]).starts_with("- [ ] A\n  - [ ] sub\n  - [ ] sub 2\n  notes\n"));
+    }
+
+    #[test]
+    fn test_tasks_md_filter() {
+        let content = r#"# TASKS
+
+## Backlog
+- [ ] API work #backend !high @agent
+- [ ] UI work #frontend @alice
+"#;
+        let tasks = parse_tasks_md(content).unwrap();
+
+        let filter = TasksMdFilter {
+            tags: vec!["#backend".to_string()],
+            ..Default::default()
+        };
+        let matched: Vec<_> = tasks.iter().filter(|t| filter.matches(t)).collect();
+        assert_eq!(matched.len(), 1);
+        assert_eq!(matched[0].subject, "API work");
+
+        let filter = TasksMdFilter {
+            assignee: Some("@Alice".to_string()),
+            ..Default::default()
+        };
+        let matched: Vec<_> = tasks.iter().filter(|t| filter.matches(t)).collect();
+        assert_eq!(matched.len(), 1);
+        assert_eq!(matched[0].subject, "UI work");
+    }
+
+    #[test]
+    fn test_parse_tasks_md_dependency_tokens() {
+        let content = r#"# TASKS
+
+## Backlog
+- [ ] Deploy service #ops id:deploy blocked-by:build,set-up-ci
+"#;
+        let tasks = parse_tasks_md(content).unwrap();
+        assert_eq!(tasks[0].subject, "Deploy service");
+        assert_eq!(tasks[0].id, "deploy");
+        assert_eq!(tasks[0].stable_id, Some("deploy".to_string()));
+        assert_eq!(tasks[0].blocked_by, vec!["build", "set-up-ci"]);
+        assert_eq!(
+            format_task_text(&tasks[0]),
+            "Deploy service #ops id:deploy blocked-by:build,set-up-ci"
+        );
+    }
+
+    #[test]
+    fn test_diff_tasks() {
+        let old = parse_tasks_md("## Backlog\n- [ ] Keep\n- [ ] Move\n- [ ] Drop\n").unwrap();
+        let new = parse_tasks_md("## Backlog\n- [ ] Keep\n- [ ] New\n\n## Done\n- [x] Move\n").unwrap();
+
+        let (added, updated, removed) = diff_tasks(&old, &new);
+        assert_eq!(added.iter().map(|t| t.subject.as_str()).collect::<Vec<_>>(), vec!["New"]);
+        assert_eq!(updated.len(), 1);
+        assert_eq!(updated[0].column, "done");
+        assert_eq!(removed[0].subject, "Drop");
+    }
+
+    #[test]
+    fn test_own_writes() {
+        let path = Path::new("/tmp/own-writes/TASKS.md");
+        remember_own_write(path, "# TASKS\n");
+        assert!(is_own_write(path, &merge::content_hash("# TASKS\n")));
+        assert!(!is_own_write(Path::new("/tmp/other/TASKS.md"), &merge::content_hash("# TASKS\n")));
+        assert!(!is_own_write(path, &merge::content_hash("# TASKS\n\n")));
+    }
+
+    #[test]
+    fn test_frontend_write_resets_after_panic() {
+        let result = std::panic::catch_unwind(|| frontend_write(|| panic!("write failed")));
+        assert!(result.is_err());
+        assert!(!FRONTEND_WRITE.with(Cell::get));
+    }
 }
@@ -0,0 +1,609 @@
+use chrono::{DateTime, Datelike, Days, Local, NaiveDate, NaiveDateTime, NaiveTime, Offset, TimeZone};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use uuid::Uuid;
+
+use super::boards::{self, BoardConfig};
+use super::history;
+use super::merge;
+use super::tasks::{self, TasksMdSubtask, TasksMdTask, TasksMdWriteResult};
+
+/// Serializes template store updates from commands and the scheduler thread
+static TEMPLATES_LOCK: Mutex<()> = Mutex::new(());
+
+const WEEKDAYS: &[&str] = &["mon", "tue", "wed", "thu", "fri", "sat", "sun"];
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TaskTemplate {
+    #[serde(default)]
+    pub id: String,
+    pub name: String,
+    /// May contain `{project}` and `{date}` (YYYY-MM-DD) placeholders
+    pub subject: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub subtasks: Vec<String>,
+    #[serde(default = "default_column")]
+    pub column: String,
+    /// Add this task to every newly created TASKS.md (global templates only)
+    #[serde(default)]
+    pub seed: bool,
+}
+
+fn default_column() -> String {
+    "backlog".to_string()
+}
+
+/// "Every Monday add 'Dependency audit' to Backlog". Days are local.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecurrenceRule {
+    #[serde(default)]
+    pub id: String,
+    #[serde(rename = "templateId")]
+    pub template_id: String,
+    /// "day", "week" or "month"
+    pub every: String,
+    /// For weekly rules: "mon" … "sun"
+    #[serde(default)]
+    pub weekday: Option<String>,
+    /// For monthly rules: 1–31, clamped to the length of the month
+    #[serde(rename = "dayOfMonth", default)]
+    pub day_of_month: Option<u32>,
+    /// Overrides the template's column
+    #[serde(default)]
+    pub column: Option<String>,
+    /// Board file the tasks are added to; TASKS.md when unset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub board: Option<String>,
+    #[serde(rename = "createdAt", default)]
+    pub created_at: u64,
+    #[serde(rename = "lastRun", default)]
+    pub last_run: Option<u64>,
+}
+
+/// Templates saved globally or for one project; recurrence rules only live in project stores
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TemplateStore {
+    #[serde(default)]
+    pub templates: Vec<TaskTemplate>,
+    #[serde(default)]
+    pub recurrences: Vec<RecurrenceRule>,
+}
+
+fn store_path(project_path: Option<&str>) -> PathBuf {
+    let dir = crate::state::config_dir().join("templates");
+    match project_path {
+        Some(path) => dir.join(format!("{}.json", merge::content_hash(path))),
+        None => dir.join("global.json"),
+    }
+}
+
+fn load_store(project_path: Option<&str>) -> Result<TemplateStore, String> {
+    let path = store_path(project_path);
+
+    if !path.exists() {
+        return Ok(TemplateStore::default());
+    }
+
+    let content = fs::read_to_string(&path)
+        .map_err(|e| format!("Failed to read task templates: {}", e))?;
+
+    serde_json::from_str(&content).map_err(|e| format!("Failed to parse task templates: {}", e))
+}
+
+fn save_store(project_path: Option<&str>, store: &TemplateStore) -> Result<(), String> {
+    let path = store_path(project_path);
+
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create templates directory: {}", e))?;
+    }
+
+    let content = serde_json::to_string_pretty(store)
+        .map_err(|e| format!("Failed to serialize task templates: {}", e))?;
+
+    fs::write(&path, content).map_err(|e| format!("Failed to write task templates: {}", e))
+}
+
+/// Offset of the local time zone from UTC at a timestamp in milliseconds
+pub(crate) fn local_offset_ms(timestamp: u64) -> i64 {
+    DateTime::from_timestamp_millis(timestamp as i64)
+        .map(|utc| Local.offset_from_utc_datetime(&utc.naive_utc()).fix().local_minus_utc() as i64 * 1000)
+        .unwrap_or(0)
+}
+
+/// The calendar date at a timestamp in a time zone `offset_ms` ahead of UTC
+fn date_at_offset(timestamp: u64, offset_ms: i64) -> NaiveDate {
+    DateTime::from_timestamp_millis(timestamp as i64 + offset_ms)
+        .unwrap_or_default()
+        .date_naive()
+}
+
+/// The local calendar date at a timestamp in milliseconds
+pub(crate) fn local_date(timestamp: u64) -> NaiveDate {
+    date_at_offset(timestamp, local_offset_ms(timestamp))
+}
+
+/// YYYY-MM-DD of the local date at a timestamp in milliseconds
+pub(crate) fn format_date(timestamp: u64) -> String {
+    local_date(timestamp).format("%Y-%m-%d").to_string()
+}
+
+/// Milliseconds at the start (UTC) of a YYYY-MM-DD date
+pub(crate) fn parse_date(date: &str) -> Option<u64> {
+    if date.len() != 10 {
+        return None;
+    }
+    let date = NaiveDate::parse_from_str(date, "%Y-%m-%d").ok()?;
+    u64::try_from(date.and_time(NaiveTime::MIN).and_utc().timestamp_millis()).ok()
+}
+
+/// YYYY-MM-DDTHH:MM:SSZ for a timestamp in milliseconds
+pub(crate) fn format_timestamp(timestamp: u64) -> String {
+    DateTime::from_timestamp_millis(timestamp as i64)
+        .unwrap_or_default()
+        .format("%Y-%m-%dT%H:%M:%SZ")
+        .to_string()
+}
+
+/// Milliseconds of a YYYY-MM-DDTHH:MM:SSZ timestamp
+pub(crate) fn parse_timestamp(timestamp: &str) -> Option<u64> {
+    if timestamp.len() != 20 {
+        return None;
+    }
+    let time = NaiveDateTime::parse_from_str(timestamp, "%Y-%m-%dT%H:%M:%SZ").ok()?;
+    u64::try_from(time.and_utc().timestamp_millis()).ok()
+}
+
+fn validate_rule(rule: &RecurrenceRule) -> Result<(), String> {
+    match rule.every.as_str() {
+        "day" => Ok(()),
+        "week" => match rule.weekday.as_deref() {
+            Some(day) if WEEKDAYS.contains(&day) => Ok(()),
+            _ => Err("Weekly recurrences need a weekday (mon … sun)".to_string()),
+        },
+        "month" => match rule.day_of_month {
+            Some(1..=31) => Ok(()),
+            _ => Err("Monthly recurrences need a day of the month (1–31)".to_string()),
+        },
+        other => Err(format!("Unknown recurrence '{}'", other)),
+    }
+}
+
+/// A project template can go to any of the project's `boards`, so its column has to exist on
+/// one of them; global templates (and the seeds among them) use the standard columns
+fn validate_template_column(template: &TaskTemplate, boards: Option<&[BoardConfig]>) -> Result<(), String> {
+    match boards {
+        Some(boards) => {
+            if boards
+                .iter()
+                .any(|b| b.check_column(&template.column).is_ok())
+            {
+                Ok(())
+            } else {
+                Err(format!("No board of this project has a column '{}'", template.column))
+            }
+        }
+        None => BoardConfig::default().check_column(&template.column),
+    }
+}
+
+/// Last day of a date's month
+fn last_day_of_month(date: NaiveDate) -> u32 {
+    (28..=31)
+        .rev()
+        .find(|&day| date.with_day(day).is_some())
+        .unwrap_or(28)
+}
+
+fn occurs_on(rule: &RecurrenceRule, day: NaiveDate) -> bool {
+    match rule.every.as_str() {
+        "day" => true,
+        "week" => {
+            let weekday = WEEKDAYS[day.weekday().num_days_from_monday() as usize];
+            rule.weekday.as_deref() == Some(weekday)
+        }
+        "month" => {
+            let target = rule.day_of_month.unwrap_or(1).min(last_day_of_month(day));
+            day.day() == target
+        }
+        _ => false,
+    }
+}
+
+/// A rule is due when it occurs on a local day since it was created that it hasn't run on yet.
+/// Only today and the previous week are checked, so a long-closed app catches up once.
+fn is_due(rule: &RecurrenceRule, now: u64) -> bool {
+    is_due_at_offset(rule, now, local_offset_ms(now))
+}
+
+/// `is_due` with days counted in a time zone `offset_ms` ahead of UTC
+fn is_due_at_offset(rule: &RecurrenceRule, now: u64, offset_ms: i64) -> bool {
+    let today = date_at_offset(now, offset_ms);
+    let created = date_at_offset(rule.created_at, offset_ms);
+    let last_run = rule.last_run.map(|t| date_at_offset(t, offset_ms));
+
+    (0..7)
+        .filter_map(|n| today.checked_sub_days(Days::new(n)))
+        .find(|&day| occurs_on(rule, day))
+        .map(|day| day >= created && last_run.map(|r| r < day).unwrap_or(true))
+        .unwrap_or(false)
+}
+
+/// Turn a template into a task, filling in placeholders
+fn instantiate(template: &TaskTemplate, project_name: &str, column: Option<&str>, now: u64) -> TasksMdTask {
+    let fill = |text: &str| text.replace("{project}", project_name).replace("{date}", &format_date(now));
+
+    let column = column.unwrap_or(&template.column);
+    let mut task = TasksMdTask::new(&fill(&template.subject), template.description.as_deref().map(fill), column);
+    task.completed = column == "done";
+    task.subtasks = template
+        .subtasks
+        .iter()
+        .map(|subject| TasksMdSubtask {
+            id: Uuid::new_v4().to_string(),
+            parent_id: task.id.clone(),
+            subject: fill(subject),
+            completed: false,
+            line_number: 0,
+            subtasks: vec![],
+            position: None,
+        })
+        .collect();
+    task
+}
+
+fn project_name(project_path: &str) -> String {
+    std::path::Path::new(project_path)
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default()
+}
+
+/// Look a template up in the project store first, then the global one
+fn find_template(project_path: &str, template_id: &str) -> Result<TaskTemplate, String> {
+    let project = load_store(Some(project_path))?;
+    let global = load_store(None)?;
+
+    project
+        .templates
+        .into_iter()
+        .chain(global.templates)
+        .find(|t| t.id == template_id)
+        .ok_or_else(|| format!("Template '{}' not found", template_id))
+}
+
+/// The tasks a new TASKS.md starts with: global seed templates, or the classic setup task
+pub fn seed_tasks(project_name: &str) -> Vec<TasksMdTask> {
+    let now = history::now_ms();
+    let seeds: Vec<TaskTemplate> = load_store(None)
+        .map(|store| store.templates.into_iter().filter(|t| t.seed).collect())
+        .unwrap_or_default();
+
+    if seeds.is_empty() {
+        let default = TaskTemplate {
+            id: String::new(),
+            name: "Project setup".to_string(),
+            subject: "Set up {project} project".to_string(),
+            description: None,
+            subtasks: vec![],
+            column: default_column(),
+            seed: true,
+        };
+        return vec![instantiate(&default, project_name, None, now)];
+    }
+
+    seeds.iter().map(|t| instantiate(t, project_name, None, now)).collect()
+}
+
+/// Add the tasks of every due recurrence rule to its board. A rule whose task is still open
+/// from last time doesn't add a second copy. Returns the subjects added.
+pub fn apply_due_recurrences(
+    state: &crate::state::AppState,
+    versions: &merge::TasksMdVersions,
+    project_path: &str,
+    now: u64,
+) -> Result<Vec<String>, String> {
+    let _guard = TEMPLATES_LOCK.lock().map_err(|e| e.to_string())?;
+
+    let mut store = load_store(Some(project_path))?;
+    if !store.recurrences.iter().any(|r| is_due(r, now)) {
+        return Ok(vec![]);
+    }
+
+    let global = load_store(None)?;
+    let name = project_name(project_path);
+    let mut boards: Vec<(BoardConfig, Vec<TasksMdTask>)> = Vec::new();
+
+    for rule in store.recurrences.iter_mut().filter(|r| is_due(r, now)) {
+        rule.last_run = Some(now);
+
+        let Some(template) = store
+            .templates
+            .iter()
+            .chain(&global.templates)
+            .find(|t| t.id == rule.template_id)
+        else {
+            continue;
+        };
+
+        // A rule saved before its board or column went away is skipped rather than failing
+        // every other rule of the project
+        let column = rule.column.as_deref().unwrap_or(&template.column);
+        let board = match boards::board_config(state, project_path, rule.board.as_deref())
+            .and_then(|board| board.check_column(column).map(|_| board))
+        {
+            Ok(board) => board,
+            Err(e) => {
+                eprintln!("Skipping recurrence '{}': {}", rule.id, e);
+                continue;
+            }
+        };
+        let task = instantiate(template, &name, Some(column), now);
+
+        match boards.iter_mut().find(|(b, _)| b.file == board.file) {
+            Some((_, new_tasks)) => new_tasks.push(task),
+            None => boards.push((board, vec![task])),
+        }
+    }
+
+    let mut added = Vec::new();
+    for (board, new_tasks) in boards {
+        // Merged into whatever is on disk now, so edits made meanwhile aren't overwritten
+        tasks::mutate_board(project_path, &board, None, versions, false, |tasks| {
+            for task in new_tasks {
+                let still_open = tasks
+                    .iter()
+                    .any(|t| t.subject == task.subject && t.column != "done" && !t.completed);
+                if !still_open {
+                    added.push(task.subject.clone());
+                    tasks.push(task);
+                }
+            }
+            Ok(())
+        })?;
+    }
+    save_store(Some(project_path), &store)?;
+
+    Ok(added)
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TemplateList {
+    pub global: Vec<TaskTemplate>,
+    pub project: Vec<TaskTemplate>,
+    pub recurrences: Vec<RecurrenceRule>,
+}
+
+/// Global templates, plus a project's own templates and recurrence rules
+#[tauri::command]
+pub fn list_task_templates(project_path: Option<String>) -> Result<TemplateList, String> {
+    let global = load_store(None)?;
+    let project = match &project_path {
+        Some(path) => load_store(Some(path))?,
+        None => TemplateStore::default(),
+    };
+
+    Ok(TemplateList {
+        global: global.templates,
+        project: project.templates,
+        recurrences: project.recurrences,
+    })
+}
+
+/// Create or replace a template; without a project path it is saved globally
+#[tauri::command]
+pub fn save_task_template(
+    template: TaskTemplate,
+    project_path: Option<String>,
+    state: tauri::State<'_, crate::state::AppState>,
+) -> Result<TaskTemplate, String> {
+    if template.subject.trim().is_empty() {
+        return Err("Template subject cannot be empty".to_string());
+    }
+    let project_boards = project_path.as_deref().map(|path| boards::boards_for_path(&state, path));
+    validate_template_column(&template, project_boards.as_deref())?;
+
+    let _guard = TEMPLATES_LOCK.lock().map_err(|e| e.to_string())?;
+    let mut store = load_store(project_path.as_deref())?;
+
+    let mut template = template;
+    if template.id.is_empty() {
+        template.id = Uuid::new_v4().to_string();
+    }
+
+    match store.templates.iter_mut().find(|t| t.id == template.id) {
+        Some(existing) => *existing = template.clone(),
+        None => store.templates.push(template.clone()),
+    }
+
+    save_store(project_path.as_deref(), &store)?;
+    Ok(template)
+}
+
+/// Delete a template, and in a project store the recurrence rules that use it
+#[tauri::command]
+pub fn delete_task_template(template_id: String, project_path: Option<String>) -> Result<(), String> {
+    let _guard = TEMPLATES_LOCK.lock().map_err(|e| e.to_string())?;
+    let mut store = load_store(project_path.as_deref())?;
+
+    let before = store.templates.len();
+    store.templates.retain(|t| t.id != template_id);
+    if store.templates.len() == before {
+        return Err(format!("Template '{}' not found", template_id));
+    }
+    store.recurrences.retain(|r| r.template_id != template_id);
+
+    save_store(project_path.as_deref(), &store)
+}
+
+/// Add a task made from a template to one of a project's boards
+#[tauri::command]
+pub fn instantiate_task_template(
+    project_path: String,
+    board: Option<String>,
+    template_id: String,
+    column: Option<String>,
+    base_version: Option<String>,
+    state: tauri::State<'_, crate::state::AppState>,
+    versions: tauri::State<'_, merge::TasksMdVersions>,
+) -> Result<TasksMdWriteResult, String> {
+    let board = boards::board_config(&state, &project_path, board.as_deref())?;
+    let template = find_template(&project_path, &template_id)?;
+    let column = column.as_deref().unwrap_or(&template.column);
+    board.check_column(column)?;
+    let task = instantiate(&template, &project_name(&project_path), Some(column), history::now_ms());
+
+    tasks::frontend_write(|| {
+        tasks::mutate_board(&project_path, &board, base_version, &versions, false, |tasks| {
+            tasks.push(task);
+            Ok(())
+        })
+    })
+}
+
+/// Create or replace a project's recurrence rule
+#[tauri::command]
+pub fn save_recurrence_rule(
+    project_path: String,
+    rule: RecurrenceRule,
+    state: tauri::State<'_, crate::state::AppState>,
+) -> Result<RecurrenceRule, String> {
+    validate_rule(&rule)?;
+    let template = find_template(&project_path, &rule.template_id)?;
+    boards::board_config(&state, &project_path, rule.board.as_deref())?
+        .check_column(rule.column.as_deref().unwrap_or(&template.column))?;
+
+    let _guard = TEMPLATES_LOCK.lock().map_err(|e| e.to_string())?;
+    let mut store = load_store(Some(&project_path))?;
+
+    let mut rule = rule;
+    if rule.id.is_empty() {
+        rule.id = Uuid::new_v4().to_string();
+    }
+
+    match store.recurrences.iter_mut().find(|r| r.id == rule.id) {
+        Some(existing) => {
+            rule.created_at = existing.created_at;
+            rule.last_run = existing.last_run;
+            *existing = rule.clone();
+        }
+        None => {
+            rule.created_at = history::now_ms();
+            rule.last_run = None;
+            store.recurrences.push(rule.clone());
+        }
+    }
+
+    save_store(Some(&project_path), &store)?;
+    Ok(rule)
+}
+
+#[tauri::command]
+pub fn delete_recurrence_rule(project_path: String, rule_id: String) -> Result<(), String> {
+    let _guard = TEMPLATES_LOCK.lock().map_err(|e| e.to_string())?;
+    let mut store = load_store(Some(&project_path))?;
+
+    store.recurrences.retain(|r| r.id != rule_id);
+    save_store(Some(&project_path), &store)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::history::DAY_MS;
+
+    // 2024-01-01 was a Monday
+    const MONDAY: u64 = 19_723 * DAY_MS;
+
+    fn rule(every: &str) -> RecurrenceRule {
+        RecurrenceRule {
+            id: "r".to_string(),
+            template_id: "t".to_string(),
+            every: every.to_string(),
+            weekday: None,
+            day_of_month: None,
+            column: None,
+            board: None,
+            created_at: MONDAY - 30 * DAY_MS,
+            last_run: None,
+        }
+    }
+
+    #[test]
+    fn test_format_date() {
+        assert_eq!(date_at_offset(0, 0).to_string(), "1970-01-01");
+        assert_eq!(date_at_offset(MONDAY + 59 * DAY_MS, 0).to_string(), "2024-02-29");
+        // A second before midnight UTC is already the next day an hour ahead of UTC
+        assert_eq!(date_at_offset(MONDAY - 1000, 3_600_000).to_string(), "2024-01-01");
+        assert_eq!(date_at_offset(MONDAY - 1000, 0).to_string(), "2023-12-31");
+
+        assert_eq!(parse_date("2024-02-29"), Some(MONDAY + 59 * DAY_MS));
+        assert_eq!(parse_date("2023-02-29"), None);
+        assert_eq!(format_timestamp(MONDAY + 45_296_000), "2024-01-01T12:34:56Z");
+        assert_eq!(parse_timestamp("2024-01-01T12:34:56Z"), Some(MONDAY + 45_296_000));
+    }
+
+    #[test]
+    fn test_weekly_rule() {
+        let mut weekly = rule("week");
+        weekly.weekday = Some("mon".to_string());
+
+        assert!(is_due_at_offset(&weekly, MONDAY + 1000, 0));
+        // Catches up later in the week, but only once
+        assert!(is_due_at_offset(&weekly, MONDAY + 3 * DAY_MS, 0));
+        weekly.last_run = Some(MONDAY + 1000);
+        assert!(!is_due_at_offset(&weekly, MONDAY + 3 * DAY_MS, 0));
+        assert!(is_due_at_offset(&weekly, MONDAY + 7 * DAY_MS, 0));
+
+        // A rule created mid-week waits for the next Monday
+        weekly.last_run = None;
+        weekly.created_at = MONDAY + 2 * DAY_MS;
+        assert!(!is_due_at_offset(&weekly, MONDAY + 3 * DAY_MS, 0));
+
+        // Just after midnight UTC on Monday is still Sunday eight hours behind UTC
+        weekly.created_at = MONDAY - DAY_MS;
+        assert!(is_due_at_offset(&weekly, MONDAY + 1000, 0));
+        assert!(!is_due_at_offset(&weekly, MONDAY + 1000, -8 * 3_600_000));
+    }
+
+    #[test]
+    fn test_monthly_rule_clamps_to_month_end() {
+        let mut monthly = rule("month");
+        monthly.day_of_month = Some(31);
+        // 2024-02-29
+        assert!(is_due_at_offset(&monthly, MONDAY + 59 * DAY_MS, 0));
+        assert!(!is_due_at_offset(&monthly, MONDAY + 58 * DAY_MS - 7 * DAY_MS, 0));
+        assert!(validate_rule(&rule("week")).is_err());
+    }
+
+    #[test]
+    fn test_instantiate_template() {
+        let template = TaskTemplate {
+            id: "t".to_string(),
+            name: "Audit".to_string(),
+            subject: "Dependency audit {date} #maintenance".to_string(),
+            description: Some("For {project}".to_string()),
+            subtasks: vec!["Run cargo audit".to_string()],
+            column: default_column(),
+            seed: false,
+        };
+        let now = MONDAY + DAY_MS / 2;
+        let task = instantiate(&template, "station", None, now);
+
+        // {date} is the local date
+        assert_eq!(task.subject, format!("Dependency audit {}", local_date(now)));
+        assert_eq!(task.tags, vec!["maintenance"]);
+        assert_eq!(task.description.as_deref(), Some("For station"));
+        assert_eq!(task.subtasks[0].parent_id, task.id);
+        assert_eq!(task.column, "backlog");
+
+        assert!(validate_template_column(&template, None).is_ok());
+        let mut custom = template;
+        custom.column = "triage".to_string();
+        assert!(validate_template_column(&custom, None).is_err());
+    }
+}
@@ -29,6 +29,9 @@ pub struct TerminalInstance {
     pub writer: Arc<Mutex<Box<dyn Write + Send>>>,
     pub master: Arc<Mutex<Box<dyn MasterPty + Send>>>,
     pub running: Arc<Mutex<bool>>,
+    /// Whether the PTY runs the agent itself rather than a shell, so input reaches the agent
+    /// for as long as the terminal is running
+    pub agent: bool,
 }
 
 pub struct TerminalManager {
@@ -55,6 +58,18 @@ pub fn spawn_terminal(
     cwd: String,
     app_handle: AppHandle,
     state: tauri::State<'_, TerminalManager>,
+) -> Result<String, String> {
+    spawn_terminal_process(project_id, cwd, None, app_handle, &state)
+}
+
+/// Spawn a login shell in a PTY for a project and stream its output to the frontend. With
+/// `command` the shell runs just that command line and the terminal exits along with it.
+pub(crate) fn spawn_terminal_process(
+    project_id: String,
+    cwd: String,
+    command: Option<&str>,
+    app_handle: AppHandle,
+    state: &TerminalManager,
 ) -> Result<String, String> {
     let pty_system = native_pty_system();
 
@@ -73,6 +88,10 @@ pub fn spawn_terminal(
     // Spawn as login shell to load user's profile (PATH, etc.)
     let mut cmd = CommandBuilder::new(&shell);
     cmd.arg("-l"); // Login shell flag
+    if let Some(command) = command {
+        cmd.arg("-c");
+        cmd.arg(format!("exec {}", command));
+    }
     cmd.cwd(&cwd);
 
     // Set environment variables for a better terminal experience
@@ -115,6 +134,7 @@ pub fn spawn_terminal(
                 writer: writer.clone(),
                 master: master.clone(),
                 running: running.clone(),
+                agent: command.is_some(),
             },
         );
     }
@@ -176,10 +196,14 @@ pub fn write_terminal(
     data: String,
     state: tauri::State<'_, TerminalManager>,
 ) -> Result<(), String> {
+    write_to_terminal(&terminal_id, &data, &state)
+}
+
+pub(crate) fn write_to_terminal(terminal_id: &str, data: &str, state: &TerminalManager) -> Result<(), String> {
     let terminals = state.terminals.lock().map_err(|e| e.to_string())?;
 
     let terminal = terminals
-        .get(&terminal_id)
+        .get(terminal_id)
         .ok_or_else(|| "Terminal not found".to_string())?;
 
     let mut writer = terminal.writer.lock().map_err(|e| e.to_string())?;
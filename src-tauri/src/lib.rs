@@ -1,7 +1,7 @@
 mod commands;
 mod state;
 
-use commands::{filesystem, notifications, projects, settings, tasks, terminal};
+use commands::{archive, board_sources, boards, claude_tasks, comments, commits, dependencies, dispatch, epics, filesystem, history, import_export, journal, lint, merge, notifications, progress, projects, query, reminders, scheduler, settings, sync, task_graph, tasks, templates, terminal};
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
@@ -13,6 +13,15 @@ pub fn run() {
         .manage(state::AppState::new())
         .manage(terminal::TerminalManager::new())
         .manage(tasks::TasksWatcherState::new())
+        .manage(claude_tasks::ClaudeTasksWatcherState::new())
+        .manage(merge::TasksMdVersions::new())
+        .manage(journal::TaskJournal::new())
+        .manage(query::TaskIndex::new())
+        .setup(|app| {
+            scheduler::start_scheduler(app.handle().clone());
+            reminders::start_reminder_scheduler(app.handle().clone());
+            Ok(())
+        })
         .invoke_handler(tauri::generate_handler![
             // Project commands
             projects::get_projects,
@@ -30,16 +39,87 @@ pub fn run() {
             // Task commands
             tasks::get_task_progress,
             tasks::get_claude_task_progress,
+            // Claude Code task list commands
+            claude_tasks::watch_claude_tasks,
+            claude_tasks::unwatch_claude_tasks,
+            claude_tasks::create_claude_task,
+            claude_tasks::update_claude_task,
+            claude_tasks::set_claude_task_status,
+            claude_tasks::reorder_claude_tasks,
+            claude_tasks::delete_claude_task,
+            task_graph::get_task_graph,
+            // Task template commands
+            templates::list_task_templates,
+            templates::save_task_template,
+            templates::delete_task_template,
+            templates::instantiate_task_template,
+            templates::save_recurrence_rule,
+            templates::delete_recurrence_rule,
             // TASKS.md commands
             tasks::read_tasks_md,
+            tasks::read_tasks_md_snapshot,
             tasks::write_tasks_md,
             tasks::create_tasks_md,
             tasks::move_task_in_tasks_md,
             tasks::add_task_to_tasks_md,
             tasks::update_task_in_tasks_md,
             tasks::delete_task_from_tasks_md,
+            tasks::add_subtask_to_tasks_md,
+            tasks::toggle_subtask_in_tasks_md,
+            tasks::reorder_subtask_in_tasks_md,
+            dependencies::check_tasks_md_dependencies,
+            dependencies::refresh_tasks_md_blocking,
+            // Undo / redo commands
+            journal::undo_task_operation,
+            journal::redo_task_operation,
+            journal::get_task_journal,
+            // Claude Code task sync commands
+            sync::sync_claude_tasks,
+            sync::resolve_sync_conflict,
+            // Task history commands
+            history::get_task_history,
+            history::get_task_cycle_times,
+            history::get_task_lead_times,
+            history::get_task_throughput,
+            history::get_task_column_times,
+            // Cross-project query commands
+            query::query_tasks,
+            query::upcoming_tasks,
+            // Import / export commands
+            import_export::export_tasks,
+            import_export::import_tasks,
+            // Board commands
+            boards::list_boards,
+            boards::set_project_boards,
+            // Epic commands
+            epics::get_epic_progress,
+            epics::set_task_epic,
+            // Comment commands
+            comments::add_task_comment,
+            comments::list_task_comments,
+            // Board source commands
+            board_sources::list_board_sources,
+            board_sources::set_board_source,
+            board_sources::read_board,
+            board_sources::move_board_task,
+            // Checkbox progress commands
+            progress::get_task_progress_breakdown,
+            progress::set_progress_globs,
+            // Task archive commands
+            archive::set_archive_policy,
+            archive::archive_tasks,
+            archive::read_task_archive,
+            archive::restore_archived_task,
+            // TASKS.md lint commands
+            lint::lint_tasks_md,
+            lint::format_tasks_md,
             tasks::watch_tasks_md,
             tasks::unwatch_tasks_md,
+            // Agent dispatch commands
+            dispatch::dispatch_task,
+            // Git commit link commands
+            commits::get_task_commits,
+            commits::advance_tasks_from_git,
             // Terminal commands
             terminal::spawn_terminal,
             terminal::write_terminal,
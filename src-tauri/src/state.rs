@@ -22,7 +22,8 @@ impl Default for AppState {
     }
 }
 
-fn get_config_path() -> PathBuf {
+/// Agent Station's config directory, created on first use
+pub fn config_dir() -> PathBuf {
     let config_dir = dirs::config_dir()
         .unwrap_or_else(|| PathBuf::from("."))
         .join("agent-station");
@@ -31,7 +32,11 @@ fn get_config_path() -> PathBuf {
         let _ = fs::create_dir_all(&config_dir);
     }
 
-    config_dir.join("projects.json")
+    config_dir
+}
+
+fn get_config_path() -> PathBuf {
+    config_dir().join("projects.json")
 }
 
 pub fn load_projects() -> Result<Vec<Project>, String> {
@@ -12,7 +12,7 @@ import { AddTaskModal } from './AddTaskModal';
 import { EditTaskModal } from './EditTaskModal';
 import { TaskDetailModal } from './TaskDetailModal';
 import { useTasksMdOperations } from '../hooks/useTasksMdOperations';
-import type { TasksMdTask } from '../types';
+import type { TasksMdSnapshot, TasksMdWriteResult } from '../types';
 import {
   mapHumanTasksToKanban,
   mapTasksMdToKanban,
@@ -34,6 +34,7 @@ export function KanbanBoard({ projectId }: KanbanBoardProps) {
     addHumanTask,
     deleteHumanTask,
     tasksMdTasks,
+    tasksMdVersions,
     projects,
     setTasksMdTasks,
     zoomLevel,
@@ -45,24 +46,34 @@ export function KanbanBoard({ projectId }: KanbanBoardProps) {
 
   const projectKanban = kanbanState[projectId] || { taskOverlays: {}, humanTasks: [] };
   const projectTasksMd = tasksMdTasks[projectId] || [];
+  const tasksMdVersion = tasksMdVersions[projectId];
   const project = projects.find((p) => p.id === projectId);
 
   // TASKS.md operations
-  const { addTask, updateTask, deleteTask } = useTasksMdOperations(project?.path);
+  const { addTask, updateTask, deleteTask, moveTask: moveTasksMdTask } = useTasksMdOperations(project?.path);
 
-  // Manual refresh of TASKS.md (fallback when watcher doesn't trigger)
+  // Refresh TASKS.md after our own writes; the watcher doesn't echo them back
   const refreshTasksMd = async () => {
     if (!project) return;
     try {
-      const tasks = await invoke<TasksMdTask[]>('read_tasks_md', {
+      const snapshot = await invoke<TasksMdSnapshot>('read_tasks_md_snapshot', {
         projectPath: project.path,
       });
-      setTasksMdTasks(projectId, tasks);
+      setTasksMdTasks(projectId, snapshot.tasks, snapshot.version);
     } catch (err) {
       console.error('Failed to refresh TASKS.md:', err);
     }
   };
 
+  // Conflicting edits are not written; reloading shows what is in TASKS.md now
+  const handleWriteResult = async (result: TasksMdWriteResult) => {
+    if (result.conflicts.length > 0) {
+      const subjects = result.conflicts.map((c) => c.subject).join(', ');
+      alert(`TASKS.md was changed elsewhere and your edit conflicts with it. Not saved: ${subjects}`);
+    }
+    await refreshTasksMd();
+  };
+
   // Map all tasks to Kanban format (TASKS.md only, no Claude ~/.claude/tasks)
   const allTasks = useMemo(() => {
     const humanTasks = mapHumanTasksToKanban(projectKanban.humanTasks);
@@ -88,13 +99,7 @@ export function KanbanBoard({ projectId }: KanbanBoardProps) {
       // Extract the subject from the ID (format: tasksmd-{subject})
       const taskSubject = task.subject;
       try {
-        await invoke('move_task_in_tasks_md', {
-          projectPath: project.path,
-          taskSubject,
-          newColumn,
-        });
-        // Manually refresh to ensure UI updates (watcher may not trigger immediately)
-        await refreshTasksMd();
+        await handleWriteResult(await moveTasksMdTask(taskSubject, newColumn, tasksMdVersion));
       } catch (err) {
         console.error('Failed to move TASKS.md task:', err);
       }
@@ -107,12 +112,15 @@ export function KanbanBoard({ projectId }: KanbanBoardProps) {
   const handleAddTask = async (task: Omit<HumanTask, 'id' | 'createdAt'>) => {
     // Write to TASKS.md instead of localStorage
     try {
-      await addTask({
-        subject: task.subject,
-        description: task.description,
-        column: task.column,
-      });
-      // The file watcher will automatically update the UI
+      const result = await addTask(
+        {
+          subject: task.subject,
+          description: task.description,
+          column: task.column,
+        },
+        tasksMdVersion
+      );
+      await handleWriteResult(result);
     } catch (err) {
       console.error('Failed to add task to TASKS.md:', err);
       // Fallback to localStorage if TASKS.md fails
@@ -125,8 +133,7 @@ export function KanbanBoard({ projectId }: KanbanBoardProps) {
     if (task.isTasksMdTask) {
       // Delete from TASKS.md
       try {
-        await deleteTask(task.subject);
-        // The file watcher will automatically update the UI
+        await handleWriteResult(await deleteTask(task.subject, tasksMdVersion));
       } catch (err) {
         console.error('Failed to delete task from TASKS.md:', err);
       }
@@ -138,8 +145,7 @@ export function KanbanBoard({ projectId }: KanbanBoardProps) {
 
   const handleEditTask = async (oldSubject: string, newSubject: string, newDescription?: string) => {
     try {
-      await updateTask(oldSubject, newSubject, newDescription);
-      // The file watcher will automatically update the UI
+      await handleWriteResult(await updateTask(oldSubject, newSubject, newDescription, tasksMdVersion));
     } catch (err) {
       console.error('Failed to update task in TASKS.md:', err);
     }
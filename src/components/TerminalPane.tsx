@@ -18,6 +18,13 @@ interface TerminalInfo {
   isRunning: boolean;
 }
 
+interface TaskDispatched {
+  projectId: string;
+  terminalId: string;
+  subject: string;
+  spawned: boolean;
+}
+
 interface TerminalInstance {
   terminal: Terminal;
   fitAddon: FitAddon;
@@ -280,6 +287,35 @@ export function TerminalPane() {
     initialize();
   }, [selectedProjectId, selectedProject?.path, projectTerminalIds.length, createTerminalInstance, addTerminalToProject, updateProjectProcessStatus, spawnTerminal]);
 
+  // Show terminals that dispatch_task spawned for an agent
+  useEffect(() => {
+    let unlisten: UnlistenFn | null = null;
+    let isActive = true;
+
+    listen<TaskDispatched>("task-dispatched", (event) => {
+      const { projectId, terminalId, spawned } = event.payload;
+      if (!spawned) return;
+
+      if (!terminalsRef.current.has(terminalId)) {
+        createTerminalInstance(terminalId);
+      }
+      addTerminalToProject(projectId, terminalId);
+      updateProjectProcessStatus(projectId, true);
+      spawnedProjectsRef.current.add(projectId);
+    }).then((fn) => {
+      if (isActive) {
+        unlisten = fn;
+      } else {
+        fn();
+      }
+    });
+
+    return () => {
+      isActive = false;
+      if (unlisten) unlisten();
+    };
+  }, [createTerminalInstance, addTerminalToProject, updateProjectProcessStatus]);
+
   // Handle displaying and hiding terminals when active terminal changes
   useEffect(() => {
     if (!containerRef.current) return;
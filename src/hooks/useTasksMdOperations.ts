@@ -1,49 +1,77 @@
 import { useCallback } from 'react';
 import { invoke } from '@tauri-apps/api/core';
+import type { TaskMetadataField, TasksMdWriteResult } from '../types';
 
+// Each operation takes the version the caller's tasks were read from, so edits made to
+// TASKS.md since then are merged instead of overwritten
 export function useTasksMdOperations(projectPath: string | undefined) {
   const addTask = useCallback(
-    async (task: { subject: string; description?: string; column: string }) => {
+    async (task: { subject: string; description?: string; column: string }, baseVersion?: string) => {
       if (!projectPath) {
         throw new Error('No project path provided');
       }
-      await invoke('add_task_to_tasks_md', {
+      return invoke<TasksMdWriteResult>('add_task_to_tasks_md', {
         projectPath,
         subject: task.subject,
         description: task.description,
         column: task.column,
+        baseVersion,
       });
     },
     [projectPath]
   );
 
   const updateTask = useCallback(
-    async (oldSubject: string, newSubject: string, newDescription?: string) => {
+    async (
+      oldSubject: string,
+      newSubject: string,
+      newDescription?: string,
+      baseVersion?: string,
+      clear?: TaskMetadataField[]
+    ) => {
       if (!projectPath) {
         throw new Error('No project path provided');
       }
-      await invoke('update_task_in_tasks_md', {
+      return invoke<TasksMdWriteResult>('update_task_in_tasks_md', {
         projectPath,
         oldSubject,
         newSubject,
         newDescription,
+        clear,
+        baseVersion,
       });
     },
     [projectPath]
   );
 
   const deleteTask = useCallback(
-    async (taskSubject: string) => {
+    async (taskSubject: string, baseVersion?: string) => {
       if (!projectPath) {
         throw new Error('No project path provided');
       }
-      await invoke('delete_task_from_tasks_md', {
+      return invoke<TasksMdWriteResult>('delete_task_from_tasks_md', {
         projectPath,
         taskSubject,
+        baseVersion,
       });
     },
     [projectPath]
   );
 
-  return { addTask, updateTask, deleteTask };
+  const moveTask = useCallback(
+    async (taskSubject: string, newColumn: string, baseVersion?: string) => {
+      if (!projectPath) {
+        throw new Error('No project path provided');
+      }
+      return invoke<TasksMdWriteResult>('move_task_in_tasks_md', {
+        projectPath,
+        taskSubject,
+        newColumn,
+        baseVersion,
+      });
+    },
+    [projectPath]
+  );
+
+  return { addTask, updateTask, deleteTask, moveTask };
 }
@@ -2,12 +2,7 @@ import { useEffect, useCallback } from 'react';
 import { invoke } from '@tauri-apps/api/core';
 import { listen } from '@tauri-apps/api/event';
 import { useAppStore } from '../stores/appStore';
-import type { TasksMdTask } from '../types';
-
-interface TasksMdChangedPayload {
-  projectId: string;
-  projectPath: string;
-}
+import type { TasksMdChange, TasksMdSnapshot } from '../types';
 
 export function useTasksMdWatcher() {
   const selectedProjectId = useAppStore((s) => s.selectedProjectId);
@@ -16,10 +11,10 @@ export function useTasksMdWatcher() {
 
   const loadTasksMd = useCallback(async (projectId: string, projectPath: string) => {
     try {
-      const tasks = await invoke<TasksMdTask[]>('read_tasks_md', {
+      const snapshot = await invoke<TasksMdSnapshot>('read_tasks_md_snapshot', {
         projectPath,
       });
-      setTasksMdTasks(projectId, tasks);
+      setTasksMdTasks(projectId, snapshot.tasks, snapshot.version);
     } catch (error) {
       console.error('Failed to load TASKS.md:', error);
       setTasksMdTasks(projectId, []);
@@ -56,10 +51,9 @@ export function useTasksMdWatcher() {
 
   // Listen for file change events
   useEffect(() => {
-    const unlisten = listen<TasksMdChangedPayload>('tasks-md-changed', (event) => {
-      const { projectId, projectPath } = event.payload;
-      // Debounce by checking if this is the currently selected project
-      if (projectId === selectedProjectId) {
+    const unlisten = listen<TasksMdChange>('tasks-md-changed', (event) => {
+      const { projectId, projectPath, board } = event.payload;
+      if (projectId === selectedProjectId && board === 'TASKS.md') {
         loadTasksMd(projectId, projectPath);
       }
     });
@@ -34,6 +34,9 @@ interface AppState {
   // TASKS.md tasks per project
   tasksMdTasks: Record<string, TasksMdTask[]>;
 
+  // Version of the TASKS.md content those tasks were read from, sent back as baseVersion
+  tasksMdVersions: Record<string, string>;
+
   // Terminal state per project (projectId -> array of terminalIds)
   // Not persisted - terminals don't survive app restart
   terminalIds: Record<string, string[]>;
@@ -85,7 +88,7 @@ interface AppState {
 
   setTaskProgress: (projectId: string, progress: TaskProgress) => void;
   setClaudeTaskProgress: (projectId: string, progress: ClaudeTaskProgress) => void;
-  setTasksMdTasks: (projectId: string, tasks: TasksMdTask[]) => void;
+  setTasksMdTasks: (projectId: string, tasks: TasksMdTask[], version?: string) => void;
 
   // Kanban actions
   setTaskOverlay: (projectId: string, taskId: string, overlay: Partial<TaskOverlay>) => void;
@@ -111,6 +114,7 @@ export const useAppStore = create<AppState>()(
       taskProgress: {},
       claudeTaskProgress: {},
       tasksMdTasks: {},
+      tasksMdVersions: {},
       terminalIds: {},
       terminalNames: {},
       settings: {
@@ -121,6 +125,10 @@ export const useAppStore = create<AppState>()(
         enableSound: true,
         notificationSound: 'default',
         notifyOnlyWhenUnfocused: true,
+        autoBlockTasks: false,
+        dispatchPromptTemplate: 'Work on this task from TASKS.md: {subject}\n\n{description}\n\n{subtasks}',
+        commitAdvanceColumn: null,
+        dueReminders: true,
       },
       claudeProcessStates: {},
       projectSettings: {},
@@ -300,12 +308,16 @@ export const useAppStore = create<AppState>()(
           },
         })),
 
-      setTasksMdTasks: (projectId, tasks) =>
+      setTasksMdTasks: (projectId, tasks, version) =>
         set((state) => ({
           tasksMdTasks: {
             ...state.tasksMdTasks,
             [projectId]: tasks,
           },
+          tasksMdVersions:
+            version === undefined
+              ? state.tasksMdVersions
+              : { ...state.tasksMdVersions, [projectId]: version },
         })),
 
       // Kanban actions
@@ -4,11 +4,45 @@ export interface Project {
   path: string;
   hasActiveProcess: boolean;
   taskProgress?: TaskProgress;
+  progressGlobs?: string[];
+  archivePolicy?: ArchivePolicy;
+  boards?: BoardConfig[];
+}
+
+// Board files of a project
+export interface ColumnConfig {
+  id: string;
+  heading: string;
+}
+
+// How a board file is read; 'auto' is only accepted by set_board_source
+export type BoardLayout = 'auto' | 'tasks_md' | 'headings' | 'checklist';
+
+export interface BoardConfig {
+  file: string;
+  layout?: Exclude<BoardLayout, 'auto'>;
+  name?: string;
+  columns?: ColumnConfig[];
+}
+
+export interface BoardColumn {
+  id: string;
+  title: string;
+}
+
+export interface Board {
+  file: string;
+  layout: Exclude<BoardLayout, 'auto'>;
+  columns: BoardColumn[];
+  tasks: TasksMdTask[];
+  // Content version, passed back as baseVersion to move_board_task
+  version: string;
 }
 
 export interface TaskProgress {
   total: number;
   completed: number;
+  inProgress?: number;
   percentage: number;
 }
 
@@ -30,6 +64,50 @@ export interface ClaudeTask {
   owner?: string;
 }
 
+// Emitted by the Claude Code task list watcher
+export type ClaudeTaskEventName =
+  | 'claude-task-added'
+  | 'claude-task-started'
+  | 'claude-task-completed'
+  | 'claude-task-updated'
+  | 'claude-task-removed';
+
+export interface ClaudeTaskEvent {
+  event: ClaudeTaskEventName;
+  taskListId: string;
+  task: ClaudeTask;
+  previousStatus: ClaudeTask['status'] | null;
+}
+
+export interface ClaudeTasksLockChanged {
+  taskListId: string;
+  isActive: boolean;
+}
+
+// Dependency graph of a Claude Code task list
+export type TaskGraphFormat = 'json' | 'dot' | 'mermaid';
+
+export interface TaskGraphNode {
+  id: string;
+  subject: string;
+  status: ClaudeTask['status'];
+}
+
+export interface TaskGraphEdge {
+  from: string;
+  to: string;
+}
+
+export interface TaskGraph {
+  nodes: TaskGraphNode[];
+  edges: TaskGraphEdge[];
+  cycles: string[][];
+  dangling: { taskId: string; reference: string }[];
+  criticalPath: string[];
+  unblocked: string[];
+  rendered: string | null;
+}
+
 export interface ClaudeTaskProgress {
   total: number;
   pending: number;
@@ -64,8 +142,337 @@ export interface TasksMdTask {
   subject: string;
   description?: string;
   column: 'backlog' | 'blocked' | 'in_progress' | 'review' | 'done';
+  epic?: string | null;
   completed: boolean;
   lineNumber: number;
+  tags?: string[];
+  priority?: 'low' | 'medium' | 'high' | 'critical';
+  assignee?: string;
+  estimate?: string;
+  due?: string | null;
+  stableId?: string;
+  blockedBy?: string[];
+  comments?: TaskComment[];
+  subtasks?: TasksMdSubtask[];
+  progress?: TaskProgress;
+}
+
+export interface TasksMdSubtask {
+  id: string;
+  parentId: string;
+  subject: string;
+  completed: boolean;
+  lineNumber: number;
+  subtasks: TasksMdSubtask[];
+  // Where the subtask sits among the description lines; new subtasks follow the one before
+  position?: number;
+}
+
+// Filter accepted by read_tasks_md
+// A note on a task, stored as a `> [!comment]` block in TASKS.md
+export interface TaskComment {
+  author: string;
+  authorKind: 'human' | 'agent';
+  timestamp: number;
+  body: string;
+  // Where the comment sits among the description lines; new comments go after the description
+  position?: number;
+}
+
+export interface TasksMdFilter {
+  tags?: string[];
+  priority?: string;
+  assignee?: string;
+  column?: string;
+  epic?: string;
+  text?: string;
+  completed?: boolean;
+}
+
+// Completion of an epic (### heading) across the columns of a board
+export interface EpicProgress {
+  epic: string;
+  total: number;
+  completed: number;
+  percentage: number;
+  columns: Record<string, number>;
+}
+
+// Result of query_tasks: a TASKS.md task tagged with its project
+export interface ProjectTask extends TasksMdTask {
+  projectId: string;
+  projectName: string;
+  projectPath: string;
+  // Board file the task is on
+  board: string;
+}
+
+// Result of upcoming_tasks: an open task with a due date; daysLeft is negative when overdue
+export interface UpcomingTask extends ProjectTask {
+  daysLeft: number;
+}
+
+// TASKS.md content version, passed back as baseVersion on mutations
+export interface TasksMdSnapshot {
+  tasks: TasksMdTask[];
+  version: string;
+}
+
+export interface TaskMergeConflict {
+  subject: string;
+  base?: TasksMdTask;
+  ours?: TasksMdTask;
+  theirs?: TasksMdTask;
+}
+
+// Metadata update_task_in_tasks_md can remove through its `clear` list
+export type TaskMetadataField = 'tags' | 'priority' | 'assignee' | 'estimate' | 'due' | 'blockedBy';
+
+// Result of every TASKS.md mutation; nothing was written when conflicts is non-empty
+export interface TasksMdWriteResult {
+  version: string;
+  merged: boolean;
+  conflicts: TaskMergeConflict[];
+}
+
+// Result of check_tasks_md_dependencies
+export interface DependencyReport {
+  missing: { taskSubject: string; reference: string }[];
+  // References whose subject slug matches more than one task
+  ambiguous: { taskSubject: string; reference: string }[];
+  cycles: string[][];
+  startable: string[];
+  blocked: string[];
+}
+
+// Result of sync_claude_tasks
+export interface SyncConflict {
+  claudeTaskId: string;
+  subject: string;
+  kind: 'status' | 'card_missing' | 'claude_task_missing';
+  cardColumn?: string;
+  claudeStatus?: string;
+}
+
+export interface SyncReport {
+  createdClaudeTasks: string[];
+  createdCards: string[];
+  updatedClaudeTasks: string[];
+  movedCards: string[];
+  conflicts: SyncConflict[];
+}
+
+// Task history and metrics (timestamps in ms since epoch)
+export interface TaskTransition {
+  task: string;
+  subject: string;
+  from?: string;
+  to?: string;
+  timestamp: number;
+}
+
+export interface TaskDuration {
+  task: string;
+  subject: string;
+  startedAt: number;
+  finishedAt: number;
+  durationMs: number;
+}
+
+export interface WeeklyThroughput {
+  weekStart: number;
+  completed: number;
+}
+
+export interface ColumnTime {
+  column: string;
+  totalMs: number;
+  averageMs: number;
+  visits: number;
+}
+
+// Checkbox progress per file and per heading
+export interface HeadingProgress {
+  heading: string;
+  level: number;
+  lineNumber: number;
+  total: number;
+  completed: number;
+  inProgress: number;
+}
+
+export interface FileProgress {
+  path: string;
+  total: number;
+  completed: number;
+  inProgress: number;
+  percentage: number;
+  headings: HeadingProgress[];
+}
+
+export interface ProgressBreakdown {
+  total: number;
+  completed: number;
+  inProgress: number;
+  percentage: number;
+  files: FileProgress[];
+}
+
+// Archive of completed tasks (TASKS.archive.md)
+export interface ArchivePolicy {
+  olderThanDays?: number | null;
+  keepRecent?: number | null;
+}
+
+export interface ArchivedTask {
+  task: TasksMdTask;
+  month: string;
+  // null when the task was completed before history was recorded
+  completedOn: string | null;
+}
+
+export interface ArchiveResult {
+  archived: ArchivedTask[];
+  write: TasksMdWriteResult | null;
+}
+
+// Git commits linked to tasks
+export interface TaskCommit {
+  hash: string;
+  shortHash: string;
+  subject: string;
+  author: string;
+  timestamp: number;
+  onDefaultBranch: boolean;
+}
+
+export interface TaskCommits {
+  task: string;
+  subject: string;
+  commits: TaskCommit[];
+}
+
+export interface CommitAdvanceResult {
+  advanced: string[];
+  write: TasksMdWriteResult | null;
+}
+
+// Board file watcher events
+export interface TasksMdChange {
+  projectId: string;
+  projectPath: string;
+  board: string;
+  version: string;
+  added: TasksMdTask[];
+  updated: TasksMdTask[];
+  removed: TasksMdTask[];
+}
+
+export interface TasksMdWatchStopped {
+  projectId: string;
+  projectPath: string;
+}
+
+// TASKS.md lint / format
+export type TasksMdDiagnosticCode =
+  | 'duplicate-subject'
+  | 'orphan-task'
+  | 'unknown-heading'
+  | 'unclosed-fence'
+  | 'completed-outside-done'
+  | 'indentation'
+  | 'discarded-line'
+  | 'renamed-section';
+
+export interface TasksMdDiagnostic {
+  line: number;
+  severity: 'error' | 'warning';
+  code: TasksMdDiagnosticCode;
+  message: string;
+  fixable: boolean;
+}
+
+export interface FormatResult {
+  content: string;
+  fixed: TasksMdDiagnostic[];
+  remaining: TasksMdDiagnostic[];
+  unfixable: TasksMdDiagnostic[];
+  write: TasksMdWriteResult | null;
+}
+
+// Board import / export
+export type TaskExchangeFormat = 'csv' | 'json' | 'todotxt' | 'github';
+
+// An imported task left out because the board can't store its column or metadata
+export interface InvalidImport {
+  subject: string;
+  reason: string;
+}
+
+export interface ImportPreview {
+  tasks: TasksMdTask[];
+  duplicates: string[];
+  invalid: InvalidImport[];
+  result: TasksMdWriteResult | null;
+}
+
+// Task templates and recurring tasks
+export interface TaskTemplate {
+  id: string;
+  name: string;
+  subject: string;
+  description?: string | null;
+  subtasks: string[];
+  column: string;
+  seed: boolean;
+}
+
+export interface RecurrenceRule {
+  id: string;
+  templateId: string;
+  every: 'day' | 'week' | 'month';
+  weekday?: 'mon' | 'tue' | 'wed' | 'thu' | 'fri' | 'sat' | 'sun' | null;
+  dayOfMonth?: number | null;
+  column?: string | null;
+  // Board file the tasks are added to; TASKS.md when unset
+  board?: string | null;
+  createdAt: number;
+  lastRun?: number | null;
+}
+
+export interface TemplateList {
+  global: TaskTemplate[];
+  project: TaskTemplate[];
+  recurrences: RecurrenceRule[];
+}
+
+// Result of sending a board task to the project's agent terminal
+export interface DispatchResult {
+  terminalId: string;
+  spawned: boolean;
+  prompt: string;
+  write: TasksMdWriteResult;
+}
+
+// Undo / redo journal of board mutations
+export interface TaskOperation {
+  kind: 'move' | 'add' | 'update' | 'delete';
+  subject: string;
+  before: TasksMdTask | null;
+  after: TasksMdTask | null;
+  index: number;
+  timestamp: number;
+  board?: string;
+}
+
+export interface ProjectJournal {
+  undo: TaskOperation[];
+  redo: TaskOperation[];
+}
+
+export interface JournalResult {
+  operation: TaskOperation;
+  write: TasksMdWriteResult;
 }
 
 // Per-project settings (keyed by project path)
@@ -82,6 +489,10 @@ export interface Settings {
   enableSound: boolean;
   notificationSound: string;
   notifyOnlyWhenUnfocused: boolean;
+  autoBlockTasks?: boolean;
+  dispatchPromptTemplate?: string;
+  commitAdvanceColumn?: 'review' | 'done' | null;
+  dueReminders?: boolean;
 }
 
 // Claude process state for attention tracking